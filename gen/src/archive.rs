@@ -0,0 +1,178 @@
+use crate::{Date, Time, Value, phf};
+use std::io::Write;
+
+// The layout is documented in the `archive` module of `const-config`,
+// which reads it.
+const MAGIC: &[u8; 8] = b"CCFGARCH";
+const VERSION: u32 = 1;
+const SLOT_LEN: usize = 16;
+const OBJECT_ENTRY_LEN: usize = 24;
+const MAP_ENTRY_LEN: usize = 32;
+
+const NULL: u8 = 0;
+const BOOL: u8 = 1;
+const UINT: u8 = 2;
+const INT: u8 = 3;
+const UINT128: u8 = 4;
+const INT128: u8 = 5;
+const FLOAT: u8 = 6;
+const DATE: u8 = 7;
+const TIME: u8 = 8;
+const DATETIME: u8 = 9;
+const STR: u8 = 10;
+const BYTES: u8 = 11;
+const ARRAY: u8 = 12;
+const OBJECT: u8 = 13;
+const MAP: u8 = 14;
+
+/// Generates a binary archive of `value`, which `const_config::ArchivedValue`
+/// reads at runtime with the same API as a generated `Value`.
+///
+/// Fails if the archive would be larger than 4 GiB.
+pub fn generate_archive<O>(mut value: Value, mut output: O) -> std::io::Result<()>
+where
+    O: Write,
+{
+    value.dedup_all_keys();
+    let mut archive = Archive(Vec::new());
+    archive.0.extend_from_slice(MAGIC);
+    archive.0.extend_from_slice(&VERSION.to_le_bytes());
+    archive.0.extend_from_slice(&[0; 4]);
+    let root = archive.reserve(SLOT_LEN, 8);
+    archive.value(&value, root)?;
+    output.write_all(&archive.0)
+}
+
+struct Archive(Vec<u8>);
+
+impl Archive {
+    /// Appends `len` zero bytes aligned to `align`, returning their offset.
+    fn reserve(&mut self, len: usize, align: usize) -> usize {
+        self.0.resize(self.0.len().next_multiple_of(align), 0);
+        let offset = self.0.len();
+        self.0.resize(offset + len, 0);
+        offset
+    }
+
+    fn offset(offset: usize) -> std::io::Result<u32> {
+        u32::try_from(offset).map_err(|_| std::io::Error::other("archive is larger than 4 GiB"))
+    }
+
+    fn put(&mut self, at: usize, bytes: &[u8]) {
+        self.0[at..at + bytes.len()].copy_from_slice(bytes);
+    }
+
+    /// Appends `bytes`, returning their offset.
+    fn bytes(&mut self, bytes: &[u8], align: usize) -> std::io::Result<u32> {
+        let offset = self.reserve(bytes.len(), align);
+        self.put(offset, bytes);
+        Self::offset(offset)
+    }
+
+    fn slot(&mut self, at: usize, tag: u8, flag: u8, extra: i16, a: u32, b: u64) {
+        self.0[at] = tag;
+        self.0[at + 1] = flag;
+        self.put(at + 2, &extra.to_le_bytes());
+        self.put(at + 4, &a.to_le_bytes());
+        self.put(at + 8, &b.to_le_bytes());
+    }
+
+    fn phf(&mut self, at: usize, phf: &phf::Phf) {
+        for (idx, param) in phf.params.iter().chain(&phf.values).enumerate() {
+            self.put(at + idx * 4, &param.to_le_bytes());
+        }
+    }
+
+    /// Writes `value` to the slot at `at`, appending everything it refers
+    /// to.
+    fn value(&mut self, value: &Value, at: usize) -> std::io::Result<()> {
+        match value {
+            Value::Null => self.slot(at, NULL, 0, 0, 0, 0),
+            Value::Bool(value) => self.slot(at, BOOL, *value as u8, 0, 0, 0),
+            Value::UInt(value) => match u64::try_from(*value) {
+                Ok(value) => self.slot(at, UINT, 0, 0, 0, value),
+                Err(_) => {
+                    let offset = self.bytes(&value.to_le_bytes(), 8)?;
+                    self.slot(at, UINT128, 0, 0, offset, 0);
+                }
+            },
+            Value::Int(value) => match i64::try_from(*value) {
+                Ok(value) => self.slot(at, INT, 0, 0, 0, value as u64),
+                Err(_) => {
+                    let offset = self.bytes(&value.to_le_bytes(), 8)?;
+                    self.slot(at, INT128, 0, 0, offset, 0);
+                }
+            },
+            Value::Float(value) => self.slot(at, FLOAT, 0, 0, 0, value.to_bits()),
+            Value::Date(date) => self.slot(at, DATE, 0, 0, pack_date(date), 0),
+            Value::Time(time) => {
+                let packed = pack_time(time);
+                self.slot(at, TIME, 0, 0, packed as u32, time.nanosecond as u64);
+            }
+            Value::DateTime(dt) => {
+                let date = pack_date(&dt.date);
+                let time = pack_time(&dt.time);
+                match dt.offset {
+                    Some(offset) => self.slot(at, DATETIME, 1, offset, date, time),
+                    None => self.slot(at, DATETIME, 0, 0, date, time),
+                }
+            }
+            Value::Str(s) => {
+                let offset = self.bytes(s.as_bytes(), 1)?;
+                self.slot(at, STR, 0, 0, offset, s.len() as u64);
+            }
+            Value::Bytes(b) => {
+                let offset = self.bytes(b, 1)?;
+                self.slot(at, BYTES, 0, 0, offset, b.len() as u64);
+            }
+            Value::Array(array) => {
+                let start = self.reserve(array.len() * SLOT_LEN, 8);
+                self.slot(at, ARRAY, 0, 0, Self::offset(start)?, array.len() as u64);
+                for (idx, elem) in array.iter().enumerate() {
+                    self.value(elem, start + idx * SLOT_LEN)?;
+                }
+            }
+            Value::Object(entries) => {
+                let len = entries.len();
+                let start = self.reserve(len * (OBJECT_ENTRY_LEN + 8), 8);
+                self.slot(at, OBJECT, 0, 0, Self::offset(start)?, len as u64);
+                self.phf(
+                    start + len * OBJECT_ENTRY_LEN,
+                    &phf::Phf::build_object(entries),
+                );
+                for (idx, (key, value)) in entries.iter().enumerate() {
+                    let entry = start + idx * OBJECT_ENTRY_LEN;
+                    let offset = self.bytes(key.as_bytes(), 1)?;
+                    self.put(entry, &offset.to_le_bytes());
+                    self.put(entry + 4, &Self::offset(key.len())?.to_le_bytes());
+                    self.value(value, entry + 8)?;
+                }
+            }
+            Value::Map(entries) => {
+                let len = entries.len();
+                let start = self.reserve(len * (MAP_ENTRY_LEN + 8), 8);
+                self.slot(at, MAP, 0, 0, Self::offset(start)?, len as u64);
+                self.phf(start + len * MAP_ENTRY_LEN, &phf::Phf::build_map(entries));
+                for (idx, (key, value)) in entries.iter().enumerate() {
+                    let entry = start + idx * MAP_ENTRY_LEN;
+                    self.value(key, entry)?;
+                    self.value(value, entry + SLOT_LEN)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+fn pack_date(date: &Date) -> u32 {
+    date.year as u32 | (date.month as u32) << 16 | (date.day as u32) << 24
+}
+
+/// Packs the hour, minute and second into the low bytes, and the
+/// nanosecond from bit 32.
+fn pack_time(time: &Time) -> u64 {
+    time.hour as u64
+        | (time.minute as u64) << 8
+        | (time.second as u64) << 16
+        | (time.nanosecond as u64) << 32
+}
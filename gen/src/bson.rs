@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,13 +10,28 @@ pub enum Error {
     Bson(#[from] bson::de::Error),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known. BSON errors do
+    /// not report an offset, so this is always `None`.
+    pub fn location(&self) -> Option<Location> {
+        None
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    Ok(bson::from_reader(input)?)
+}
+
 pub fn generate<I, O>(input: I, output: O) -> Result<()>
 where
     I: Read,
     O: Write,
 {
-    crate::generate(bson::from_reader(input)?, output)?;
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
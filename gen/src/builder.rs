@@ -0,0 +1,305 @@
+use crate::{
+    ArrayMerge, DetectError, Emit, EmitError, ExpandKeysError, Format, InterpolateError, Location,
+    ProfileError, SchemaError, Value,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Error produced by [`Builder::build`].
+#[derive(Debug, Error)]
+pub enum BuildError {
+    #[error("no inputs were given")]
+    NoInputs,
+    #[error("`OUT_DIR` is not set, is this running in a build script?")]
+    NoOutDir,
+    #[error("{}: {source}", path.display())]
+    Detect { path: PathBuf, source: DetectError },
+    #[error("{}: {source}", path.display())]
+    IO {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{}{}: {source}", path.display(), location_suffix(.location))]
+    Parse {
+        path: PathBuf,
+        location: Option<Location>,
+        source: Box<crate::Error>,
+    },
+    #[error(transparent)]
+    Profile(#[from] ProfileError),
+    #[error(transparent)]
+    Interpolate(#[from] InterpolateError),
+    #[error(transparent)]
+    ExpandKeys(#[from] ExpandKeysError),
+    #[error(transparent)]
+    Schema(#[from] SchemaError),
+    #[error(transparent)]
+    Emit(#[from] EmitError),
+}
+
+fn location_suffix(location: &Option<Location>) -> String {
+    match location {
+        Some(location) => format!(":{}:{}", location.line, location.column),
+        None => String::new(),
+    }
+}
+
+/// Generates Rust code for config files from a build script, as an
+/// alternative to the `include_*` macros.
+///
+/// Every input is parsed, and later inputs are deep-merged on top of
+/// earlier ones. The generated code is written to a file in `OUT_DIR`, to
+/// be included with `include!`. Cargo is told to rerun the build script
+/// when any input, the schema, or an interpolated environment variable
+/// changes.
+///
+/// ```no_run
+/// // build.rs
+/// const_config_gen::Builder::new()
+///     .input("config/app.toml")
+///     .input("config/local.json")
+///     .output("config.rs")
+///     .emit(const_config_gen::Emit::Module)
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// ```ignore
+/// // src/main.rs
+/// mod config {
+///     include!(concat!(env!("OUT_DIR"), "/config.rs"));
+/// }
+/// ```
+///
+/// With [`Emit::Archive`] the output is a binary archive instead, which
+/// can be embedded with `include_bytes!` or shipped beside the binary.
+#[derive(Debug, Clone)]
+pub struct Builder {
+    inputs: Vec<(PathBuf, Option<Format>)>,
+    out_dir: Option<PathBuf>,
+    output: PathBuf,
+    emit: Emit,
+    name: String,
+    arrays: ArrayMerge,
+    profile: Option<String>,
+    interpolate: bool,
+    timestamps: bool,
+    expand_keys: bool,
+    coerce: bool,
+    schema: Option<PathBuf>,
+    cargo: bool,
+}
+
+impl Default for Builder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Builder {
+    /// A builder with no inputs that writes a `const` expression to
+    /// `const_config.rs`.
+    pub fn new() -> Self {
+        Self {
+            inputs: Vec::new(),
+            out_dir: None,
+            output: "const_config.rs".into(),
+            emit: Emit::Expr,
+            name: "CONFIG".into(),
+            arrays: ArrayMerge::Replace,
+            profile: None,
+            interpolate: false,
+            timestamps: false,
+            expand_keys: false,
+            coerce: false,
+            schema: None,
+            cargo: true,
+        }
+    }
+
+    /// Adds an input, with its format detected by [`Format::detect`].
+    pub fn input<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.inputs.push((path.as_ref().into(), None));
+        self
+    }
+
+    /// Adds an input in the given format.
+    pub fn input_with_format<P: AsRef<Path>>(&mut self, path: P, format: Format) -> &mut Self {
+        self.inputs.push((path.as_ref().into(), Some(format)));
+        self
+    }
+
+    /// Sets the name of the generated file. Relative paths are relative to
+    /// `OUT_DIR`. Defaults to `const_config.rs`.
+    pub fn output<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.output = path.as_ref().into();
+        self
+    }
+
+    /// Sets the directory the output is written to instead of `OUT_DIR`.
+    pub fn out_dir<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.out_dir = Some(path.as_ref().into());
+        self
+    }
+
+    /// Sets the kind of code to generate. Defaults to [`Emit::Expr`].
+    pub fn emit(&mut self, emit: Emit) -> &mut Self {
+        self.emit = emit;
+        self
+    }
+
+    /// Sets the name of the item generated with [`Emit::Const`]. Defaults
+    /// to `CONFIG`.
+    pub fn const_name<S: Into<String>>(&mut self, name: S) -> &mut Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets how arrays are merged when there are several inputs.
+    pub fn arrays(&mut self, arrays: ArrayMerge) -> &mut Self {
+        self.arrays = arrays;
+        self
+    }
+
+    /// Selects a profile, see [`Value::select_profile`].
+    pub fn profile<S: Into<String>>(&mut self, profile: S) -> &mut Self {
+        self.profile = Some(profile.into());
+        self
+    }
+
+    /// Expands environment variables in string values, see
+    /// [`Value::interpolate_env`].
+    pub fn interpolate(&mut self, interpolate: bool) -> &mut Self {
+        self.interpolate = interpolate;
+        self
+    }
+
+    /// Turns strings holding a timestamp into dates and times, see
+    /// [`Value::detect_timestamps`].
+    pub fn timestamps(&mut self, timestamps: bool) -> &mut Self {
+        self.timestamps = timestamps;
+        self
+    }
+
+    /// Turns dotted keys of every input into nested objects before the
+    /// inputs are merged, see [`Value::expand_dotted_keys`].
+    pub fn expand_keys(&mut self, expand_keys: bool) -> &mut Self {
+        self.expand_keys = expand_keys;
+        self
+    }
+
+    /// Turns strings holding numbers and booleans into numbers and
+    /// booleans, see [`Value::coerce_scalars`].
+    pub fn coerce(&mut self, coerce: bool) -> &mut Self {
+        self.coerce = coerce;
+        self
+    }
+
+    /// Validates the config against a JSON Schema, see [`Value::validate`].
+    /// The format of the schema is detected by [`Format::detect`].
+    pub fn schema<P: AsRef<Path>>(&mut self, path: P) -> &mut Self {
+        self.schema = Some(path.as_ref().into());
+        self
+    }
+
+    /// Whether to print `cargo:rerun-if-changed` and
+    /// `cargo:rerun-if-env-changed` directives. Enabled by default.
+    pub fn cargo_directives(&mut self, cargo: bool) -> &mut Self {
+        self.cargo = cargo;
+        self
+    }
+
+    /// Generates the code, returning the path of the written file.
+    pub fn build(&self) -> Result<PathBuf, BuildError> {
+        if self.inputs.is_empty() {
+            return Err(BuildError::NoInputs);
+        }
+
+        let mut merged: Option<Value> = None;
+        for (path, format) in &self.inputs {
+            let mut value = self.parse(path, *format)?;
+            if self.expand_keys {
+                value.expand_dotted_keys()?;
+            }
+            match &mut merged {
+                Some(merged) => merged.merge(value, self.arrays),
+                None => merged = Some(value),
+            }
+        }
+        let mut value = merged.unwrap();
+
+        if let Some(profile) = &self.profile {
+            value.select_profile(Some(profile))?;
+        }
+        if self.interpolate {
+            value.interpolate_env(|var| {
+                if self.cargo {
+                    println!("cargo:rerun-if-env-changed={var}");
+                }
+                std::env::var(var).ok()
+            })?;
+        }
+        if self.coerce {
+            value.coerce_scalars();
+        }
+        if self.timestamps {
+            value.detect_timestamps();
+        }
+        if let Some(schema) = &self.schema {
+            value.validate(&self.parse(schema, None)?)?;
+        }
+
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .ok_or(BuildError::NoOutDir)?
+                .into(),
+        };
+        let path = out_dir.join(&self.output);
+        let io = |source| BuildError::IO {
+            path: path.clone(),
+            source,
+        };
+
+        let mut output = Vec::new();
+        match self.emit {
+            Emit::Expr => crate::generate(value, &mut output).map_err(io)?,
+            Emit::Const => {
+                crate::generate_const_item(value, &self.name, &mut output).map_err(io)?
+            }
+            Emit::Module => crate::generate_module(value, &mut output)?,
+            Emit::Archive => crate::generate_archive(value, &mut output).map_err(io)?,
+        }
+        // Leave the file untouched if nothing changed, so that cargo does
+        // not rebuild the crate for nothing.
+        if std::fs::read(&path).ok().as_ref() != Some(&output) {
+            std::fs::File::create(&path)
+                .and_then(|mut file| file.write_all(&output))
+                .map_err(io)?;
+        }
+        Ok(path)
+    }
+
+    fn parse(&self, path: &Path, format: Option<Format>) -> Result<Value, BuildError> {
+        if self.cargo {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+        let data = std::fs::read(path).map_err(|source| BuildError::IO {
+            path: path.into(),
+            source,
+        })?;
+        let format = match format {
+            Some(format) => format,
+            None => Format::detect(Some(path), &data).map_err(|source| BuildError::Detect {
+                path: path.into(),
+                source,
+            })?,
+        };
+        format.parse(&data[..]).map_err(|source| BuildError::Parse {
+            path: path.into(),
+            location: source.location(),
+            source: Box::new(source),
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,13 +10,36 @@ pub enum Error {
     Cbor(#[from] ciborium::de::Error<std::io::Error>),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Cbor(err) => match err {
+                ciborium::de::Error::Syntax(offset)
+                | ciborium::de::Error::Semantic(Some(offset), _) => {
+                    Some(Location::from_byte_offset(*offset))
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    Ok(ciborium::from_reader(input)?)
+}
+
 pub fn generate<I, O>(input: I, output: O) -> Result<()>
 where
     I: Read,
     O: Write,
 {
-    crate::generate(ciborium::from_reader(input)?, output)?;
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
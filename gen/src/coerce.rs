@@ -0,0 +1,85 @@
+use crate::Value;
+
+impl Value {
+    /// Turns string values holding a number or a boolean into
+    /// [`Value::UInt`], [`Value::Int`], [`Value::Float`] or [`Value::Bool`],
+    /// for formats like INI or dotenv where every value is a string.
+    ///
+    /// Recognized are `true` and `false`, integers like `42` or `-7`, and
+    /// decimal floats like `0.5` or `-1.5e3`, each without surrounding
+    /// whitespace. Integers too large for 128 bits, numbers with leading
+    /// zeros like `0012`, which are more likely codes than numbers, floats
+    /// too large for `f64` like `1e400`, and anything else, like `0x10`,
+    /// `1_000`, `.5` or `inf`, stay strings. Keys of objects and maps are
+    /// left untouched.
+    pub fn coerce_scalars(&mut self) {
+        match self {
+            Value::Str(s) => {
+                if let Some(value) = parse_scalar(s) {
+                    *self = value;
+                }
+            }
+            Value::Array(array) => array.iter_mut().for_each(Value::coerce_scalars),
+            Value::Object(entries) => entries
+                .iter_mut()
+                .for_each(|(_, value)| value.coerce_scalars()),
+            Value::Map(entries) => entries
+                .iter_mut()
+                .for_each(|(_, value)| value.coerce_scalars()),
+            _ => {}
+        }
+    }
+}
+
+fn parse_scalar(s: &str) -> Option<Value> {
+    match s {
+        "true" => return Some(Value::Bool(true)),
+        "false" => return Some(Value::Bool(false)),
+        _ => {}
+    }
+
+    let digits = s.strip_prefix(['-', '+']).unwrap_or(s);
+    let (int, rest) = digits.split_at(
+        digits
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(digits.len()),
+    );
+    if int.is_empty() || (int.len() > 1 && int.starts_with('0')) {
+        return None;
+    }
+    if rest.is_empty() {
+        return if s.starts_with('-') {
+            s.parse().ok().map(Value::Int)
+        } else {
+            digits.parse().ok().map(Value::UInt)
+        };
+    }
+
+    // Only plain decimal notation, so that Rust's float syntax does not
+    // let through things like `inf`.
+    let mut rest = rest.as_bytes();
+    if let [b'.', tail @ ..] = rest {
+        let len = tail.iter().take_while(|b| b.is_ascii_digit()).count();
+        if len == 0 {
+            return None;
+        }
+        rest = &tail[len..];
+    }
+    if let [b'e' | b'E', tail @ ..] = rest {
+        let tail = tail
+            .strip_prefix(b"-")
+            .or(tail.strip_prefix(b"+"))
+            .unwrap_or(tail);
+        if tail.is_empty() || !tail.iter().all(u8::is_ascii_digit) {
+            return None;
+        }
+        rest = &[];
+    }
+    if !rest.is_empty() {
+        return None;
+    }
+    s.parse()
+        .ok()
+        .filter(|float: &f64| float.is_finite())
+        .map(Value::Float)
+}
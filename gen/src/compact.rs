@@ -0,0 +1,179 @@
+use crate::dedup::Shared;
+use crate::{Date, Time, Value, phf, write_bytes, write_float, write_str};
+use std::io::Write;
+
+/// The names used by compact code, imported at the start of its block.
+const PRELUDE: &str = "use ::const_config::{Date as D,DateTime as DT,Map as M,Number::{Float,Int,UInt},Object as O,Time as T,Value,Value::*};";
+
+/// Generates the same `const` block as [`generate`](crate::generate), but
+/// with short names imported at the start of the block and without
+/// indentation or line breaks.
+///
+/// The code is much smaller, and so faster to compile, which matters for
+/// large inputs. This is what the `include_*` and `from_*` macros use.
+pub fn generate_compact<O>(mut value: Value, mut output: O) -> std::io::Result<()>
+where
+    O: Write,
+{
+    value.dedup_all_keys();
+    let shared = Shared::find(&value);
+    write!(output, "const{{{PRELUDE}")?;
+    for (item, value) in shared.items().iter().enumerate() {
+        write!(output, "const V{item}:Value<'static>=")?;
+        generate_node(value, &mut output, &shared)?;
+        write!(output, ";")?;
+    }
+    generate_impl(&value, &mut output, &shared)?;
+    write!(output, "}}")
+}
+
+fn generate_impl<O>(value: &Value, output: &mut O, shared: &Shared) -> std::io::Result<()>
+where
+    O: Write,
+{
+    match shared.item(value) {
+        Some(item) => write!(output, "V{item}"),
+        None => generate_node(value, output, shared),
+    }
+}
+
+fn generate_node<O>(value: &Value, output: &mut O, shared: &Shared) -> std::io::Result<()>
+where
+    O: Write,
+{
+    match value {
+        Value::Null => write!(output, "Null"),
+        Value::Bool(value) => write!(output, "Bool({value})"),
+        Value::UInt(value) => write!(output, "Number(UInt({value}))"),
+        Value::Int(value) => write!(output, "Number(Int({value}))"),
+        Value::Float(value) => {
+            write!(output, "Number(Float(")?;
+            write_float(output, *value)?;
+            write!(output, "))")
+        }
+        Value::Date(value) => {
+            write!(output, "Date(")?;
+            write_date(output, value)?;
+            write!(output, ")")
+        }
+        Value::Time(value) => {
+            write!(output, "Time(")?;
+            write_time(output, value)?;
+            write!(output, ")")
+        }
+        Value::DateTime(value) => {
+            write!(output, "DateTime(DT{{date:")?;
+            write_date(output, &value.date)?;
+            write!(output, ",time:")?;
+            write_time(output, &value.time)?;
+            match value.offset {
+                Some(offset) => {
+                    write!(output, ",offset: ::core::option::Option::Some({offset})}})")
+                }
+                None => write!(output, ",offset: ::core::option::Option::None}})"),
+            }
+        }
+        Value::Str(value) => {
+            write!(output, "Str(")?;
+            write_str(output, value)?;
+            write!(output, ")")
+        }
+        Value::Bytes(value) => {
+            write!(output, "Bytes(")?;
+            write_bytes(output, value)?;
+            write!(output, ")")
+        }
+        Value::Array(value) => {
+            write!(output, "Array(const{{&[")?;
+            for (idx, elem) in value.iter().enumerate() {
+                if idx != 0 {
+                    write!(output, ",")?;
+                }
+                generate_impl(elem, output, shared)?;
+            }
+            write!(output, "]}})")
+        }
+        Value::Object(value) => {
+            let phf = phf::Phf::build_object(value);
+            write!(output, "Object(O::new(const{{&[")?;
+            for (idx, (key, value)) in value.iter().enumerate() {
+                if idx != 0 {
+                    write!(output, ",")?;
+                }
+                write!(output, "(")?;
+                write_str(output, key)?;
+                write!(output, ",")?;
+                generate_impl(value, output, shared)?;
+                write!(output, ")")?;
+            }
+            write!(output, "]}},")?;
+            write_phf(output, &phf)?;
+            write!(output, "))")
+        }
+        Value::Map(value) => {
+            let phf = phf::Phf::build_map(value);
+            write!(output, "Map(M::new(const{{&[")?;
+            for (idx, (key, value)) in value.iter().enumerate() {
+                if idx != 0 {
+                    write!(output, ",")?;
+                }
+                write!(output, "(")?;
+                generate_impl(key, output, shared)?;
+                write!(output, ",")?;
+                generate_impl(value, output, shared)?;
+                write!(output, ")")?;
+            }
+            write!(output, "]}},")?;
+            write_phf(output, &phf)?;
+            write!(output, "))")
+        }
+    }
+}
+
+fn write_date<O>(output: &mut O, date: &Date) -> std::io::Result<()>
+where
+    O: Write,
+{
+    write!(
+        output,
+        "D{{year:{},month:{},day:{}}}",
+        date.year, date.month, date.day
+    )
+}
+
+fn write_time<O>(output: &mut O, time: &Time) -> std::io::Result<()>
+where
+    O: Write,
+{
+    write!(
+        output,
+        "T{{hour:{},minute:{},second:{},nanosecond:{}}}",
+        time.hour, time.minute, time.second, time.nanosecond
+    )
+}
+
+/// Writes the parameters and values of a perfect hash table as two array
+/// arguments.
+fn write_phf<O>(output: &mut O, phf: &phf::Phf) -> std::io::Result<()>
+where
+    O: Write,
+{
+    write!(output, "const{{&[")?;
+    write_list(output, &phf.params)?;
+    write!(output, "]}},const{{&[")?;
+    write_list(output, &phf.values)?;
+    write!(output, "]}}")
+}
+
+fn write_list<O>(output: &mut O, list: &[u32]) -> std::io::Result<()>
+where
+    O: Write,
+{
+    for (idx, elem) in list.iter().enumerate() {
+        if idx != 0 {
+            write!(output, ",")?;
+        }
+        write!(output, "{elem}")?;
+    }
+    Ok(())
+}
@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,18 +10,38 @@ pub enum Error {
     Csv(#[from] csv::Error),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Csv(err) => err.position().map(|pos| Location {
+                line: pos.line() as usize,
+                column: 1,
+            }),
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
-pub fn generate<I, O>(input: I, output: O) -> Result<()>
+pub fn parse<I>(input: I) -> Result<Value>
 where
     I: Read,
-    O: Write,
 {
     let mut rdr = csv::Reader::from_reader(input);
     let mut rows = Vec::new();
     for row in rdr.deserialize() {
         rows.push(row?);
     }
-    crate::generate(Value::Array(rows), output)?;
+    Ok(Value::Array(rows))
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
@@ -0,0 +1,154 @@
+use crate::Value;
+use std::collections::HashMap;
+
+/// The strings, byte strings, arrays, objects and maps that occur more
+/// than once in a value, which are generated once as `const` items and
+/// referenced from everywhere they occur.
+pub(crate) struct Shared<'a> {
+    /// The item of every occurrence of a shared value, by its address.
+    names: HashMap<*const Value, usize>,
+    /// One occurrence of each shared value, in the order of their items.
+    items: Vec<&'a Value>,
+}
+
+/// A value with its children replaced by their ids, so that structurally
+/// identical values have equal keys.
+#[derive(PartialEq, Eq, Hash)]
+enum Key<'a> {
+    Scalar(String),
+    Str(&'a str),
+    Bytes(&'a [u8]),
+    Array(Vec<usize>),
+    Object(Vec<(&'a str, usize)>),
+    Map(Vec<(usize, usize)>),
+}
+
+struct Node<'a> {
+    value: &'a Value,
+    children: Vec<usize>,
+    /// Whether the value is worth sharing, i.e. is not a scalar.
+    shareable: bool,
+}
+
+#[derive(Default)]
+struct Finder<'a> {
+    ids: HashMap<Key<'a>, usize>,
+    nodes: Vec<Node<'a>>,
+    /// The id of every shareable occurrence, by its address.
+    occurrences: Vec<(*const Value, usize)>,
+}
+
+impl<'a> Finder<'a> {
+    /// Assigns ids to `value` and everything in it, where structurally
+    /// identical values get the same id. Children always get a lower id
+    /// than their parents.
+    fn visit(&mut self, value: &'a Value) -> usize {
+        let (key, children) = match value {
+            Value::Str(s) => (Key::Str(s), Vec::new()),
+            Value::Bytes(b) => (Key::Bytes(b), Vec::new()),
+            Value::Array(array) => {
+                let children: Vec<usize> = array.iter().map(|elem| self.visit(elem)).collect();
+                (Key::Array(children.clone()), children)
+            }
+            Value::Object(entries) => {
+                let entries: Vec<(&str, usize)> = entries
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), self.visit(value)))
+                    .collect();
+                let children = entries.iter().map(|(_, id)| *id).collect();
+                (Key::Object(entries), children)
+            }
+            Value::Map(entries) => {
+                let pairs: Vec<(usize, usize)> = entries
+                    .iter()
+                    .map(|(key, value)| (self.visit(key), self.visit(value)))
+                    .collect();
+                let children = pairs
+                    .iter()
+                    .flat_map(|(key, value)| [*key, *value])
+                    .collect();
+                (Key::Map(pairs), children)
+            }
+            value => (Key::Scalar(format!("{value:?}")), Vec::new()),
+        };
+        let shareable = !matches!(key, Key::Scalar(_));
+        let next = self.nodes.len();
+        let id = *self.ids.entry(key).or_insert(next);
+        if id == next {
+            self.nodes.push(Node {
+                value,
+                children,
+                shareable,
+            });
+        }
+        if shareable {
+            self.occurrences.push((value as *const Value, id));
+        }
+        id
+    }
+}
+
+impl<'a> Shared<'a> {
+    /// Finds the values occurring more than once in `root`.
+    ///
+    /// A value only counts as occurring more than once if it would be
+    /// generated more than once, so the children of a shared value are not
+    /// shared just because their parent is.
+    pub fn find(root: &'a Value) -> Self {
+        let mut finder = Finder::default();
+        let root_id = finder.visit(root);
+
+        // Parents have higher ids than their children, so walking the ids
+        // downwards sees every parent before its children.
+        let mut uses = vec![0usize; finder.nodes.len()];
+        let mut items = Vec::new();
+        uses[root_id] = 1;
+        for id in (0..finder.nodes.len()).rev() {
+            if uses[id] == 0 {
+                continue;
+            }
+            let node = &finder.nodes[id];
+            let generated = if node.shareable && uses[id] > 1 {
+                items.push(id);
+                1
+            } else {
+                uses[id]
+            };
+            for &child in &node.children {
+                uses[child] += generated;
+            }
+        }
+
+        // Number the items children first, so every item only refers to
+        // items defined before it.
+        items.sort_unstable();
+        let mut index = vec![usize::MAX; finder.nodes.len()];
+        for (idx, &id) in items.iter().enumerate() {
+            index[id] = idx;
+        }
+        let names = finder
+            .occurrences
+            .into_iter()
+            .filter(|(_, id)| index[*id] != usize::MAX)
+            .map(|(addr, id)| (addr, index[id]))
+            .collect();
+        Self {
+            names,
+            items: items.into_iter().map(|id| finder.nodes[id].value).collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// The index of the item for `value`, if it is shared.
+    pub fn item(&self, value: &Value) -> Option<usize> {
+        self.names.get(&(value as *const Value)).copied()
+    }
+
+    /// The shared values, by item index.
+    pub fn items(&self) -> &[&'a Value] {
+        &self.items
+    }
+}
@@ -0,0 +1,252 @@
+use crate::{Location, Value};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("{message}")]
+    Syntax { message: String, location: Location },
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Syntax { location, .. } => Some(*location),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Parses a `.env` file into a flat object of strings.
+///
+/// Every line holds a `KEY=value` pair, optionally prefixed by `export`.
+/// Blank lines and lines starting with `#` are skipped. Values can be:
+/// * unquoted, up to the end of the line or a ` #` comment, with trailing
+///   whitespace removed,
+/// * in single quotes, taken literally,
+/// * in double quotes, where `\n`, `\r`, `\t`, `\"`, `\\` and `\$` are
+///   escapes.
+///
+/// Quoted values may span several lines. In unquoted and double quoted
+/// values, `${KEY}` and `$KEY` are replaced by the value of an earlier key
+/// in the file, and it is an error to reference any other key. A key given
+/// more than once keeps its last value.
+///
+/// Everything stays a string. See [`Value::coerce_scalars`] to turn
+/// numbers and booleans into [`Value::UInt`], [`Value::Int`],
+/// [`Value::Float`] and [`Value::Bool`].
+pub fn parse<I>(mut input: I) -> Result<Value>
+where
+    I: Read,
+{
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    let mut parser = Parser {
+        text: &text,
+        pos: 0,
+        entries: Vec::new(),
+    };
+    parser.parse()?;
+    Ok(Value::Object(parser.entries))
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    entries: Vec<(String, Value)>,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, pos: usize, message: impl Into<String>) -> Error {
+        Error::Syntax {
+            message: message.into(),
+            location: Location::from_offset(self.text, pos),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn skip_blanks(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    }
+
+    /// Skips trailing whitespace and an optional comment up to and
+    /// including the end of the line.
+    fn end_of_line(&mut self) -> Result<()> {
+        self.skip_blanks();
+        match self.peek() {
+            None => Ok(()),
+            Some('#') | Some('\n') | Some('\r') => {
+                let rest = self.rest();
+                self.pos += rest.find('\n').map_or(rest.len(), |idx| idx + 1);
+                Ok(())
+            }
+            Some(_) => Err(self.error(self.pos, "expected the end of the line")),
+        }
+    }
+
+    fn key(&mut self) -> Result<&'a str> {
+        let start = self.pos;
+        let rest = self.rest();
+        let len = rest
+            .find(|ch: char| !(ch.is_ascii_alphanumeric() || matches!(ch, '_' | '.' | '-')))
+            .unwrap_or(rest.len());
+        if len == 0 || rest.starts_with(|ch: char| ch.is_ascii_digit()) {
+            return Err(self.error(start, "expected a key"));
+        }
+        self.pos += len;
+        Ok(&self.text[start..start + len])
+    }
+
+    fn parse(&mut self) -> Result<()> {
+        loop {
+            self.skip_blanks();
+            match self.peek() {
+                None => return Ok(()),
+                Some('#') | Some('\n') | Some('\r') => {
+                    self.end_of_line()?;
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            if let Some(rest) = self.rest().strip_prefix("export")
+                && rest.starts_with([' ', '\t'])
+            {
+                self.pos += "export".len();
+                self.skip_blanks();
+            }
+            let key = self.key()?;
+            self.skip_blanks();
+            if self.bump() != Some('=') {
+                return Err(self.error(self.pos, format!("expected `=` after `{key}`")));
+            }
+            self.skip_blanks();
+            let value = match self.peek() {
+                Some('\'') => self.single_quoted()?,
+                Some('"') => self.double_quoted()?,
+                _ => self.unquoted()?,
+            };
+            self.end_of_line()?;
+
+            match self.entries.iter_mut().find(|(k, _)| k == key) {
+                Some((_, existing)) => *existing = Value::Str(value),
+                None => self.entries.push((key.into(), Value::Str(value))),
+            }
+        }
+    }
+
+    fn single_quoted(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.bump();
+        match self.rest().find('\'') {
+            Some(len) => {
+                let value = self.rest()[..len].into();
+                self.pos += len + 1;
+                Ok(value)
+            }
+            None => Err(self.error(start, "unterminated single quoted value")),
+        }
+    }
+
+    fn double_quoted(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.bump();
+        let mut value = String::new();
+        loop {
+            let pos = self.pos;
+            match self.bump() {
+                None => return Err(self.error(start, "unterminated double quoted value")),
+                Some('"') => return Ok(value),
+                Some('\\') => match self.bump() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some(ch @ ('"' | '\\' | '$')) => value.push(ch),
+                    _ => return Err(self.error(pos, "unknown escape sequence")),
+                },
+                Some('$') => self.reference(pos, &mut value)?,
+                Some(ch) => value.push(ch),
+            }
+        }
+    }
+
+    fn unquoted(&mut self) -> Result<String> {
+        let mut value = String::new();
+        loop {
+            let pos = self.pos;
+            match self.peek() {
+                None | Some('\n') | Some('\r') => break,
+                Some('#') if self.text[..pos].ends_with([' ', '\t']) => break,
+                Some('$') => {
+                    self.bump();
+                    self.reference(pos, &mut value)?;
+                }
+                Some(ch) => {
+                    self.bump();
+                    value.push(ch);
+                }
+            }
+        }
+        value.truncate(value.trim_end().len());
+        Ok(value)
+    }
+
+    /// Expands the reference starting with the `$` at `pos` into `value`.
+    /// A `$` not followed by a name is kept as it is.
+    fn reference(&mut self, pos: usize, value: &mut String) -> Result<()> {
+        let name = if self.peek() == Some('{') {
+            let rest = &self.rest()[1..];
+            let Some(len) = rest.find('}') else {
+                return Err(self.error(pos, "unterminated `${`"));
+            };
+            self.pos += len + 2;
+            &rest[..len]
+        } else {
+            let rest = self.rest();
+            let len = rest
+                .find(|ch: char| !(ch.is_ascii_alphanumeric() || ch == '_'))
+                .unwrap_or(rest.len());
+            if len == 0 {
+                value.push('$');
+                return Ok(());
+            }
+            self.pos += len;
+            &rest[..len]
+        };
+        match self.entries.iter().find(|(key, _)| key == name) {
+            Some((_, Value::Str(referenced))) => {
+                value.push_str(referenced);
+                Ok(())
+            }
+            _ => Err(self.error(pos, format!("`{name}` is not defined by an earlier key"))),
+        }
+    }
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
+    Ok(())
+}
@@ -0,0 +1,75 @@
+use crate::Value;
+use thiserror::Error;
+
+/// Error produced by [`Value::expand_dotted_keys`].
+#[derive(Debug, Error)]
+pub enum ExpandKeysError {
+    #[error("`{0}` is both a value and a table")]
+    Conflict(String),
+}
+
+impl Value {
+    /// Turns dotted keys of objects into nested objects, so that
+    /// `server.port = 80` becomes `server = { port = 80 }`.
+    ///
+    /// Keys sharing a prefix end up in the same object, next to any object
+    /// already stored under that prefix. It is an error for a prefix to
+    /// also have a value other than an object, like `server = x` next to
+    /// `server.port = 80`. Keys of maps are left untouched.
+    pub fn expand_dotted_keys(&mut self) -> Result<(), ExpandKeysError> {
+        match self {
+            Value::Array(array) => array.iter_mut().try_for_each(Value::expand_dotted_keys),
+            Value::Map(entries) => entries
+                .iter_mut()
+                .try_for_each(|(_, value)| value.expand_dotted_keys()),
+            Value::Object(entries) => {
+                let mut expanded = Vec::with_capacity(entries.len());
+                for (key, value) in std::mem::take(entries) {
+                    insert(&mut expanded, &key, &key, value)?;
+                }
+                for (_, value) in &mut expanded {
+                    value.expand_dotted_keys()?;
+                }
+                *entries = expanded;
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Inserts `value` under the dotted `key`, which is what is left of
+/// `full_key`.
+fn insert(
+    entries: &mut Vec<(String, Value)>,
+    key: &str,
+    full_key: &str,
+    value: Value,
+) -> Result<(), ExpandKeysError> {
+    let (head, rest) = match key.split_once('.') {
+        Some((head, rest)) => (head, Some(rest)),
+        None => (key, None),
+    };
+    let prefix = &full_key[..full_key.len() - key.len() + head.len()];
+    let existing = entries.iter_mut().find(|(k, _)| k == head);
+    match (existing, rest) {
+        (None, None) => entries.push((head.into(), value)),
+        (None, Some(rest)) => {
+            let mut nested = Vec::new();
+            insert(&mut nested, rest, full_key, value)?;
+            entries.push((head.into(), Value::Object(nested)));
+        }
+        (Some((_, Value::Object(nested))), Some(rest)) => insert(nested, rest, full_key, value)?,
+        (Some((_, Value::Object(nested))), None) => match value {
+            Value::Object(more) => {
+                for (key, value) in more {
+                    let full_key = format!("{prefix}.{key}");
+                    insert(nested, &key, &full_key, value)?;
+                }
+            }
+            _ => return Err(ExpandKeysError::Conflict(prefix.into())),
+        },
+        (Some(_), _) => return Err(ExpandKeysError::Conflict(prefix.into())),
+    }
+    Ok(())
+}
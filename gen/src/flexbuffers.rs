@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,15 +10,30 @@ pub enum Error {
     FlexBuffers(#[from] flexbuffers::DeserializationError),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known. FlexBuffers errors do
+    /// not report an offset, so this is always `None`.
+    pub fn location(&self) -> Option<Location> {
+        None
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
-pub fn generate<I, O>(mut input: I, output: O) -> Result<()>
+pub fn parse<I>(mut input: I) -> Result<Value>
 where
     I: Read,
-    O: Write,
 {
     let mut buf = Vec::new();
     input.read_to_end(&mut buf)?;
-    crate::generate(flexbuffers::from_slice(&buf)?, output)?;
+    Ok(flexbuffers::from_slice(&buf)?)
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
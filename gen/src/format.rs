@@ -0,0 +1,496 @@
+use crate::{Location, Value};
+use clap::ValueEnum;
+use std::io::{Read, Write};
+use thiserror::Error;
+
+/// An input format supported by the generator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ValueEnum)]
+pub enum Format {
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "json")]
+    Json,
+    #[cfg(feature = "yaml")]
+    Yaml,
+    #[cfg(feature = "ron")]
+    Ron,
+    #[cfg(feature = "json5")]
+    Json5,
+    #[cfg(feature = "s-expr")]
+    SExpr,
+    #[cfg(feature = "hjson")]
+    Hjson,
+    #[cfg(feature = "csv")]
+    Csv,
+    #[cfg(feature = "cbor")]
+    Cbor,
+    #[cfg(feature = "msgpack")]
+    Msgpack,
+    #[cfg(feature = "pickle")]
+    Pickle,
+    #[cfg(feature = "bson")]
+    Bson,
+    #[cfg(feature = "flexbuffers")]
+    Flexbuffers,
+    #[cfg(feature = "ini")]
+    Ini,
+    #[cfg(feature = "properties")]
+    Properties,
+    #[cfg(feature = "dotenv")]
+    Dotenv,
+    #[cfg(feature = "xml")]
+    Xml,
+    #[cfg(feature = "kdl")]
+    Kdl,
+    #[cfg(feature = "jsonl")]
+    Jsonl,
+}
+
+/// Error produced by any of the supported formats.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[cfg(feature = "toml")]
+    #[error(transparent)]
+    Toml(#[from] crate::toml::Error),
+    #[cfg(feature = "json")]
+    #[error(transparent)]
+    Json(#[from] crate::json::Error),
+    #[cfg(feature = "yaml")]
+    #[error(transparent)]
+    Yaml(#[from] crate::yaml::Error),
+    #[cfg(feature = "ron")]
+    #[error(transparent)]
+    Ron(#[from] crate::ron::Error),
+    #[cfg(feature = "json5")]
+    #[error(transparent)]
+    Json5(#[from] crate::json5::Error),
+    #[cfg(feature = "s-expr")]
+    #[error(transparent)]
+    SExpr(#[from] crate::s_expr::Error),
+    #[cfg(feature = "hjson")]
+    #[error(transparent)]
+    HJson(#[from] crate::hjson::Error),
+    #[cfg(feature = "csv")]
+    #[error(transparent)]
+    Csv(#[from] crate::csv::Error),
+    #[cfg(feature = "cbor")]
+    #[error(transparent)]
+    Cbor(#[from] crate::cbor::Error),
+    #[cfg(feature = "msgpack")]
+    #[error(transparent)]
+    MsgPack(#[from] crate::msgpack::Error),
+    #[cfg(feature = "pickle")]
+    #[error(transparent)]
+    Pickle(#[from] crate::pickle::Error),
+    #[cfg(feature = "bson")]
+    #[error(transparent)]
+    Bson(#[from] crate::bson::Error),
+    #[cfg(feature = "flexbuffers")]
+    #[error(transparent)]
+    FlexBuffers(#[from] crate::flexbuffers::Error),
+    #[cfg(feature = "ini")]
+    #[error(transparent)]
+    Ini(#[from] crate::ini::Error),
+    #[cfg(feature = "properties")]
+    #[error(transparent)]
+    Properties(#[from] crate::properties::Error),
+    #[cfg(feature = "dotenv")]
+    #[error(transparent)]
+    Dotenv(#[from] crate::dotenv::Error),
+    #[cfg(feature = "xml")]
+    #[error(transparent)]
+    Xml(#[from] crate::xml::Error),
+    #[cfg(feature = "kdl")]
+    #[error(transparent)]
+    Kdl(#[from] crate::kdl::Error),
+    #[cfg(feature = "jsonl")]
+    #[error(transparent)]
+    Jsonl(#[from] crate::jsonl::Error),
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            #[cfg(feature = "toml")]
+            Error::Toml(err) => err.location(),
+            #[cfg(feature = "json")]
+            Error::Json(err) => err.location(),
+            #[cfg(feature = "yaml")]
+            Error::Yaml(err) => err.location(),
+            #[cfg(feature = "ron")]
+            Error::Ron(err) => err.location(),
+            #[cfg(feature = "json5")]
+            Error::Json5(err) => err.location(),
+            #[cfg(feature = "s-expr")]
+            Error::SExpr(err) => err.location(),
+            #[cfg(feature = "hjson")]
+            Error::HJson(err) => err.location(),
+            #[cfg(feature = "csv")]
+            Error::Csv(err) => err.location(),
+            #[cfg(feature = "cbor")]
+            Error::Cbor(err) => err.location(),
+            #[cfg(feature = "msgpack")]
+            Error::MsgPack(err) => err.location(),
+            #[cfg(feature = "pickle")]
+            Error::Pickle(err) => err.location(),
+            #[cfg(feature = "bson")]
+            Error::Bson(err) => err.location(),
+            #[cfg(feature = "flexbuffers")]
+            Error::FlexBuffers(err) => err.location(),
+            #[cfg(feature = "ini")]
+            Error::Ini(err) => err.location(),
+            #[cfg(feature = "properties")]
+            Error::Properties(err) => err.location(),
+            #[cfg(feature = "dotenv")]
+            Error::Dotenv(err) => err.location(),
+            #[cfg(feature = "xml")]
+            Error::Xml(err) => err.location(),
+            #[cfg(feature = "kdl")]
+            Error::Kdl(err) => err.location(),
+            #[cfg(feature = "jsonl")]
+            Error::Jsonl(err) => err.location(),
+        }
+    }
+}
+
+/// Error produced by [`Format::detect`].
+#[derive(Debug, Error)]
+pub enum DetectError {
+    #[error("`.{extension}` files require the `{feature}` feature")]
+    Disabled {
+        extension: String,
+        feature: &'static str,
+    },
+    #[error("the input looks like {feature}, which requires the `{feature}` feature")]
+    DisabledContents { feature: &'static str },
+    #[error("unknown file extension `.{0}`")]
+    UnknownExtension(String),
+    #[error("cannot determine the format of the input")]
+    Unknown,
+}
+
+/// The file extensions of every format, whether enabled or not, by the
+/// feature enabling the format.
+const EXTENSIONS: &[(&str, &[&str])] = &[
+    ("toml", &["toml"]),
+    ("json", &["json"]),
+    ("yaml", &["yaml", "yml"]),
+    ("ron", &["ron"]),
+    ("json5", &["json5"]),
+    ("s-expr", &["sexp", "sexpr", "lisp"]),
+    ("hjson", &["hjson"]),
+    ("csv", &["csv"]),
+    ("cbor", &["cbor"]),
+    ("msgpack", &["msgpack", "mpk"]),
+    ("pickle", &["pickle", "pkl"]),
+    ("bson", &["bson"]),
+    ("flexbuffers", &["flexbuffers", "flexbuf"]),
+    ("ini", &["ini"]),
+    ("properties", &["properties"]),
+    ("dotenv", &["env"]),
+    ("xml", &["xml"]),
+    ("kdl", &["kdl"]),
+    ("jsonl", &["jsonl", "ndjson"]),
+];
+
+/// The extension of `path`. Files named `.env` or `.env.<environment>`
+/// have no real extension, and are given the `env` extension of dotenv
+/// files, unless they end in the extension of a format, as in
+/// `.env.local.toml`.
+fn extension(path: &std::path::Path) -> Option<String> {
+    let name = path.file_name()?.to_str()?;
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    let known = extension.is_some_and(|ext| {
+        EXTENSIONS
+            .iter()
+            .flat_map(|(_, extensions)| extensions.iter())
+            .any(|known| known.eq_ignore_ascii_case(ext))
+    });
+    if (name == ".env" || name.starts_with(".env.")) && !known {
+        return Some("env".into());
+    }
+    Some(extension?.into())
+}
+
+/// Recognizes a binary format from its contents, returning the feature
+/// enabling it.
+///
+/// Apart from pickles, which start with a protocol header, and the
+/// self-described CBOR tag, none of these formats have a real magic number,
+/// so this relies on how a document whose root is a map is laid out:
+/// * A BSON document starts with its length and ends with a nul byte.
+/// * A CBOR map starts with a byte in `0xa0..=0xbf`.
+/// * A MessagePack map starts with a byte in `0x80..=0x8f`, `0xde` or
+///   `0xdf`.
+/// * A FlexBuffer ends with the type of the root, which is a map, and the
+///   byte width of the root.
+fn sniff(data: &[u8]) -> Option<&'static str> {
+    let (&first, &last) = (data.first()?, data.last()?);
+    if data.len() > 2 && first == 0x80 && (2..=5).contains(&data[1]) && last == b'.' {
+        return Some("pickle");
+    }
+    if data.len() >= 5
+        && last == 0
+        && u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize == data.len()
+    {
+        return Some("bson");
+    }
+    if data.starts_with(&[0xd9, 0xd9, 0xf7]) || (0xa0..=0xbf).contains(&first) {
+        return Some("cbor");
+    }
+    if (0x80..=0x8f).contains(&first) || first == 0xde || first == 0xdf {
+        return Some("msgpack");
+    }
+    if data.len() >= 3 && matches!(last, 1 | 2 | 4 | 8) && data[data.len() - 2] >> 2 == 9 {
+        return Some("flexbuffers");
+    }
+    None
+}
+
+impl Format {
+    /// All formats enabled in this build.
+    pub const ALL: &'static [Format] = &[
+        #[cfg(feature = "toml")]
+        Format::Toml,
+        #[cfg(feature = "json")]
+        Format::Json,
+        #[cfg(feature = "yaml")]
+        Format::Yaml,
+        #[cfg(feature = "ron")]
+        Format::Ron,
+        #[cfg(feature = "json5")]
+        Format::Json5,
+        #[cfg(feature = "s-expr")]
+        Format::SExpr,
+        #[cfg(feature = "hjson")]
+        Format::Hjson,
+        #[cfg(feature = "csv")]
+        Format::Csv,
+        #[cfg(feature = "cbor")]
+        Format::Cbor,
+        #[cfg(feature = "msgpack")]
+        Format::Msgpack,
+        #[cfg(feature = "pickle")]
+        Format::Pickle,
+        #[cfg(feature = "bson")]
+        Format::Bson,
+        #[cfg(feature = "flexbuffers")]
+        Format::Flexbuffers,
+        #[cfg(feature = "ini")]
+        Format::Ini,
+        #[cfg(feature = "properties")]
+        Format::Properties,
+        #[cfg(feature = "dotenv")]
+        Format::Dotenv,
+        #[cfg(feature = "xml")]
+        Format::Xml,
+        #[cfg(feature = "kdl")]
+        Format::Kdl,
+        #[cfg(feature = "jsonl")]
+        Format::Jsonl,
+    ];
+
+    /// The name of the format, as used in macro names, e.g. `s_expr`
+    /// for `include_s_expr!`.
+    pub fn name(self) -> &'static str {
+        match self {
+            #[cfg(feature = "toml")]
+            Format::Toml => "toml",
+            #[cfg(feature = "json")]
+            Format::Json => "json",
+            #[cfg(feature = "yaml")]
+            Format::Yaml => "yaml",
+            #[cfg(feature = "ron")]
+            Format::Ron => "ron",
+            #[cfg(feature = "json5")]
+            Format::Json5 => "json5",
+            #[cfg(feature = "s-expr")]
+            Format::SExpr => "s_expr",
+            #[cfg(feature = "hjson")]
+            Format::Hjson => "hjson",
+            #[cfg(feature = "csv")]
+            Format::Csv => "csv",
+            #[cfg(feature = "cbor")]
+            Format::Cbor => "cbor",
+            #[cfg(feature = "msgpack")]
+            Format::Msgpack => "msgpack",
+            #[cfg(feature = "pickle")]
+            Format::Pickle => "pickle",
+            #[cfg(feature = "bson")]
+            Format::Bson => "bson",
+            #[cfg(feature = "flexbuffers")]
+            Format::Flexbuffers => "flexbuffers",
+            #[cfg(feature = "ini")]
+            Format::Ini => "ini",
+            #[cfg(feature = "properties")]
+            Format::Properties => "properties",
+            #[cfg(feature = "dotenv")]
+            Format::Dotenv => "dotenv",
+            #[cfg(feature = "xml")]
+            Format::Xml => "xml",
+            #[cfg(feature = "kdl")]
+            Format::Kdl => "kdl",
+            #[cfg(feature = "jsonl")]
+            Format::Jsonl => "jsonl",
+        }
+    }
+
+    /// Looks up an enabled format by name. Both `s_expr` and `s-expr`
+    /// are accepted.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| format.name() == name.replace('-', "_"))
+    }
+
+    /// Looks up an enabled format by file extension, e.g. `yml` for YAML.
+    /// The comparison is case insensitive.
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        let ext = ext.to_ascii_lowercase();
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| format.extensions().contains(&ext.as_str()))
+    }
+
+    /// Looks up an enabled format by the extension of `path`.
+    pub fn from_path<P>(path: P) -> Option<Self>
+    where
+        P: AsRef<std::path::Path>,
+    {
+        Self::from_extension(&extension(path.as_ref())?)
+    }
+
+    /// The file extensions recognized for the format.
+    pub fn extensions(self) -> &'static [&'static str] {
+        EXTENSIONS
+            .iter()
+            .find(|(feature, _)| *feature == self.feature())
+            .map_or(&[], |(_, extensions)| extensions)
+    }
+
+    /// The name of the cargo feature enabling the format, e.g. `s-expr`.
+    pub fn feature(self) -> &'static str {
+        match self.name() {
+            "s_expr" => "s-expr",
+            name => name,
+        }
+    }
+
+    /// Determines the format of a file from the extension of `path`, or
+    /// failing that, from the magic bytes at the start of `data`.
+    ///
+    /// Only the binary formats can be recognized from their contents. An
+    /// extension belonging to a format whose feature is disabled is an
+    /// error rather than a reason to look at the contents.
+    pub fn detect(path: Option<&std::path::Path>, data: &[u8]) -> Result<Self, DetectError> {
+        let extension = path.and_then(extension).map(|ext| ext.to_ascii_lowercase());
+        if let Some(extension) = &extension {
+            if let Some(format) = Self::from_extension(extension) {
+                return Ok(format);
+            }
+            if let Some((feature, _)) = EXTENSIONS
+                .iter()
+                .find(|(_, extensions)| extensions.contains(&extension.as_str()))
+            {
+                return Err(DetectError::Disabled {
+                    extension: extension.clone(),
+                    feature,
+                });
+            }
+        }
+        match sniff(data) {
+            Some(feature) => Self::ALL
+                .iter()
+                .copied()
+                .find(|format| format.feature() == feature)
+                .ok_or(DetectError::DisabledContents { feature }),
+            None => Err(match extension {
+                Some(extension) => DetectError::UnknownExtension(extension),
+                None => DetectError::Unknown,
+            }),
+        }
+    }
+
+    /// Returns `true` if the format is a binary format, i.e. inline data
+    /// is given as a byte string rather than a string.
+    pub fn is_binary(self) -> bool {
+        match self {
+            #[cfg(feature = "cbor")]
+            Format::Cbor => true,
+            #[cfg(feature = "msgpack")]
+            Format::Msgpack => true,
+            #[cfg(feature = "pickle")]
+            Format::Pickle => true,
+            #[cfg(feature = "bson")]
+            Format::Bson => true,
+            #[cfg(feature = "flexbuffers")]
+            Format::Flexbuffers => true,
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// Parses `input` into a [`Value`].
+    pub fn parse<I>(self, input: I) -> Result<Value, Error>
+    where
+        I: Read,
+    {
+        #[allow(unreachable_code)]
+        Ok(match self {
+            #[cfg(feature = "toml")]
+            Format::Toml => crate::toml::parse(input)?,
+            #[cfg(feature = "json")]
+            Format::Json => crate::json::parse(input)?,
+            #[cfg(feature = "yaml")]
+            Format::Yaml => crate::yaml::parse(input)?,
+            #[cfg(feature = "ron")]
+            Format::Ron => crate::ron::parse(input)?,
+            #[cfg(feature = "json5")]
+            Format::Json5 => crate::json5::parse(input)?,
+            #[cfg(feature = "s-expr")]
+            Format::SExpr => crate::s_expr::parse(input)?,
+            #[cfg(feature = "hjson")]
+            Format::Hjson => crate::hjson::parse(input)?,
+            #[cfg(feature = "csv")]
+            Format::Csv => crate::csv::parse(input)?,
+            #[cfg(feature = "cbor")]
+            Format::Cbor => crate::cbor::parse(input)?,
+            #[cfg(feature = "msgpack")]
+            Format::Msgpack => crate::msgpack::parse(input)?,
+            #[cfg(feature = "pickle")]
+            Format::Pickle => crate::pickle::parse(input)?,
+            #[cfg(feature = "bson")]
+            Format::Bson => crate::bson::parse(input)?,
+            #[cfg(feature = "flexbuffers")]
+            Format::Flexbuffers => crate::flexbuffers::parse(input)?,
+            #[cfg(feature = "ini")]
+            Format::Ini => crate::ini::parse(input)?,
+            #[cfg(feature = "properties")]
+            Format::Properties => crate::properties::parse(input)?,
+            #[cfg(feature = "dotenv")]
+            Format::Dotenv => crate::dotenv::parse(input)?,
+            #[cfg(feature = "xml")]
+            Format::Xml => crate::xml::parse(input)?,
+            #[cfg(feature = "kdl")]
+            Format::Kdl => crate::kdl::parse(input)?,
+            #[cfg(feature = "jsonl")]
+            Format::Jsonl => crate::jsonl::parse(input)?,
+        })
+    }
+
+    /// Parses `input` and writes the generated Rust code to `output`.
+    pub fn generate<I, O>(self, input: I, output: O) -> Result<(), Error>
+    where
+        I: Read,
+        O: Write,
+    {
+        crate::generate(self.parse(input)?, output)?;
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,13 +10,37 @@ pub enum Error {
     HJson(#[from] deser_hjson::Error),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::HJson(err) => match err {
+                deser_hjson::Error::Syntax { line, col, .. }
+                | deser_hjson::Error::Serde { line, col, .. } => Some(Location {
+                    line: *line,
+                    column: *col,
+                }),
+                _ => None,
+            },
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    Ok(deser_hjson::from_reader(input)?)
+}
+
 pub fn generate<I, O>(input: I, output: O) -> Result<()>
 where
     I: Read,
     O: Write,
 {
-    crate::generate(deser_hjson::from_reader(input)?, output)?;
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
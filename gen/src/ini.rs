@@ -0,0 +1,127 @@
+use crate::{Location, Value};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("{message}")]
+    Syntax {
+        message: &'static str,
+        location: Location,
+    },
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Syntax { location, .. } => Some(*location),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Parses an INI file.
+///
+/// Keys before the first section header end up at the top level, and every
+/// `[section]` becomes a nested object. A section given more than once is
+/// merged, and a key given more than once keeps its last value.
+///
+/// Keys and values are separated by `=` or `:`, and surrounding whitespace
+/// is trimmed. A value wrapped in matching single or double quotes keeps
+/// its inner whitespace, without the quotes. Lines starting with `;` or `#`
+/// are comments. INI has no types, so all values are strings.
+pub fn parse<I>(mut input: I) -> Result<Value>
+where
+    I: Read,
+{
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+
+    let mut root: Vec<(String, Value)> = Vec::new();
+    let mut section: Option<usize> = None;
+    for (idx, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with([';', '#']) {
+            continue;
+        }
+        let indent = line.len() - line.trim_start().len();
+        let error = |message, offset: usize| Error::Syntax {
+            message,
+            location: Location {
+                line: idx + 1,
+                column: line[..indent + offset].chars().count() + 1,
+            },
+        };
+
+        if let Some(header) = trimmed.strip_prefix('[') {
+            let Some(name) = header.strip_suffix(']') else {
+                return Err(error(
+                    "expected `]` at the end of the section header",
+                    trimmed.len(),
+                ));
+            };
+            let name = name.trim();
+            if name.is_empty() {
+                return Err(error("empty section name", 1));
+            }
+            section = Some(match root.iter().position(|(key, _)| key == name) {
+                Some(pos) if matches!(root[pos].1, Value::Object(_)) => pos,
+                Some(_) => return Err(error("section has the same name as a key", 1)),
+                None => {
+                    root.push((name.into(), Value::Object(Vec::new())));
+                    root.len() - 1
+                }
+            });
+            continue;
+        }
+
+        let Some(sep) = trimmed.find(['=', ':']) else {
+            return Err(error("expected `=` or `:` after the key", trimmed.len()));
+        };
+        let key = trimmed[..sep].trim_end();
+        if key.is_empty() {
+            return Err(error("expected a key before the separator", sep));
+        }
+        let value = Value::Str(unquote(trimmed[sep + 1..].trim()).into());
+
+        let entries = match section {
+            Some(pos) => match &mut root[pos].1 {
+                Value::Object(entries) => entries,
+                _ => unreachable!(),
+            },
+            None => &mut root,
+        };
+        match entries.iter_mut().find(|(k, _)| k == key) {
+            Some((_, existing)) => *existing = value,
+            None => entries.push((key.into(), value)),
+        }
+    }
+    Ok(Value::Object(root))
+}
+
+/// Strips a pair of matching quotes around `value`, if any.
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(inner) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    value
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
+    Ok(())
+}
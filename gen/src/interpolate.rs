@@ -0,0 +1,167 @@
+use crate::Value;
+use thiserror::Error;
+
+/// Error produced by [`Value::interpolate_env`].
+#[derive(Debug, Error)]
+pub enum InterpolateError {
+    #[error("`{path}`: environment variable `{var}` is not set{}", message_suffix(.message))]
+    Unset {
+        path: String,
+        var: String,
+        message: String,
+    },
+    #[error("`{path}`: malformed placeholder `{placeholder}`")]
+    Malformed { path: String, placeholder: String },
+}
+
+fn message_suffix(message: &str) -> String {
+    if message.is_empty() {
+        String::new()
+    } else {
+        format!(": {message}")
+    }
+}
+
+impl Value {
+    /// Expands environment variable placeholders in every string value.
+    ///
+    /// The supported placeholders are:
+    /// * `${VAR}`, replaced by the value of `VAR`, or nothing if it is unset.
+    /// * `${VAR:-default}`, replaced by `default` if `VAR` is unset or empty.
+    ///   `${VAR-default}` only uses `default` if `VAR` is unset.
+    /// * `${VAR:?message}`, an error mentioning `message` if `VAR` is unset
+    ///   or empty. `${VAR?message}` is only an error if `VAR` is unset.
+    ///
+    /// `$${` is replaced by a literal `${`. Variables are looked up with
+    /// `lookup`, which returns `None` for unset variables. Keys of objects
+    /// and maps are left untouched.
+    pub fn interpolate_env<F>(&mut self, mut lookup: F) -> Result<(), InterpolateError>
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        self.interpolate_impl(&mut String::new(), &mut lookup)
+    }
+
+    fn interpolate_impl<F>(
+        &mut self,
+        path: &mut String,
+        lookup: &mut F,
+    ) -> Result<(), InterpolateError>
+    where
+        F: FnMut(&str) -> Option<String>,
+    {
+        match self {
+            Value::Str(s) => {
+                if let Some(expanded) = interpolate_str(s, path, lookup)? {
+                    *s = expanded;
+                }
+            }
+            Value::Array(array) => {
+                for (idx, value) in array.iter_mut().enumerate() {
+                    let len = path.len();
+                    path.push_str(&format!("[{idx}]"));
+                    value.interpolate_impl(path, lookup)?;
+                    path.truncate(len);
+                }
+            }
+            Value::Object(entries) => {
+                for (key, value) in entries.iter_mut() {
+                    let len = path.len();
+                    if !path.is_empty() {
+                        path.push('.');
+                    }
+                    path.push_str(key);
+                    value.interpolate_impl(path, lookup)?;
+                    path.truncate(len);
+                }
+            }
+            Value::Map(entries) => {
+                for (key, value) in entries.iter_mut() {
+                    let len = path.len();
+                    match key {
+                        Value::Str(key) => {
+                            if !path.is_empty() {
+                                path.push('.');
+                            }
+                            path.push_str(key);
+                        }
+                        Value::UInt(key) => path.push_str(&format!("[{key}]")),
+                        Value::Int(key) => path.push_str(&format!("[{key}]")),
+                        key => path.push_str(&format!("[{key:?}]")),
+                    }
+                    value.interpolate_impl(path, lookup)?;
+                    path.truncate(len);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Expands the placeholders in `s`, or returns `None` if there are none.
+fn interpolate_str<F>(
+    s: &str,
+    path: &str,
+    lookup: &mut F,
+) -> Result<Option<String>, InterpolateError>
+where
+    F: FnMut(&str) -> Option<String>,
+{
+    if !s.contains("${") {
+        return Ok(None);
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(idx) = rest.find("${") {
+        if rest[..idx].ends_with('$') {
+            out.push_str(&rest[..idx - 1]);
+            out.push_str("${");
+            rest = &rest[idx + 2..];
+            continue;
+        }
+        out.push_str(&rest[..idx]);
+
+        let malformed = || InterpolateError::Malformed {
+            path: path.into(),
+            placeholder: rest[idx..].chars().take(32).collect(),
+        };
+        let body_start = idx + 2;
+        let Some(len) = rest[body_start..].find('}') else {
+            return Err(malformed());
+        };
+        let body = &rest[body_start..body_start + len];
+        let name_len = body
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+            .unwrap_or(body.len());
+        let (var, op) = body.split_at(name_len);
+        if var.is_empty() || var.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(malformed());
+        }
+
+        let (empty_is_unset, op) = match op.strip_prefix(':') {
+            Some("") => return Err(malformed()),
+            Some(op) => (true, op),
+            None => (false, op),
+        };
+        let value = lookup(var);
+        let value = value.filter(|value| !(empty_is_unset && value.is_empty()));
+        match (op.chars().next(), value) {
+            (None, value) => out.push_str(&value.unwrap_or_default()),
+            (Some('-' | '?'), Some(value)) => out.push_str(&value),
+            (Some('-'), None) => out.push_str(&op[1..]),
+            (Some('?'), None) => {
+                return Err(InterpolateError::Unset {
+                    path: path.into(),
+                    var: var.into(),
+                    message: op[1..].into(),
+                });
+            }
+            _ => return Err(malformed()),
+        }
+        rest = &rest[body_start + len + 1..];
+    }
+    out.push_str(rest);
+    Ok(Some(out))
+}
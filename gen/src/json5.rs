@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,15 +10,37 @@ pub enum Error {
     Json5(#[from] json5::Error),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Json5(err) => match err {
+                json5::Error::Message { location, .. } => location.as_ref().map(|loc| Location {
+                    line: loc.line,
+                    column: loc.column,
+                }),
+            },
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
-pub fn generate<I, O>(mut input: I, output: O) -> Result<()>
+pub fn parse<I>(mut input: I) -> Result<Value>
 where
     I: Read,
-    O: Write,
 {
     let mut s = String::new();
     input.read_to_string(&mut s)?;
-    crate::generate(json5::from_str(&s)?, output)?;
+    Ok(json5::from_str(&s)?)
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
@@ -0,0 +1,70 @@
+use crate::{Location, Value};
+use std::io::{BufRead, BufReader, Read, Write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("{} at line {line} column {}", message(source), source.column().max(1))]
+    Json {
+        line: usize,
+        source: serde_json::Error,
+    },
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Json { line, source } => Some(Location {
+                line: *line,
+                column: source.column().max(1),
+            }),
+        }
+    }
+}
+
+/// The message of `err` without the position serde_json appends to it,
+/// which is relative to the line rather than the whole input.
+fn message(err: &serde_json::Error) -> String {
+    let message = err.to_string();
+    let position = format!(" at line {} column {}", err.line(), err.column());
+    match message.strip_suffix(&position) {
+        Some(message) => message.into(),
+        None => message,
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Parses newline-delimited JSON into an array with one element per line.
+/// Lines holding only whitespace are skipped.
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    let mut values = Vec::new();
+    for (idx, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value = serde_json::from_str(&line).map_err(|source| Error::Json {
+            line: idx + 1,
+            source,
+        })?;
+        values.push(value);
+    }
+    Ok(Value::Array(values))
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
+    Ok(())
+}
@@ -0,0 +1,633 @@
+use crate::{Location, Value};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("{message}")]
+    Syntax { message: String, location: Location },
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Syntax { location, .. } => Some(*location),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// The key holding the name of a node.
+pub const NAME_KEY: &str = "name";
+/// The key holding the arguments of a node.
+pub const ARGS_KEY: &str = "args";
+/// The key holding the children of a node.
+pub const CHILDREN_KEY: &str = "children";
+
+/// Parses a KDL document into an array of nodes.
+///
+/// Every node becomes an object with its name under `name`, its arguments
+/// in an array under `args`, and its properties as further keys. A node
+/// with a children block also has its child nodes in an array under
+/// `children`. Properties called `name`, `args` or `children` are an
+/// error, and when a property is given more than once, the last one wins.
+///
+/// Both KDL 2 and KDL 1 syntax is accepted, i.e. `#true` as well as `true`,
+/// and `#"raw"#` as well as `r#"raw"#`. Values annotated with `(date)`,
+/// `(time)` or `(date-time)` become [`Value::Date`], [`Value::Time`] and
+/// [`Value::DateTime`], and must be strings holding an RFC 3339 date, time
+/// or date-time. Any other type annotation is ignored.
+pub fn parse<I>(mut input: I) -> Result<Value>
+where
+    I: Read,
+{
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    let mut parser = Parser {
+        text: &text,
+        pos: 0,
+    };
+    parser.bom();
+    Ok(Value::Array(parser.nodes(false)?))
+}
+
+/// Characters that cannot be part of an identifier.
+const NON_IDENT: &[char] = &['\\', '/', '(', ')', '{', '}', ';', '[', ']', '"', '#', '='];
+
+fn is_space(ch: char) -> bool {
+    matches!(
+        ch,
+        '\t' | ' ' | '\u{a0}' | '\u{1680}' | '\u{2000}'
+            ..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}' | '\u{feff}'
+    )
+}
+
+fn is_newline(ch: char) -> bool {
+    matches!(
+        ch,
+        '\n' | '\r' | '\u{b}' | '\u{c}' | '\u{85}' | '\u{2028}' | '\u{2029}'
+    )
+}
+
+fn is_ident(ch: char) -> bool {
+    !is_space(ch) && !is_newline(ch) && !NON_IDENT.contains(&ch)
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, pos: usize, message: impl Into<String>) -> Error {
+        Error::Syntax {
+            message: message.into(),
+            location: Location::from_offset(self.text, pos),
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.pos += ch.len_utf8();
+        Some(ch)
+    }
+
+    fn eat(&mut self, s: &str) -> bool {
+        if self.rest().starts_with(s) {
+            self.pos += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn bom(&mut self) {
+        self.eat("\u{feff}");
+    }
+
+    /// Skips a newline, treating `\r\n` as one.
+    fn newline(&mut self) -> bool {
+        if self.eat("\r\n") {
+            return true;
+        }
+        match self.peek() {
+            Some(ch) if is_newline(ch) => {
+                self.bump();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn block_comment(&mut self) -> Result<()> {
+        let start = self.pos;
+        self.pos += 2;
+        let mut depth = 1;
+        while depth > 0 {
+            if self.eat("/*") {
+                depth += 1;
+            } else if self.eat("*/") {
+                depth -= 1;
+            } else if self.bump().is_none() {
+                return Err(self.error(start, "unterminated block comment"));
+            }
+        }
+        Ok(())
+    }
+
+    fn line_comment(&mut self) {
+        while let Some(ch) = self.peek() {
+            if is_newline(ch) {
+                break;
+            }
+            self.bump();
+        }
+        self.newline();
+    }
+
+    /// Skips whitespace within a line, including block comments and line
+    /// continuations. Returns whether anything was skipped.
+    fn ws(&mut self) -> Result<bool> {
+        let start = self.pos;
+        loop {
+            match self.peek() {
+                Some(ch) if is_space(ch) => {
+                    self.bump();
+                }
+                Some('/') if self.rest().starts_with("/*") => self.block_comment()?,
+                Some('\\') => {
+                    let pos = self.pos;
+                    self.bump();
+                    loop {
+                        match self.peek() {
+                            Some(ch) if is_space(ch) => {
+                                self.bump();
+                            }
+                            Some('/') if self.rest().starts_with("/*") => self.block_comment()?,
+                            _ => break,
+                        }
+                    }
+                    if self.rest().starts_with("//") {
+                        self.line_comment();
+                    } else if !self.newline() && self.peek().is_some() {
+                        return Err(self.error(pos, "expected a newline after `\\`"));
+                    }
+                }
+                _ => return Ok(self.pos != start),
+            }
+        }
+    }
+
+    /// Skips whitespace, newlines and comments between nodes.
+    fn linespace(&mut self) -> Result<()> {
+        loop {
+            self.ws()?;
+            if self.rest().starts_with("//") {
+                self.line_comment();
+            } else if !self.newline() {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Parses nodes up to the end of the input, or up to and including the
+    /// `}` closing a children block.
+    fn nodes(&mut self, block: bool) -> Result<Vec<Value>> {
+        let mut nodes = Vec::new();
+        loop {
+            self.linespace()?;
+            match self.peek() {
+                None if block => return Err(self.error(self.pos, "expected `}`")),
+                None => return Ok(nodes),
+                Some('}') if block => {
+                    self.bump();
+                    return Ok(nodes);
+                }
+                Some('}') => return Err(self.error(self.pos, "unexpected `}`")),
+                Some(';') => {
+                    self.bump();
+                    continue;
+                }
+                _ => {}
+            }
+            if self.eat("/-") {
+                self.linespace()?;
+                self.node()?;
+            } else {
+                nodes.push(self.node()?);
+            }
+        }
+    }
+
+    fn node(&mut self) -> Result<Value> {
+        if self.peek() == Some('(') {
+            self.annotation()?;
+            self.ws()?;
+        }
+        let start = self.pos;
+        let name = match self.string()? {
+            Some(name) => name,
+            None => return Err(self.error(start, "expected a node name")),
+        };
+        let mut args = Vec::new();
+        let mut entries = vec![(NAME_KEY.to_string(), Value::Str(name))];
+        let mut children = None;
+        loop {
+            let spaced = self.ws()?;
+            match self.peek() {
+                None | Some('}') => break,
+                Some(';') => {
+                    self.bump();
+                    break;
+                }
+                Some(ch) if is_newline(ch) => {
+                    self.newline();
+                    break;
+                }
+                Some('/') if self.rest().starts_with("//") => {
+                    self.line_comment();
+                    break;
+                }
+                _ => {}
+            }
+
+            let discard = self.eat("/-");
+            if discard {
+                self.linespace()?;
+            }
+            if self.peek() == Some('{') {
+                self.bump();
+                let nodes = self.nodes(true)?;
+                if !discard {
+                    if children.is_some() {
+                        return Err(self.error(self.pos, "a node can have only one children block"));
+                    }
+                    children = Some(nodes);
+                }
+                continue;
+            }
+            if children.is_some() {
+                return Err(self.error(self.pos, "expected the end of the node"));
+            }
+            if !spaced && !discard {
+                return Err(self.error(self.pos, "expected whitespace"));
+            }
+
+            let start = self.pos;
+            match self.entry()? {
+                _ if discard => {}
+                (None, value) => args.push(value),
+                (Some(key), value) => {
+                    if [NAME_KEY, ARGS_KEY, CHILDREN_KEY].contains(&key.as_str()) {
+                        return Err(self.error(start, format!("`{key}` is a reserved key")));
+                    }
+                    match entries.iter_mut().find(|(k, _)| *k == key) {
+                        Some((_, existing)) => *existing = value,
+                        None => entries.push((key, value)),
+                    }
+                }
+            }
+        }
+        entries.insert(1, (ARGS_KEY.into(), Value::Array(args)));
+        if let Some(children) = children {
+            entries.push((CHILDREN_KEY.into(), Value::Array(children)));
+        }
+        Ok(Value::Object(entries))
+    }
+
+    /// Parses an argument, or a property with its key.
+    fn entry(&mut self) -> Result<(Option<String>, Value)> {
+        let start = self.pos;
+        if self.peek() != Some('(') && !self.starts_number() && !self.starts_keyword() {
+            if let Some(key) = self.string()? {
+                let before = self.pos;
+                self.ws()?;
+                if self.eat("=") {
+                    self.ws()?;
+                    return Ok((Some(key), self.value()?));
+                }
+                self.pos = before;
+                return Ok((None, Value::Str(key)));
+            }
+            return Err(self.error(start, "expected a value"));
+        }
+        Ok((None, self.value()?))
+    }
+
+    fn annotation(&mut self) -> Result<String> {
+        let start = self.pos;
+        self.bump();
+        self.ws()?;
+        let Some(ty) = self.string()? else {
+            return Err(self.error(self.pos, "expected a type name"));
+        };
+        self.ws()?;
+        if !self.eat(")") {
+            return Err(self.error(start, "expected `)`"));
+        }
+        Ok(ty)
+    }
+
+    fn value(&mut self) -> Result<Value> {
+        let ty = if self.peek() == Some('(') {
+            let ty = self.annotation()?;
+            self.ws()?;
+            Some(ty)
+        } else {
+            None
+        };
+        let start = self.pos;
+        let value = if self.starts_number() {
+            self.number()?
+        } else if self.starts_keyword() {
+            self.keyword()?
+        } else {
+            match self.string()? {
+                Some(s) => Value::Str(s),
+                None => return Err(self.error(start, "expected a value")),
+            }
+        };
+
+        let expected = match ty.as_deref() {
+            Some("date") => "date",
+            Some("time") => "time",
+            Some("date-time" | "datetime") => "date-time",
+            _ => return Ok(value),
+        };
+        let timestamp = match &value {
+            Value::Str(s) => crate::timestamp::parse_timestamp(s),
+            _ => None,
+        };
+        match (expected, timestamp) {
+            ("date", Some(value @ Value::Date(_)))
+            | ("time", Some(value @ Value::Time(_)))
+            | ("date-time", Some(value @ Value::DateTime(_))) => Ok(value),
+            _ => Err(self.error(start, format!("expected a string holding a {expected}"))),
+        }
+    }
+
+    fn starts_number(&self) -> bool {
+        let rest = self.rest();
+        let rest = rest.strip_prefix(['+', '-']).unwrap_or(rest);
+        rest.starts_with(|ch: char| ch.is_ascii_digit())
+    }
+
+    fn starts_keyword(&self) -> bool {
+        let rest = self.rest();
+        if let Some(rest) = rest.strip_prefix('#') {
+            return !rest.starts_with(['#', '"']);
+        }
+        ["true", "false", "null"]
+            .iter()
+            .any(|kw| rest.starts_with(kw) && !rest[kw.len()..].starts_with(is_ident))
+    }
+
+    fn keyword(&mut self) -> Result<Value> {
+        let start = self.pos;
+        self.eat("#");
+        let len = self
+            .rest()
+            .find(|ch| !is_ident(ch))
+            .unwrap_or(self.rest().len());
+        let word = &self.rest()[..len];
+        self.pos += len;
+        Ok(match word {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" => Value::Null,
+            "inf" => Value::Float(f64::INFINITY),
+            "-inf" => Value::Float(f64::NEG_INFINITY),
+            "nan" => Value::Float(f64::NAN),
+            _ => return Err(self.error(start, format!("unknown keyword `#{word}`"))),
+        })
+    }
+
+    fn number(&mut self) -> Result<Value> {
+        let start = self.pos;
+        let len = self
+            .rest()
+            .find(|ch| !is_ident(ch))
+            .unwrap_or(self.rest().len());
+        let token = &self.rest()[..len];
+        self.pos += len;
+        let invalid = || self.error(start, format!("invalid number `{token}`"));
+
+        let (negative, digits) = match token.as_bytes()[0] {
+            b'-' => (true, &token[1..]),
+            b'+' => (false, &token[1..]),
+            _ => (false, token),
+        };
+        let radix = match digits.get(..2) {
+            Some("0x") => 16,
+            Some("0o") => 8,
+            Some("0b") => 2,
+            _ => 10,
+        };
+        let body = if radix == 10 { digits } else { &digits[2..] };
+        if body.is_empty() || body.starts_with('_') {
+            return Err(invalid());
+        }
+        let body = body.replace('_', "");
+
+        if radix == 10 && body.contains(['.', 'e', 'E']) {
+            // Digits are required on both sides of the point.
+            let mantissa = body.split(['e', 'E']).next().unwrap_or_default();
+            if mantissa.ends_with('.') || body.contains(".e") || body.contains(".E") {
+                return Err(invalid());
+            }
+            let value: f64 = body.parse().map_err(|_| invalid())?;
+            return Ok(Value::Float(if negative { -value } else { value }));
+        }
+        let value = u128::from_str_radix(&body, radix).map_err(|_| invalid())?;
+        if !negative {
+            return Ok(Value::UInt(value));
+        }
+        match value {
+            0 => Ok(Value::Int(0)),
+            value if value <= i128::MAX as u128 + 1 => {
+                Ok(Value::Int((value as i128).wrapping_neg()))
+            }
+            _ => Err(invalid()),
+        }
+    }
+
+    /// Parses an identifier, a quoted string or a raw string. Returns `None`
+    /// if there is none at the current position.
+    fn string(&mut self) -> Result<Option<String>> {
+        let rest = self.rest();
+        if rest.starts_with('"') {
+            return self.quoted().map(Some);
+        }
+        let hashes = |s: &str| s.len() - s.trim_start_matches('#').len();
+        if rest.starts_with('#') && rest[hashes(rest)..].starts_with('"') {
+            let count = hashes(rest);
+            self.pos += count;
+            return self.raw(count).map(Some);
+        }
+        if let Some(raw) = rest.strip_prefix('r')
+            && raw[hashes(raw)..].starts_with('"')
+        {
+            let count = hashes(raw);
+            self.pos += 1 + count;
+            return self.raw(count).map(Some);
+        }
+        if self.starts_number() {
+            return Ok(None);
+        }
+        let len = rest.find(|ch| !is_ident(ch)).unwrap_or(rest.len());
+        if len == 0 {
+            return Ok(None);
+        }
+        self.pos += len;
+        Ok(Some(rest[..len].into()))
+    }
+
+    /// Parses a raw string after its leading `#`s.
+    fn raw(&mut self, hashes: usize) -> Result<String> {
+        let start = self.pos;
+        let multiline = self.rest().starts_with("\"\"\"");
+        self.pos += if multiline { 3 } else { 1 };
+        let close = format!(
+            "{}{}",
+            if multiline { "\"\"\"" } else { "\"" },
+            "#".repeat(hashes)
+        );
+        let Some(len) = self.rest().find(&close) else {
+            return Err(self.error(start, "unterminated raw string"));
+        };
+        let body = &self.rest()[..len];
+        self.pos += len + close.len();
+        if multiline {
+            return self.dedent(start, body);
+        }
+        Ok(body.into())
+    }
+
+    fn quoted(&mut self) -> Result<String> {
+        let start = self.pos;
+        if self.eat("\"\"\"") {
+            let mut body = String::new();
+            loop {
+                if self.rest().starts_with("\"\"\"") {
+                    self.pos += 3;
+                    break;
+                }
+                match self.peek() {
+                    None => return Err(self.error(start, "unterminated string")),
+                    Some('\\') => self.escape(&mut body)?,
+                    Some(ch) => {
+                        self.bump();
+                        body.push(ch);
+                    }
+                }
+            }
+            return self.dedent(start, &body);
+        }
+
+        self.bump();
+        let mut value = String::new();
+        loop {
+            match self.peek() {
+                None => return Err(self.error(start, "unterminated string")),
+                Some('"') => {
+                    self.bump();
+                    return Ok(value);
+                }
+                Some('\\') => self.escape(&mut value)?,
+                Some(ch) => {
+                    self.bump();
+                    value.push(ch);
+                }
+            }
+        }
+    }
+
+    fn escape(&mut self, out: &mut String) -> Result<()> {
+        let start = self.pos;
+        self.bump();
+        match self.bump() {
+            Some('n') => out.push('\n'),
+            Some('r') => out.push('\r'),
+            Some('t') => out.push('\t'),
+            Some('b') => out.push('\u{8}'),
+            Some('f') => out.push('\u{c}'),
+            Some('s') => out.push(' '),
+            Some(ch @ ('\\' | '"' | '/')) => out.push(ch),
+            Some('u') => {
+                let hex = self
+                    .rest()
+                    .strip_prefix('{')
+                    .and_then(|rest| rest.split_once('}'))
+                    .map(|(hex, _)| hex)
+                    .filter(|hex| (1..=6).contains(&hex.len()));
+                let ch = hex
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .and_then(char::from_u32)
+                    .ok_or_else(|| self.error(start, "invalid unicode escape"))?;
+                self.pos += hex.unwrap_or_default().len() + 2;
+                out.push(ch);
+            }
+            // Escaped whitespace is removed, along with any whitespace
+            // following it.
+            Some(ch) if is_space(ch) || is_newline(ch) => {
+                while let Some(ch) = self.peek() {
+                    if !is_space(ch) && !is_newline(ch) {
+                        break;
+                    }
+                    self.bump();
+                }
+            }
+            _ => return Err(self.error(start, "invalid escape")),
+        }
+        Ok(())
+    }
+
+    /// Removes the first and last line of a multi-line string, and the
+    /// indentation of its closing line from every other line.
+    fn dedent(&self, start: usize, body: &str) -> Result<String> {
+        let error = || self.error(start, "invalid multi-line string");
+        let body = body.replace("\r\n", "\n");
+        let (first, rest) = body.split_once('\n').ok_or_else(error)?;
+        if !first.chars().all(is_space) {
+            return Err(error());
+        }
+        let (lines, indent) = rest.rsplit_once('\n').unwrap_or(("", rest));
+        if !indent.chars().all(is_space) {
+            return Err(error());
+        }
+        if lines.is_empty() && !rest.contains('\n') {
+            return Ok(String::new());
+        }
+        let mut out = Vec::new();
+        for line in lines.split('\n') {
+            if line.chars().all(is_space) {
+                out.push("");
+            } else {
+                out.push(line.strip_prefix(indent).ok_or_else(error)?);
+            }
+        }
+        Ok(out.join("\n"))
+    }
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
+    Ok(())
+}
@@ -1,7 +1,21 @@
 #![allow(dead_code)]
 #![allow(unused)]
 
+mod archive;
+mod builder;
+mod coerce;
+mod compact;
+mod dedup;
+mod dotted;
+mod format;
+mod interpolate;
+mod location;
+mod merge;
+mod module;
 pub(crate) mod phf;
+mod profile;
+mod schema;
+mod timestamp;
 mod value;
 
 #[cfg(feature = "toml")]
@@ -43,6 +57,37 @@ pub mod bson;
 #[cfg(feature = "flexbuffers")]
 pub mod flexbuffers;
 
+#[cfg(feature = "ini")]
+pub mod ini;
+
+#[cfg(feature = "properties")]
+pub mod properties;
+
+#[cfg(feature = "dotenv")]
+pub mod dotenv;
+
+#[cfg(feature = "xml")]
+pub mod xml;
+
+#[cfg(feature = "kdl")]
+pub mod kdl;
+
+#[cfg(feature = "jsonl")]
+pub mod jsonl;
+
+use dedup::Shared;
+
+pub use archive::generate_archive;
+pub use builder::{BuildError, Builder};
+pub use compact::generate_compact;
+pub use dotted::ExpandKeysError;
+pub use format::{DetectError, Error, Format};
+pub use interpolate::InterpolateError;
+pub use location::Location;
+pub use merge::ArrayMerge;
+pub use module::{Emit, EmitError, generate_const_item, generate_module};
+pub use profile::{PROFILE_KEY, ProfileError};
+pub use schema::{SchemaError, Violation};
 pub use value::*;
 
 #[cfg(feature = "toml")]
@@ -165,16 +210,115 @@ where
     gen_fs(input_path, output_path, flexbuffers::generate)
 }
 
-pub fn generate<O>(value: Value, mut output: O) -> std::io::Result<()>
+#[cfg(feature = "ini")]
+pub fn generate_from_ini<I, O>(input_path: I, output_path: O) -> Result<(), ini::Error>
+where
+    I: AsRef<std::path::Path>,
+    O: AsRef<std::path::Path>,
+{
+    gen_fs(input_path, output_path, ini::generate)
+}
+
+#[cfg(feature = "properties")]
+pub fn generate_from_properties<I, O>(
+    input_path: I,
+    output_path: O,
+) -> Result<(), properties::Error>
+where
+    I: AsRef<std::path::Path>,
+    O: AsRef<std::path::Path>,
+{
+    gen_fs(input_path, output_path, properties::generate)
+}
+
+#[cfg(feature = "dotenv")]
+pub fn generate_from_dotenv<I, O>(input_path: I, output_path: O) -> Result<(), dotenv::Error>
+where
+    I: AsRef<std::path::Path>,
+    O: AsRef<std::path::Path>,
+{
+    gen_fs(input_path, output_path, dotenv::generate)
+}
+
+#[cfg(feature = "xml")]
+pub fn generate_from_xml<I, O>(input_path: I, output_path: O) -> Result<(), xml::Error>
+where
+    I: AsRef<std::path::Path>,
+    O: AsRef<std::path::Path>,
+{
+    gen_fs(input_path, output_path, xml::generate)
+}
+
+#[cfg(feature = "kdl")]
+pub fn generate_from_kdl<I, O>(input_path: I, output_path: O) -> Result<(), kdl::Error>
+where
+    I: AsRef<std::path::Path>,
+    O: AsRef<std::path::Path>,
+{
+    gen_fs(input_path, output_path, kdl::generate)
+}
+
+#[cfg(feature = "jsonl")]
+pub fn generate_from_jsonl<I, O>(input_path: I, output_path: O) -> Result<(), jsonl::Error>
+where
+    I: AsRef<std::path::Path>,
+    O: AsRef<std::path::Path>,
+{
+    gen_fs(input_path, output_path, jsonl::generate)
+}
+
+/// Generates a `const` block evaluating to `value`.
+///
+/// Strings, byte strings, arrays, objects and maps that occur more than
+/// once are generated only once, as `const` items at the start of the
+/// block.
+pub fn generate<O>(mut value: Value, mut output: O) -> std::io::Result<()>
 where
     O: std::io::Write,
 {
-    write!(output, "const {{\n")?;
+    value.dedup_all_keys();
+    let shared = Shared::find(&value);
+    writeln!(output, "const {{")?;
+    generate_items(&shared, &mut output, 1)?;
     do_indent(&mut output, 1)?;
-    generate_impl(value, &mut output, 1)?;
+    generate_impl(&value, &mut output, 1, &shared)?;
     write!(output, "\n}}")
 }
 
+/// Generates an expression evaluating to `value`, which is a block holding
+/// the items of shared values if there are any.
+fn generate_expr<O>(mut value: Value, output: &mut O, indent: usize) -> std::io::Result<()>
+where
+    O: std::io::Write,
+{
+    value.dedup_all_keys();
+    let shared = Shared::find(&value);
+    if shared.is_empty() {
+        return generate_impl(&value, output, indent, &shared);
+    }
+    writeln!(output, "{{")?;
+    generate_items(&shared, output, indent + 1)?;
+    do_indent(output, indent + 1)?;
+    generate_impl(&value, output, indent + 1, &shared)?;
+    writeln!(output)?;
+    do_indent(output, indent)?;
+    write!(output, "}}")
+}
+
+/// Generates a `const` item for every shared value.
+fn generate_items<O>(shared: &Shared, output: &mut O, indent: usize) -> std::io::Result<()>
+where
+    O: std::io::Write,
+{
+    for (item, value) in shared.items().iter().enumerate() {
+        do_indent(output, indent)?;
+        write!(output, "const V{item}: ::const_config::Value<'static> = ")?;
+        generate_node(value, output, indent, shared)?;
+        writeln!(output, ";")?;
+    }
+    Ok(())
+}
+
 fn gen_fs<I, O, F, E>(input_path: I, output_path: O, f: F) -> Result<(), E>
 where
     I: AsRef<std::path::Path>,
@@ -200,7 +344,30 @@ where
     Ok(())
 }
 
-fn generate_impl<O>(value: Value, output: &mut O, indent: usize) -> std::io::Result<()>
+/// Writes `value`, or a reference to its item if it is shared.
+fn generate_impl<O>(
+    value: &Value,
+    output: &mut O,
+    indent: usize,
+    shared: &Shared,
+) -> std::io::Result<()>
+where
+    O: std::io::Write,
+{
+    match shared.item(value) {
+        Some(item) => write!(output, "V{item}"),
+        None => generate_node(value, output, indent, shared),
+    }
+}
+
+/// Writes `value` itself, referring to the items of any shared values in
+/// it.
+fn generate_node<O>(
+    value: &Value,
+    output: &mut O,
+    indent: usize,
+    shared: &Shared,
+) -> std::io::Result<()>
 where
     O: std::io::Write,
 {
@@ -217,89 +384,88 @@ where
             "::const_config::Value::<'static>::Number(::const_config::Number::Int({}))",
             value
         ),
-        Value::Float(value) => write!(
-            output,
-            "::const_config::Value::<'static>::Number(::const_config::Number::Float({:.17}))",
-            value
-        ),
-        Value::Date(value) => {
+        Value::Float(value) => {
             write!(
                 output,
-                "::const_config::Value::<'static>::Date(::const_config::Date {{ \n"
+                "::const_config::Value::<'static>::Number(::const_config::Number::Float("
+            )?;
+            write_float(output, *value)?;
+            write!(output, "))")
+        }
+        Value::Date(value) => {
+            writeln!(
+                output,
+                "::const_config::Value::<'static>::Date(::const_config::Date {{ "
             )?;
             do_indent(output, indent + 1)?;
-            write!(output, "year: {},\n", value.year)?;
+            writeln!(output, "year: {},", value.year)?;
             do_indent(output, indent + 1)?;
-            write!(output, "month: {},\n", value.month)?;
+            writeln!(output, "month: {},", value.month)?;
             do_indent(output, indent + 1)?;
-            write!(output, "day: {},\n", value.day)?;
+            writeln!(output, "day: {},", value.day)?;
             do_indent(output, indent)?;
             write!(output, "}})")
         }
         Value::Time(value) => {
-            write!(
+            writeln!(
                 output,
-                "::const_config::Value::<'static>::Time(::const_config::Time {{\n"
+                "::const_config::Value::<'static>::Time(::const_config::Time {{"
             )?;
             do_indent(output, indent + 1)?;
-            write!(output, "hour: {},\n", value.hour)?;
+            writeln!(output, "hour: {},", value.hour)?;
             do_indent(output, indent + 1)?;
-            write!(output, "minute: {},\n", value.minute)?;
+            writeln!(output, "minute: {},", value.minute)?;
             do_indent(output, indent + 1)?;
-            write!(output, "second: {},\n", value.second)?;
+            writeln!(output, "second: {},", value.second)?;
             do_indent(output, indent + 1)?;
-            write!(output, "nanosecond: {},\n", value.nanosecond)?;
+            writeln!(output, "nanosecond: {},", value.nanosecond)?;
             do_indent(output, indent)?;
             write!(output, "}})")
         }
         Value::DateTime(value) => {
-            write!(
+            writeln!(
                 output,
-                "::const_config::Value::<'static>::DateTime(::const_config::DateTime {{\n"
+                "::const_config::Value::<'static>::DateTime(::const_config::DateTime {{"
             )?;
             do_indent(output, indent + 1)?;
-            write!(output, "date: ::const_config::Date {{\n")?;
+            writeln!(output, "date: ::const_config::Date {{")?;
             do_indent(output, indent + 2)?;
-            write!(output, "year: {},\n", value.date.year)?;
+            writeln!(output, "year: {},", value.date.year)?;
             do_indent(output, indent + 2)?;
-            write!(output, "month: {},\n", value.date.month)?;
+            writeln!(output, "month: {},", value.date.month)?;
             do_indent(output, indent + 2)?;
-            write!(output, "day: {},\n", value.date.day)?;
+            writeln!(output, "day: {},", value.date.day)?;
             do_indent(output, indent + 1)?;
-            write!(output, "}},\n")?;
+            writeln!(output, "}},")?;
             do_indent(output, indent + 1)?;
-            write!(output, "time: ::const_config::Time {{\n")?;
+            writeln!(output, "time: ::const_config::Time {{")?;
             do_indent(output, indent + 2)?;
-            write!(output, "hour: {},\n", value.time.hour)?;
+            writeln!(output, "hour: {},", value.time.hour)?;
             do_indent(output, indent + 2)?;
-            write!(output, "minute: {},\n", value.time.minute)?;
+            writeln!(output, "minute: {},", value.time.minute)?;
             do_indent(output, indent + 2)?;
-            write!(output, "second: {},\n", value.time.second)?;
+            writeln!(output, "second: {},", value.time.second)?;
             do_indent(output, indent + 2)?;
-            write!(output, "nanosecond: {},\n", value.time.nanosecond)?;
+            writeln!(output, "nanosecond: {},", value.time.nanosecond)?;
             do_indent(output, indent + 1)?;
-            write!(output, "}},\n")?;
+            writeln!(output, "}},")?;
             do_indent(output, indent + 1)?;
             if let Some(offset) = value.offset {
-                write!(
-                    output,
-                    "offset: ::core::option::Option::Some({}),\n",
-                    offset
-                )?;
+                writeln!(output, "offset: ::core::option::Option::Some({}),", offset)?;
             } else {
-                write!(output, "offset: ::core::option::Option::None,\n")?;
+                writeln!(output, "offset: ::core::option::Option::None,")?;
             }
             do_indent(output, indent)?;
             write!(output, "}})")
         }
         Value::Str(value) => {
             write!(output, "::const_config::Value::<'static>::Str(")?;
-            write_str(output, &value)?;
+            write_str(output, value)?;
             write!(output, ")")
         }
         Value::Bytes(value) => {
             write!(output, "::const_config::Value::<'static>::Bytes(")?;
-            write_bytes(output, &value)?;
+            write_bytes(output, value)?;
             write!(output, ")")
         }
         Value::Array(value) => {
@@ -309,18 +475,18 @@ where
                     "::const_config::Value::<'static>::Array(const {{ &[] }})"
                 )
             } else {
-                write!(output, "::const_config::Value::<'static>::Array(const {{\n")?;
+                writeln!(output, "::const_config::Value::<'static>::Array(const {{")?;
                 do_indent(output, indent + 1)?;
-                write!(output, "&[\n")?;
+                writeln!(output, "&[")?;
 
                 for elem in value {
                     do_indent(output, indent + 2)?;
-                    generate_impl(elem, output, indent + 2)?;
-                    write!(output, ",\n")?;
+                    generate_impl(elem, output, indent + 2, shared)?;
+                    writeln!(output, ",")?;
                 }
 
                 do_indent(output, indent + 1)?;
-                write!(output, "]\n")?;
+                writeln!(output, "]")?;
                 do_indent(output, indent)?;
                 write!(output, "}})")
             }
@@ -332,53 +498,53 @@ where
                     "::const_config::Value::<'static>::Object(::const_config::Object::<'static>::new(const {{ &[] }}, const {{ &[] }}, const {{ &[] }}))"
                 )
             } else {
-                let phf = phf::Phf::build_object(&value);
-                write!(
+                let phf = phf::Phf::build_object(value);
+                writeln!(
                     output,
-                    "::const_config::Value::<'static>::Object(::const_config::Object::<'static>::new(\n"
+                    "::const_config::Value::<'static>::Object(::const_config::Object::<'static>::new("
                 )?;
                 do_indent(output, indent + 1)?;
-                write!(output, "const {{\n")?;
+                writeln!(output, "const {{")?;
                 do_indent(output, indent + 2)?;
-                write!(output, "&[\n")?;
+                writeln!(output, "&[")?;
                 for entry in value {
                     do_indent(output, indent + 3)?;
                     write!(output, "(")?;
                     write_str(output, &entry.0)?;
                     write!(output, ", ")?;
-                    generate_impl(entry.1, output, indent + 3)?;
-                    write!(output, "),\n")?;
+                    generate_impl(&entry.1, output, indent + 3, shared)?;
+                    writeln!(output, "),")?;
                 }
                 do_indent(output, indent + 2)?;
-                write!(output, "]\n")?;
+                writeln!(output, "]")?;
                 do_indent(output, indent + 1)?;
-                write!(output, "}},\n")?;
+                writeln!(output, "}},")?;
 
                 do_indent(output, indent + 1)?;
-                write!(output, "const {{\n")?;
+                writeln!(output, "const {{")?;
                 do_indent(output, indent + 2)?;
-                write!(output, "&[\n")?;
+                writeln!(output, "&[")?;
                 for param in &phf.params {
                     do_indent(output, indent + 3)?;
-                    write!(output, "{},\n", *param)?;
+                    writeln!(output, "{},", *param)?;
                 }
                 do_indent(output, indent + 2)?;
-                write!(output, "]\n")?;
+                writeln!(output, "]")?;
                 do_indent(output, indent + 1)?;
-                write!(output, "}},\n")?;
+                writeln!(output, "}},")?;
 
                 do_indent(output, indent + 1)?;
-                write!(output, "const {{\n")?;
+                writeln!(output, "const {{")?;
                 do_indent(output, indent + 2)?;
-                write!(output, "&[\n")?;
+                writeln!(output, "&[")?;
                 for value in &phf.values {
                     do_indent(output, indent + 3)?;
-                    write!(output, "{},\n", *value)?;
+                    writeln!(output, "{},", *value)?;
                 }
                 do_indent(output, indent + 2)?;
-                write!(output, "]\n")?;
+                writeln!(output, "]")?;
                 do_indent(output, indent + 1)?;
-                write!(output, "}},\n")?;
+                writeln!(output, "}},")?;
 
                 do_indent(output, indent)?;
                 write!(output, "))")
@@ -391,57 +557,57 @@ where
                     "::const_config::Value::<'static>::Map(::const_config::Map::<'static>::new(const {{ &[] }}, const {{ &[] }}, const {{ &[] }}))"
                 )
             } else {
-                let phf = phf::Phf::build_map(&value);
-                write!(
+                let phf = phf::Phf::build_map(value);
+                writeln!(
                     output,
-                    "::const_config::Value::<'static>::Map(::const_config::Map::<'static>::new(\n"
+                    "::const_config::Value::<'static>::Map(::const_config::Map::<'static>::new("
                 )?;
                 do_indent(output, indent + 1)?;
-                write!(output, "const {{\n")?;
+                writeln!(output, "const {{")?;
                 do_indent(output, indent + 2)?;
-                write!(output, "&[\n")?;
+                writeln!(output, "&[")?;
                 for entry in value {
                     do_indent(output, indent + 3)?;
-                    write!(output, "(\n")?;
+                    writeln!(output, "(")?;
                     do_indent(output, indent + 4)?;
-                    generate_impl(entry.0, output, indent + 4)?;
-                    write!(output, ",\n")?;
+                    generate_impl(&entry.0, output, indent + 4, shared)?;
+                    writeln!(output, ",")?;
                     do_indent(output, indent + 4)?;
-                    generate_impl(entry.1, output, indent + 4)?;
-                    write!(output, ",\n")?;
+                    generate_impl(&entry.1, output, indent + 4, shared)?;
+                    writeln!(output, ",")?;
                     do_indent(output, indent + 3)?;
-                    write!(output, "),\n")?;
+                    writeln!(output, "),")?;
                 }
                 do_indent(output, indent + 2)?;
-                write!(output, "]\n")?;
+                writeln!(output, "]")?;
                 do_indent(output, indent + 1)?;
-                write!(output, "}},\n")?;
+                writeln!(output, "}},")?;
 
                 do_indent(output, indent + 1)?;
-                write!(output, "const {{\n")?;
+                writeln!(output, "const {{")?;
                 do_indent(output, indent + 2)?;
-                write!(output, "&[\n")?;
+                writeln!(output, "&[")?;
                 for param in &phf.params {
                     do_indent(output, indent + 3)?;
-                    write!(output, "{},\n", *param)?;
+                    writeln!(output, "{},", *param)?;
                 }
                 do_indent(output, indent + 2)?;
-                write!(output, "]\n")?;
+                writeln!(output, "]")?;
                 do_indent(output, indent + 1)?;
-                write!(output, "}},\n")?;
+                writeln!(output, "}},")?;
 
                 do_indent(output, indent + 1)?;
-                write!(output, "const {{\n")?;
+                writeln!(output, "const {{")?;
                 do_indent(output, indent + 2)?;
-                write!(output, "&[\n")?;
+                writeln!(output, "&[")?;
                 for value in &phf.values {
                     do_indent(output, indent + 3)?;
-                    write!(output, "{},\n", *value)?;
+                    writeln!(output, "{},", *value)?;
                 }
                 do_indent(output, indent + 2)?;
-                write!(output, "]\n")?;
+                writeln!(output, "]")?;
                 do_indent(output, indent + 1)?;
-                write!(output, "}},\n")?;
+                writeln!(output, "}},")?;
 
                 do_indent(output, indent)?;
                 write!(output, "))")
@@ -450,6 +616,23 @@ where
     }
 }
 
+/// Writes an `f64` literal, or the matching `f64` constant for NaN and the
+/// infinities, which have no literal.
+fn write_float<O>(output: &mut O, value: f64) -> std::io::Result<()>
+where
+    O: std::io::Write,
+{
+    if value.is_nan() {
+        write!(output, "f64::NAN")
+    } else if value == f64::INFINITY {
+        write!(output, "f64::INFINITY")
+    } else if value == f64::NEG_INFINITY {
+        write!(output, "f64::NEG_INFINITY")
+    } else {
+        write!(output, "{value:?}")
+    }
+}
+
 fn write_str<O>(output: &mut O, s: &str) -> std::io::Result<()>
 where
     O: std::io::Write,
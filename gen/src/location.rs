@@ -0,0 +1,59 @@
+use std::fmt;
+
+/// Where in the input a parse error was found.
+///
+/// Lines and columns are 1-based, and columns count characters rather than
+/// bytes. Binary formats have no lines, so their errors are located on line
+/// 1 with the column being the 1-based byte offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    /// Location of a byte `offset` into text `input`.
+    pub fn from_offset(input: &str, offset: usize) -> Self {
+        let mut offset = offset.min(input.len());
+        while !input.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Location of a byte `offset` into binary input.
+    pub fn from_byte_offset(offset: usize) -> Self {
+        Self {
+            line: 1,
+            column: offset + 1,
+        }
+    }
+
+    /// Byte offset of this location in text `input`, if it is within the
+    /// input.
+    pub fn offset_in(&self, input: &str) -> Option<usize> {
+        let mut line_start = 0;
+        for _ in 1..self.line {
+            line_start += input[line_start..].find('\n')? + 1;
+        }
+        let line = &input[line_start..];
+        let line = &line[..line.find('\n').unwrap_or(line.len())];
+        let column = self.column.max(1) - 1;
+        match line.char_indices().nth(column) {
+            Some((idx, _)) => Some(line_start + idx),
+            None if line.chars().count() == column => Some(line_start + line.len()),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
@@ -1,4 +1,6 @@
-use clap::{Parser, ValueEnum};
+use clap::Parser;
+use const_config_gen::{Emit, Format};
+use std::io::Read;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -11,101 +13,106 @@ struct Cli {
     #[arg(short, long, value_name = "FILE")]
     outfile: Option<std::path::PathBuf>,
 
-    /// Format of input file.
+    /// Format of input file. Detected from the extension of the input file
+    /// or the contents of the input when not given.
     #[arg(short, long, value_enum)]
-    format: Format,
-}
+    format: Option<Format>,
+
+    /// Turn strings holding an RFC 3339 or YAML timestamp into dates and
+    /// times.
+    #[arg(short, long)]
+    timestamps: bool,
+
+    /// Turn strings holding numbers and booleans into numbers and booleans.
+    #[arg(long)]
+    coerce: bool,
+
+    /// Turn dotted keys like `server.port` into nested objects.
+    #[arg(long)]
+    expand_keys: bool,
+
+    /// Kind of output to generate.
+    #[arg(short, long, value_enum, default_value_t)]
+    emit: Emit,
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum)]
-enum Format {
-    #[cfg(feature = "toml")]
-    Toml,
-    #[cfg(feature = "json")]
-    Json,
-    #[cfg(feature = "yaml")]
-    Yaml,
-    #[cfg(feature = "ron")]
-    Ron,
-    #[cfg(feature = "json5")]
-    Json5,
-    #[cfg(feature = "s-expr")]
-    SExpr,
-    #[cfg(feature = "hjson")]
-    Hjson,
-    #[cfg(feature = "csv")]
-    Csv,
-    #[cfg(feature = "cbor")]
-    Cbor,
-    #[cfg(feature = "msgpack")]
-    Msgpack,
-    #[cfg(feature = "pickle")]
-    Pickle,
-    #[cfg(feature = "bson")]
-    Bson,
-    #[cfg(feature = "flexbuffers")]
-    Flexbuffers,
+    /// Generate the compact code used by the macros instead of readable
+    /// code. Only applies to `--emit expr`.
+    #[arg(short, long)]
+    compact: bool,
+
+    /// Name of the generated item with `--emit const`.
+    #[arg(short, long, default_value = "CONFIG")]
+    name: String,
 }
 
+// With no formats enabled `Format` is uninhabited, so everything after
+// argument parsing is unreachable.
+#[allow(unreachable_code, unused_variables)]
 fn main() -> Result<(), Box<dyn std::error::Error + 'static>> {
-    #[allow(unused_variables)]
     let args = Cli::parse();
+    if args.compact && args.emit != Emit::Expr {
+        eprintln!("error: `--compact` only applies to `--emit expr`");
+        std::process::exit(1);
+    }
+
+    let input = if let Some(infile) = args.infile.as_ref() {
+        std::fs::read(infile)?
+    } else {
+        let mut input = Vec::new();
+        std::io::stdin().lock().read_to_end(&mut input)?;
+        input
+    };
+    let name = match args.infile.as_ref() {
+        Some(infile) => infile.display().to_string(),
+        None => "<stdin>".into(),
+    };
+
+    let output: Box<dyn std::io::Write> = if let Some(outfile) = args.outfile.as_ref() {
+        Box::new(std::io::BufWriter::new(std::fs::File::create(outfile)?))
+    } else {
+        Box::new(std::io::stdout().lock())
+    };
 
-    #[cfg(any(
-        feature = "toml",
-        feature = "json",
-        feature = "yaml",
-        feature = "ron",
-        feature = "json5",
-        feature = "s-expr",
-        feature = "hjson",
-        feature = "csv",
-        feature = "cbor",
-        feature = "msgpack",
-        feature = "pickle",
-        feature = "bson",
-        feature = "flexbuffers",
-    ))]
-    {
-        let input: Box<dyn std::io::Read> = if let Some(infile) = args.infile.as_ref() {
-            Box::new(std::io::BufReader::new(std::fs::File::open(infile)?))
-        } else {
-            Box::new(std::io::stdin().lock())
-        };
-
-        let output: Box<dyn std::io::Write> = if let Some(outfile) = args.outfile.as_ref() {
-            Box::new(std::io::BufWriter::new(std::fs::File::create(outfile)?))
-        } else {
-            Box::new(std::io::stdout().lock())
-        };
-
-        match args.format {
-            #[cfg(feature = "toml")]
-            Format::Toml => const_config_gen::toml::generate(input, output)?,
-            #[cfg(feature = "json")]
-            Format::Json => const_config_gen::json::generate(input, output)?,
-            #[cfg(feature = "yaml")]
-            Format::Yaml => const_config_gen::yaml::generate(input, output)?,
-            #[cfg(feature = "ron")]
-            Format::Ron => const_config_gen::ron::generate(input, output)?,
-            #[cfg(feature = "json5")]
-            Format::Json5 => const_config_gen::json5::generate(input, output)?,
-            #[cfg(feature = "s-expr")]
-            Format::SExpr => const_config_gen::s_expr::generate(input, output)?,
-            #[cfg(feature = "hjson")]
-            Format::Hjson => const_config_gen::hjson::generate(input, output)?,
-            #[cfg(feature = "csv")]
-            Format::Csv => const_config_gen::csv::generate(input, output)?,
-            #[cfg(feature = "cbor")]
-            Format::Cbor => const_config_gen::cbor::generate(input, output)?,
-            #[cfg(feature = "msgpack")]
-            Format::Msgpack => const_config_gen::msgpack::generate(input, output)?,
-            #[cfg(feature = "pickle")]
-            Format::Pickle => const_config_gen::pickle::generate(input, output)?,
-            #[cfg(feature = "bson")]
-            Format::Bson => const_config_gen::bson::generate(input, output)?,
-            #[cfg(feature = "flexbuffers")]
-            Format::Flexbuffers => const_config_gen::flexbuffers::generate(input, output)?,
+    let format = match args.format {
+        Some(format) => format,
+        None => match Format::detect(args.infile.as_deref(), &input) {
+            Ok(format) => format,
+            Err(err) => {
+                eprintln!("error: {name}: {err}, pass `--format` to choose one");
+                std::process::exit(1);
+            }
+        },
+    };
+
+    let mut value = match format.parse(&input[..]) {
+        Ok(value) => value,
+        Err(err) => {
+            let Some(location) = err.location() else {
+                return Err(err.into());
+            };
+            eprintln!("error: {name}:{}:{}: {err}", location.line, location.column);
+            std::process::exit(1);
         }
+    };
+
+    if args.expand_keys {
+        value.expand_dotted_keys()?;
+    }
+
+    if args.coerce {
+        value.coerce_scalars();
+    }
+
+    if args.timestamps {
+        value.detect_timestamps();
+    }
+
+    match args.emit {
+        Emit::Expr if args.compact => const_config_gen::generate_compact(value, output)?,
+        Emit::Expr => const_config_gen::generate(value, output)?,
+        Emit::Const => const_config_gen::generate_const_item(value, &args.name, output)?,
+        Emit::Module => const_config_gen::generate_module(value, output)?,
+        Emit::Archive => const_config_gen::generate_archive(value, output)?,
     }
 
     Ok(())
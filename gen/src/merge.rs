@@ -0,0 +1,291 @@
+use crate::Value;
+use clap::ValueEnum;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// How arrays are combined when one [`Value`] is merged on top of another.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum ArrayMerge {
+    /// The overriding array replaces the original one.
+    #[default]
+    Replace,
+    /// The elements of the overriding array are appended to the original
+    /// one.
+    Append,
+}
+
+impl ArrayMerge {
+    /// Looks up an array merge strategy by name, i.e. `replace` or
+    /// `append`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "replace" => Some(Self::Replace),
+            "append" => Some(Self::Append),
+            _ => None,
+        }
+    }
+}
+
+impl Value {
+    /// Deep-merges `other` on top of `self`.
+    ///
+    /// Objects and maps are merged key by key, keeping the position of keys
+    /// already present in `self` and adding new keys at the end. Arrays are
+    /// combined according to `arrays`. Any other value in `other`, including
+    /// null, replaces the value in `self`.
+    pub fn merge(&mut self, other: Value, arrays: ArrayMerge) {
+        match (self, other) {
+            (Value::Object(base), Value::Object(other)) => merge_entries(base, other, arrays),
+            (Value::Map(base), Value::Map(other)) => merge_entries(base, other, arrays),
+            (Value::Map(base), Value::Object(other)) => merge_entries(
+                base,
+                other
+                    .into_iter()
+                    .map(|(key, value)| (Value::Str(key), value))
+                    .collect(),
+                arrays,
+            ),
+            (this @ Value::Object(_), Value::Map(other)) => {
+                let Value::Object(base) = std::mem::replace(this, Value::Null) else {
+                    unreachable!();
+                };
+                let mut base = base
+                    .into_iter()
+                    .map(|(key, value)| (Value::Str(key), value))
+                    .collect();
+                merge_entries(&mut base, other, arrays);
+                *this = Value::Map(base);
+            }
+            (Value::Array(base), Value::Array(other)) if arrays == ArrayMerge::Append => {
+                base.extend(other);
+            }
+            (this, other) => *this = other,
+        }
+    }
+}
+
+fn merge_entries<K: EntryKey>(
+    base: &mut Vec<(K, Value)>,
+    other: Vec<(K, Value)>,
+    arrays: ArrayMerge,
+) {
+    let positions = positions(base, &other);
+    for ((key, value), pos) in other.into_iter().zip(positions) {
+        match base.get_mut(pos) {
+            Some((_, base)) => base.merge(value, arrays),
+            None => base.push((key, value)),
+        }
+    }
+}
+
+/// The position each entry of `other` ends up at when its entries are
+/// added to `base`: the position of the same key in `base`, or of its first
+/// occurrence in `other`, past the end of `base`.
+fn positions<K: EntryKey>(base: &[(K, Value)], other: &[(K, Value)]) -> Vec<usize> {
+    let mut index = HashMap::with_capacity(base.len() + other.len());
+    for (pos, (key, _)) in base.iter().enumerate() {
+        index.entry(key.form()).or_insert(pos);
+    }
+    other
+        .iter()
+        .map(|(key, _)| {
+            let next = index.len();
+            *index.entry(key.form()).or_insert(next)
+        })
+        .collect()
+}
+
+/// A key of an object or map.
+pub(crate) trait EntryKey {
+    /// A hashable form of the key, equal for keys that are the same key of
+    /// a `const_config::Object` or `const_config::Map`.
+    type Form<'a>: Hash + Eq
+    where
+        Self: 'a;
+
+    fn form(&self) -> Self::Form<'_>;
+}
+
+impl EntryKey for String {
+    type Form<'a> = &'a str;
+
+    fn form(&self) -> &str {
+        self
+    }
+}
+
+impl EntryKey for Value {
+    type Form<'a> = KeyForm<'a>;
+
+    fn form(&self) -> KeyForm<'_> {
+        KeyForm::new(self)
+    }
+}
+
+/// A map key normalized so that keys equal by [`key_eq`] are equal: numbers
+/// of the same value are one integer or float whatever their
+/// representation, and all NaNs are one key.
+#[derive(PartialEq, Eq, Hash)]
+pub(crate) enum KeyForm<'a> {
+    Null,
+    Bool(bool),
+    Integer { negative: bool, magnitude: u128 },
+    Float(u64),
+    NaN,
+    Date(u16, u8, u8),
+    Time(u8, u8, u8, u32),
+    DateTime((u16, u8, u8), (u8, u8, u8, u32), Option<i16>),
+    Str(&'a str),
+    Bytes(&'a [u8]),
+    Array(Vec<KeyForm<'a>>),
+    Object(Vec<(&'a str, KeyForm<'a>)>),
+    Map(Vec<(KeyForm<'a>, KeyForm<'a>)>),
+}
+
+impl<'a> KeyForm<'a> {
+    fn new(value: &'a Value) -> Self {
+        match value {
+            Value::Null => Self::Null,
+            Value::Bool(value) => Self::Bool(*value),
+            Value::UInt(value) => Self::Integer {
+                negative: false,
+                magnitude: *value,
+            },
+            Value::Int(value) => Self::Integer {
+                negative: *value < 0,
+                magnitude: value.unsigned_abs(),
+            },
+            Value::Float(value) if value.is_nan() => Self::NaN,
+            // `-0.0` is the integer `0` too, like in `key_eq`.
+            Value::Float(value) if *value >= 0.0 && (*value as u128 as f64) == *value => {
+                Self::Integer {
+                    negative: false,
+                    magnitude: *value as u128,
+                }
+            }
+            Value::Float(value) if (*value as i128 as f64) == *value => Self::Integer {
+                negative: true,
+                magnitude: (*value as i128).unsigned_abs(),
+            },
+            Value::Float(value) => Self::Float(value.to_bits()),
+            Value::Date(date) => Self::Date(date.year, date.month, date.day),
+            Value::Time(time) => Self::Time(time.hour, time.minute, time.second, time.nanosecond),
+            Value::DateTime(datetime) => Self::DateTime(
+                (datetime.date.year, datetime.date.month, datetime.date.day),
+                (
+                    datetime.time.hour,
+                    datetime.time.minute,
+                    datetime.time.second,
+                    datetime.time.nanosecond,
+                ),
+                datetime.offset,
+            ),
+            Value::Str(value) => Self::Str(value),
+            Value::Bytes(value) => Self::Bytes(value),
+            Value::Array(values) => Self::Array(values.iter().map(Self::new).collect()),
+            Value::Object(entries) => Self::Object(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), Self::new(value)))
+                    .collect(),
+            ),
+            Value::Map(entries) => Self::Map(
+                entries
+                    .iter()
+                    .map(|(key, value)| (Self::new(key), Self::new(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+impl Value {
+    /// Removes repeated keys from every object and map, see [`dedup_keys`].
+    pub(crate) fn dedup_all_keys(&mut self) {
+        match self {
+            Value::Array(array) => array.iter_mut().for_each(Value::dedup_all_keys),
+            Value::Object(entries) => {
+                *entries = dedup_keys(std::mem::take(entries));
+                entries
+                    .iter_mut()
+                    .for_each(|(_, value)| value.dedup_all_keys());
+            }
+            Value::Map(entries) => {
+                *entries = dedup_keys(std::mem::take(entries));
+                entries.iter_mut().for_each(|(key, value)| {
+                    key.dedup_all_keys();
+                    value.dedup_all_keys();
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Removes repeated keys from the entries of an object or map, keeping the
+/// position of the first occurrence and the value of the last, as most
+/// parsers do for repeated keys.
+pub(crate) fn dedup_keys<K: EntryKey>(entries: Vec<(K, Value)>) -> Vec<(K, Value)> {
+    let positions = positions(&[], &entries);
+    let mut deduped: Vec<(K, Value)> = Vec::with_capacity(entries.len());
+    for ((key, value), pos) in entries.into_iter().zip(positions) {
+        match deduped.get_mut(pos) {
+            Some((_, base)) => *base = value,
+            None => deduped.push((key, value)),
+        }
+    }
+    deduped
+}
+
+/// Key equality as used by `const_config::Map`, where numbers compare by
+/// value regardless of their representation, and all NaNs are equal.
+pub(crate) fn key_eq(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::Null, Value::Null) => true,
+        (Value::Bool(lhs), Value::Bool(rhs)) => lhs == rhs,
+        (Value::UInt(lhs), Value::UInt(rhs)) => lhs == rhs,
+        (Value::Int(lhs), Value::Int(rhs)) => lhs == rhs,
+        (Value::Float(lhs), Value::Float(rhs)) => lhs == rhs || (lhs.is_nan() && rhs.is_nan()),
+        (Value::UInt(lhs), Value::Int(rhs)) | (Value::Int(rhs), Value::UInt(lhs)) => {
+            *rhs >= 0 && *lhs == *rhs as u128
+        }
+        (Value::UInt(lhs), Value::Float(rhs)) | (Value::Float(rhs), Value::UInt(lhs)) => {
+            *rhs >= 0.0 && (*rhs as u128 as f64) == *rhs && *lhs == *rhs as u128
+        }
+        (Value::Int(lhs), Value::Float(rhs)) | (Value::Float(rhs), Value::Int(lhs)) => {
+            (*rhs as i128 as f64) == *rhs && *lhs == *rhs as i128
+        }
+        (Value::Date(lhs), Value::Date(rhs)) => {
+            (lhs.year, lhs.month, lhs.day) == (rhs.year, rhs.month, rhs.day)
+        }
+        (Value::Time(lhs), Value::Time(rhs)) => {
+            (lhs.hour, lhs.minute, lhs.second, lhs.nanosecond)
+                == (rhs.hour, rhs.minute, rhs.second, rhs.nanosecond)
+        }
+        (Value::DateTime(lhs), Value::DateTime(rhs)) => {
+            key_eq(&Value::Date(lhs.date), &Value::Date(rhs.date))
+                && key_eq(&Value::Time(lhs.time), &Value::Time(rhs.time))
+                && lhs.offset == rhs.offset
+        }
+        (Value::Str(lhs), Value::Str(rhs)) => lhs == rhs,
+        (Value::Bytes(lhs), Value::Bytes(rhs)) => lhs == rhs,
+        (Value::Array(lhs), Value::Array(rhs)) => {
+            lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(lhs, rhs)| key_eq(lhs, rhs))
+        }
+        (Value::Object(lhs), Value::Object(rhs)) => {
+            lhs.len() == rhs.len()
+                && lhs
+                    .iter()
+                    .zip(rhs)
+                    .all(|(lhs, rhs)| lhs.0 == rhs.0 && key_eq(&lhs.1, &rhs.1))
+        }
+        (Value::Map(lhs), Value::Map(rhs)) => {
+            lhs.len() == rhs.len()
+                && lhs
+                    .iter()
+                    .zip(rhs)
+                    .all(|(lhs, rhs)| key_eq(&lhs.0, &rhs.0) && key_eq(&lhs.1, &rhs.1))
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,264 @@
+use crate::{Date, Time, Value, do_indent, generate_expr, write_bytes, write_float, write_str};
+use clap::ValueEnum;
+use std::io::Write;
+use thiserror::Error;
+
+/// The kind of output generated for a config.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Emit {
+    /// A single `const { ... }` expression evaluating to a
+    /// `const_config::Value`.
+    #[default]
+    Expr,
+    /// A `pub const` item of type `const_config::Value`. See
+    /// [`generate_const_item`].
+    Const,
+    /// The body of a module with a `pub const` for every top-level key and
+    /// a nested module for every object. See [`generate_module`].
+    Module,
+    /// A binary archive read at runtime with
+    /// `const_config::ArchivedValue`, rather than Rust code. See
+    /// [`generate_archive`](crate::generate_archive).
+    Archive,
+}
+
+/// Error produced by [`generate_module`].
+#[derive(Debug, Error)]
+pub enum EmitError {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("a module can only be generated for an object")]
+    NotAnObject,
+    #[error("`{path}` cannot be turned into a Rust identifier")]
+    BadKey { path: String },
+    #[error("`{first}` and `{second}` would both be named `{name}`")]
+    Collision {
+        name: String,
+        first: String,
+        second: String,
+    },
+}
+
+/// Generates a `pub const` item named `name` holding the config.
+pub fn generate_const_item<O>(value: Value, name: &str, mut output: O) -> std::io::Result<()>
+where
+    O: Write,
+{
+    write!(
+        output,
+        "pub const {name}: ::const_config::Value<'static> = "
+    )?;
+    generate_expr(value, &mut output, 0)?;
+    writeln!(output, ";")
+}
+
+/// Generates the body of a Rust module for a config object.
+///
+/// Every key of the object becomes an item named after it:
+/// * Objects become nested `pub mod`s with a `snake_case` name.
+/// * Scalars become `pub const`s with a `SCREAMING_SNAKE_CASE` name and the
+///   matching Rust type, e.g. `bool`, `&str` or `const_config::Date`.
+///   Integers are `i64`, unless they only fit a `u64`, `i128` or `u128`.
+/// * Anything else, i.e. arrays, maps and nulls, becomes a `pub const` of
+///   type `const_config::Value`.
+///
+/// Keys are split into words at punctuation and case changes, so
+/// `default-port`, `default_port` and `defaultPort` all become
+/// `DEFAULT_PORT`. Two keys of an object that end up with the same name
+/// are an error.
+pub fn generate_module<O>(value: Value, mut output: O) -> Result<(), EmitError>
+where
+    O: Write,
+{
+    let Value::Object(entries) = value else {
+        return Err(EmitError::NotAnObject);
+    };
+    generate_items(entries, &mut output, 0, &mut String::new())
+}
+
+fn generate_items<O>(
+    entries: Vec<(String, Value)>,
+    output: &mut O,
+    indent: usize,
+    path: &mut String,
+) -> Result<(), EmitError>
+where
+    O: Write,
+{
+    let mut names: Vec<(String, String)> = Vec::with_capacity(entries.len());
+    for (idx, (key, value)) in entries.into_iter().enumerate() {
+        let len = path.len();
+        if !path.is_empty() {
+            path.push('.');
+        }
+        path.push_str(&key);
+
+        let module = matches!(value, Value::Object(_));
+        let Some(name) = identifier(&key, module) else {
+            return Err(EmitError::BadKey { path: path.clone() });
+        };
+        if let Some((_, first)) = names.iter().find(|(other, _)| *other == name) {
+            return Err(EmitError::Collision {
+                name,
+                first: first.clone(),
+                second: path.clone(),
+            });
+        }
+
+        if idx > 0 {
+            writeln!(output)?;
+        }
+        do_indent(output, indent)?;
+        writeln!(output, "/// `{path}`")?;
+        do_indent(output, indent)?;
+        match value {
+            Value::Object(entries) => {
+                writeln!(output, "pub mod {name} {{")?;
+                generate_items(entries, output, indent + 1, path)?;
+                do_indent(output, indent)?;
+                writeln!(output, "}}")?;
+            }
+            value => {
+                write!(output, "pub const {name}: ")?;
+                generate_const(value, output, indent)?;
+                writeln!(output, ";")?;
+            }
+        }
+
+        names.push((name, path.clone()));
+        path.truncate(len);
+    }
+    Ok(())
+}
+
+/// Writes the type and value of a `const` item.
+fn generate_const<O>(value: Value, output: &mut O, indent: usize) -> std::io::Result<()>
+where
+    O: Write,
+{
+    match value {
+        Value::Bool(value) => write!(output, "bool = {value}"),
+        Value::UInt(value) => {
+            let ty = if i64::try_from(value).is_ok() {
+                "i64"
+            } else if u64::try_from(value).is_ok() {
+                "u64"
+            } else {
+                "u128"
+            };
+            write!(output, "{ty} = {value}")
+        }
+        Value::Int(value) => {
+            let ty = if i64::try_from(value).is_ok() {
+                "i64"
+            } else {
+                "i128"
+            };
+            write!(output, "{ty} = {value}")
+        }
+        Value::Float(value) => {
+            write!(output, "f64 = ")?;
+            write_float(output, value)
+        }
+        Value::Date(value) => write!(output, "::const_config::Date = {}", date(&value)),
+        Value::Time(value) => write!(output, "::const_config::Time = {}", time(&value)),
+        Value::DateTime(value) => {
+            let offset = match value.offset {
+                Some(offset) => format!("::core::option::Option::Some({offset})"),
+                None => "::core::option::Option::None".into(),
+            };
+            write!(
+                output,
+                "::const_config::DateTime = ::const_config::DateTime {{ date: {}, time: {}, offset: {offset} }}",
+                date(&value.date),
+                time(&value.time),
+            )
+        }
+        Value::Str(value) => {
+            write!(output, "&str = ")?;
+            write_str(output, &value)
+        }
+        Value::Bytes(value) => {
+            write!(output, "&[u8] = ")?;
+            write_bytes(output, &value)
+        }
+        value => {
+            write!(output, "::const_config::Value<'static> = ")?;
+            generate_expr(value, output, indent)
+        }
+    }
+}
+
+fn date(date: &Date) -> String {
+    format!(
+        "::const_config::Date {{ year: {}, month: {}, day: {} }}",
+        date.year, date.month, date.day,
+    )
+}
+
+fn time(time: &Time) -> String {
+    format!(
+        "::const_config::Time {{ hour: {}, minute: {}, second: {}, nanosecond: {} }}",
+        time.hour, time.minute, time.second, time.nanosecond,
+    )
+}
+
+/// Turns a key into a `snake_case` module name or a `SCREAMING_SNAKE_CASE`
+/// constant name, or `None` if the key has no letters or digits.
+fn identifier(key: &str, module: bool) -> Option<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut word = String::new();
+    let chars: Vec<char> = key.chars().collect();
+    for (idx, &ch) in chars.iter().enumerate() {
+        if !ch.is_ascii_alphanumeric() {
+            if !word.is_empty() {
+                words.push(std::mem::take(&mut word));
+            }
+            continue;
+        }
+        if ch.is_ascii_uppercase() && !word.is_empty() {
+            let prev = chars[idx - 1];
+            let next_lower = chars.get(idx + 1).is_some_and(|ch| ch.is_ascii_lowercase());
+            if prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower)
+            {
+                words.push(std::mem::take(&mut word));
+            }
+        }
+        word.push(ch);
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut name = words.join("_");
+    if module {
+        name.make_ascii_lowercase();
+    } else {
+        name.make_ascii_uppercase();
+    }
+    if name.starts_with(|ch: char| ch.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if module {
+        match name.as_str() {
+            "crate" | "self" | "super" => name.push('_'),
+            _ if KEYWORDS.contains(&name.as_str()) => name.insert_str(0, "r#"),
+            _ => {}
+        }
+    }
+    Some(name)
+}
+
+/// Rust keywords that are only usable as identifiers in their raw form.
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,13 +10,28 @@ pub enum Error {
     MsgPack(#[from] rmp_serde::decode::Error),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known. MessagePack errors do
+    /// not report an offset, so this is always `None`.
+    pub fn location(&self) -> Option<Location> {
+        None
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    Ok(rmp_serde::from_read(input)?)
+}
+
 pub fn generate<I, O>(input: I, output: O) -> Result<()>
 where
     I: Read,
     O: Write,
 {
-    crate::generate(rmp_serde::from_read(input)?, output)?;
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
@@ -71,6 +71,8 @@ fn value_hash(param: u32, key: &Value) -> u32 {
             ])
         }
         Value::Float(val) => {
+            // All NaNs are the same key, whatever their sign and payload.
+            let val = if val.is_nan() { &f64::NAN } else { val };
             if *val < 0.0f64 {
                 if ((*val as i128) as f64) == *val {
                     let val = u128::from_ne_bytes((*val as i128).to_ne_bytes());
@@ -183,13 +185,12 @@ impl Phf {
         let mut buckets = Vec::with_capacity(obj.len());
         buckets.resize(obj.len(), Vec::new());
 
-        let mut idx = 0u32;
-        for (key, _) in obj.iter() {
-            buckets[(jenkins_hash(jenkins_mix(0), key.as_bytes()) as usize) % obj.len()].push(idx);
-            idx += 1;
+        for (idx, (key, _)) in obj.iter().enumerate() {
+            buckets[(jenkins_hash(jenkins_mix(0), key.as_bytes()) as usize) % obj.len()]
+                .push(idx as u32);
         }
 
-        let mut sorted_buckets: Vec<usize> = (0..obj.len()).into_iter().collect();
+        let mut sorted_buckets: Vec<usize> = (0..obj.len()).collect();
         sorted_buckets.sort_by(|l, r| std::cmp::Ord::cmp(&buckets[*r].len(), &buckets[*l].len()));
 
         let mut slots: Vec<u32> = Vec::with_capacity(obj.len());
@@ -203,7 +204,7 @@ impl Phf {
             let mut item: usize = 0;
             slots.clear();
 
-            while (item as usize) < bucket.len() {
+            while item < bucket.len() {
                 let slot = (jenkins_hash(jenkins_mix(d), obj[bucket[item] as usize].0.as_bytes())
                     as usize
                     % obj.len()) as u32;
@@ -241,13 +242,11 @@ impl Phf {
         let mut buckets = Vec::with_capacity(map.len());
         buckets.resize(map.len(), Vec::new());
 
-        let mut idx = 0u32;
-        for (key, _) in map.iter() {
-            buckets[(value_hash(jenkins_mix(0), key) as usize) % map.len()].push(idx);
-            idx += 1;
+        for (idx, (key, _)) in map.iter().enumerate() {
+            buckets[(value_hash(0, key) as usize) % map.len()].push(idx as u32);
         }
 
-        let mut sorted_buckets: Vec<usize> = (0..map.len()).into_iter().collect();
+        let mut sorted_buckets: Vec<usize> = (0..map.len()).collect();
         sorted_buckets.sort_by(|l, r| std::cmp::Ord::cmp(&buckets[*r].len(), &buckets[*l].len()));
 
         let mut slots: Vec<u32> = Vec::with_capacity(map.len());
@@ -261,9 +260,9 @@ impl Phf {
             let mut item: usize = 0;
             slots.clear();
 
-            while (item as usize) < bucket.len() {
-                let slot = ((value_hash(jenkins_mix(d), &map[bucket[item] as usize].0) as usize)
-                    % map.len()) as u32;
+            while item < bucket.len() {
+                let slot =
+                    ((value_hash(d, &map[bucket[item] as usize].0) as usize) % map.len()) as u32;
                 if values[slot as usize] != map.len() as u32 {
                     d += 1;
                     item = 0;
@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,21 +10,38 @@ pub enum Error {
     Pickle(#[from] serde_pickle::error::Error),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Pickle(err) => match err {
+                serde_pickle::Error::Eval(_, offset) => Some(Location::from_byte_offset(*offset)),
+                _ => None,
+            },
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    Ok(serde_pickle::from_reader(
+        input,
+        serde_pickle::DeOptions::new()
+            .decode_strings()
+            .keep_restore_state(),
+    )?)
+}
+
 pub fn generate<I, O>(input: I, output: O) -> Result<()>
 where
     I: Read,
     O: Write,
 {
-    crate::generate(
-        serde_pickle::from_reader(
-            input,
-            serde_pickle::DeOptions::new()
-                .decode_strings()
-                .keep_restore_state(),
-        )?,
-        output,
-    )?;
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
@@ -0,0 +1,68 @@
+use crate::{ArrayMerge, Value};
+use thiserror::Error;
+
+/// The top-level key holding the named profiles of a config.
+pub const PROFILE_KEY: &str = "profile";
+
+/// Error produced by [`Value::select_profile`].
+#[derive(Debug, Error)]
+pub enum ProfileError {
+    #[error("profiles require the config to be an object")]
+    NotAnObject,
+    #[error("`{PROFILE_KEY}` must be a table of named profiles")]
+    NotATable,
+    #[error("profile `{0}` must be a table")]
+    ProfileNotATable(String),
+    #[error("profile `{name}` not found, available profiles are: {available}")]
+    Missing { name: String, available: String },
+    #[error("profile `{0}` not found, the config has no `{PROFILE_KEY}` table")]
+    NoProfiles(String),
+}
+
+impl Value {
+    /// Selects a profile following the `[profile.<name>]` convention.
+    ///
+    /// The top-level `profile` table is removed from the config, and if
+    /// `name` is given, the profile with that name is deep-merged on top of
+    /// the remaining keys. Profiles that are not selected are dropped.
+    pub fn select_profile(&mut self, name: Option<&str>) -> Result<(), ProfileError> {
+        let Value::Object(entries) = self else {
+            return Err(ProfileError::NotAnObject);
+        };
+        let profiles = entries
+            .iter()
+            .position(|(key, _)| key == PROFILE_KEY)
+            .map(|idx| entries.remove(idx).1);
+
+        let Some(name) = name else {
+            return Ok(());
+        };
+        let Some(profiles) = profiles else {
+            return Err(ProfileError::NoProfiles(name.into()));
+        };
+        let Value::Object(profiles) = profiles else {
+            return Err(ProfileError::NotATable);
+        };
+
+        let mut available = profiles
+            .iter()
+            .map(|(key, _)| format!("`{key}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        if available.is_empty() {
+            available.push_str("none");
+        }
+        let Some((_, profile)) = profiles.into_iter().find(|(key, _)| key == name) else {
+            return Err(ProfileError::Missing {
+                name: name.into(),
+                available,
+            });
+        };
+        if !matches!(profile, Value::Object(_)) {
+            return Err(ProfileError::ProfileNotATable(name.into()));
+        }
+
+        self.merge(profile, ArrayMerge::Replace);
+        Ok(())
+    }
+}
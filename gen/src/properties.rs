@@ -0,0 +1,173 @@
+use crate::{Location, Value};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error("malformed `\\uXXXX` escape")]
+    Unicode { location: Location },
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Unicode { location } => Some(*location),
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// Parses a Java `.properties` file into a flat object.
+///
+/// This follows `java.util.Properties`: lines starting with `#` or `!` are
+/// comments, and a key ends at the first unescaped `=`, `:` or whitespace.
+/// A line ending in an unescaped backslash continues on the next line,
+/// without the leading whitespace of that line. The escapes `\t`, `\n`,
+/// `\r`, `\f` and `\uXXXX` are recognized, and a backslash before any other
+/// character stands for that character. A key given more than once keeps
+/// its last value. All values are strings.
+///
+/// Dotted keys like `server.port` are kept as they are. See
+/// [`Value::expand_dotted_keys`] to turn them into nested objects.
+pub fn parse<I>(mut input: I) -> Result<Value>
+where
+    I: Read,
+{
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+
+    let mut entries: Vec<(String, Value)> = Vec::new();
+    let mut lines = text.lines().enumerate();
+    while let Some((idx, raw)) = lines.next() {
+        let line = raw.trim_start();
+        if line.is_empty() || line.starts_with(['#', '!']) {
+            continue;
+        }
+
+        // Join continued lines, remembering where each one starts for
+        // error locations.
+        let mut logical = String::from(line);
+        let mut starts = vec![(0, idx, start_column(raw, line))];
+        while ends_with_escape(&logical) {
+            logical.pop();
+            let Some((idx, raw)) = lines.next() else {
+                break;
+            };
+            let next = raw.trim_start();
+            starts.push((logical.len(), idx, start_column(raw, next)));
+            logical.push_str(next);
+        }
+        let location = |offset: usize| {
+            let &(start, line, column) = starts
+                .iter()
+                .rev()
+                .find(|(start, _, _)| *start <= offset)
+                .unwrap();
+            Location {
+                line: line + 1,
+                column: column + logical[start..offset].chars().count(),
+            }
+        };
+
+        let (key, rest) = unescape(&logical, 0, true, &location)?;
+        let rest = skip_separator(&logical, rest);
+        let (value, _) = unescape(&logical, rest, false, &location)?;
+        match entries.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, existing)) => *existing = Value::Str(value),
+            None => entries.push((key, Value::Str(value))),
+        }
+    }
+    Ok(Value::Object(entries))
+}
+
+/// The 1-based column of `trimmed`, a suffix of `line`.
+fn start_column(line: &str, trimmed: &str) -> usize {
+    line[..line.len() - trimmed.len()].chars().count() + 1
+}
+
+/// Whether `line` ends in an odd number of backslashes.
+fn ends_with_escape(line: &str) -> bool {
+    (line.len() - line.trim_end_matches('\\').len()) % 2 == 1
+}
+
+/// Skips the whitespace and the optional `=` or `:` between a key and its
+/// value, returning the offset of the value.
+fn skip_separator(line: &str, offset: usize) -> usize {
+    let is_space = |ch: char| matches!(ch, ' ' | '\t' | '\u{c}');
+    let rest = line[offset..].trim_start_matches(is_space);
+    let rest = rest.strip_prefix(['=', ':']).unwrap_or(rest);
+    let rest = rest.trim_start_matches(is_space);
+    line.len() - rest.len()
+}
+
+/// Unescapes `line` from `offset` up to the end of the key if `key` is
+/// set, or the end of the line otherwise. Returns the unescaped text and
+/// the offset after it.
+fn unescape(
+    line: &str,
+    offset: usize,
+    key: bool,
+    location: &dyn Fn(usize) -> Location,
+) -> Result<(String, usize)> {
+    let mut out = String::new();
+    let mut chars = line[offset..]
+        .char_indices()
+        .map(|(idx, ch)| (offset + idx, ch));
+    while let Some((idx, ch)) = chars.next() {
+        match ch {
+            '=' | ':' | ' ' | '\t' | '\u{c}' if key => return Ok((out, idx)),
+            '\\' => match chars.next() {
+                Some((_, 't')) => out.push('\t'),
+                Some((_, 'n')) => out.push('\n'),
+                Some((_, 'r')) => out.push('\r'),
+                Some((_, 'f')) => out.push('\u{c}'),
+                Some((_, 'u')) => {
+                    let malformed = || Error::Unicode {
+                        location: location(idx),
+                    };
+                    let mut code = hex4(line, idx + 2).ok_or_else(malformed)?;
+                    chars.nth(3);
+                    // Characters outside the BMP are given as a pair of
+                    // UTF-16 surrogates.
+                    if (0xd800..0xdc00).contains(&code) {
+                        let low = line[idx + 6..]
+                            .strip_prefix("\\u")
+                            .and_then(|_| hex4(line, idx + 8))
+                            .filter(|low| (0xdc00..0xe000).contains(low))
+                            .ok_or_else(malformed)?;
+                        code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                        chars.nth(5);
+                    }
+                    out.push(char::from_u32(code).ok_or_else(malformed)?);
+                }
+                Some((_, ch)) => out.push(ch),
+                None => {}
+            },
+            ch => out.push(ch),
+        }
+    }
+    Ok((out, line.len()))
+}
+
+/// The four hex digits at `offset` in `line`.
+fn hex4(line: &str, offset: usize) -> Option<u32> {
+    let hex = line.get(offset..offset + 4)?;
+    if !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
+    Ok(())
+}
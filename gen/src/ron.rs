@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,13 +10,38 @@ pub enum Error {
     Ron(#[from] ron::error::SpannedError),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Ron(err) => Some(Location {
+                line: err.position.line,
+                column: err.position.col,
+            }),
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    // `Value` is deserialized as an option, so that formats with a null
+    // value can hand it over. RON only accepts `Some(..)` and `None` for
+    // options unless implicit `Some` is enabled.
+    Ok(ron::Options::default()
+        .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+        .from_reader(input)?)
+}
+
 pub fn generate<I, O>(input: I, output: O) -> Result<()>
 where
     I: Read,
     O: Write,
 {
-    crate::generate(ron::de::from_reader(input)?, output)?;
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
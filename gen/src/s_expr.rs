@@ -1,4 +1,4 @@
-use crate::Value;
+use crate::{Location, Value};
 use std::io::{Read, Write};
 use thiserror::Error;
 
@@ -10,13 +10,33 @@ pub enum Error {
     SExpr(#[from] serde_lexpr::error::Error),
 }
 
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::SExpr(err) => err.location().map(|loc| Location {
+                line: loc.line(),
+                column: loc.column().max(1),
+            }),
+        }
+    }
+}
+
 type Result<T> = std::result::Result<T, Error>;
 
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    Ok(serde_lexpr::from_reader(input)?)
+}
+
 pub fn generate<I, O>(input: I, output: O) -> Result<()>
 where
     I: Read,
     O: Write,
 {
-    crate::generate(serde_lexpr::from_reader(input)?, output)?;
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
@@ -0,0 +1,917 @@
+use crate::Value;
+use crate::merge::key_eq;
+use regex_lite::Regex;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt;
+use thiserror::Error;
+
+/// How deep `$ref`s may nest before the schema is assumed to be recursing
+/// without making progress.
+const MAX_REF_DEPTH: usize = 64;
+
+/// Error produced by [`Value::validate`].
+#[derive(Debug, Error)]
+pub enum SchemaError {
+    #[error("invalid schema at `{location}`: {message}")]
+    Invalid { location: String, message: String },
+    #[error("config does not match the schema:{}", list(.0))]
+    Violations(Vec<Violation>),
+}
+
+/// A single place where a config does not match its schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// Dotted path of the offending value, empty for the root.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "<root>: {}", self.message)
+        } else {
+            write!(f, "`{}`: {}", self.path, self.message)
+        }
+    }
+}
+
+fn list(violations: &[Violation]) -> String {
+    violations
+        .iter()
+        .map(|violation| format!("\n  {violation}"))
+        .collect()
+}
+
+fn invalid(location: &str, message: impl Into<String>) -> SchemaError {
+    SchemaError::Invalid {
+        location: location.into(),
+        message: message.into(),
+    }
+}
+
+type Result<T> = std::result::Result<T, SchemaError>;
+
+impl Value {
+    /// Validates the value against a JSON Schema.
+    ///
+    /// A subset of draft 2020-12 is supported: `type`, `const`, `enum`, the
+    /// numeric, string, array and object assertions (including `pattern`
+    /// and `patternProperties`), `allOf`, `anyOf`, `oneOf`, `not`,
+    /// `if`/`then`/`else`, and `$ref`s within the schema, e.g. to
+    /// `#/$defs/...`. Annotations like `title` or `format` are ignored, and
+    /// `unevaluatedItems`, `unevaluatedProperties` and `$dynamicRef` are
+    /// rejected as unsupported.
+    ///
+    /// Dates and times are treated as strings in RFC 3339 form, and maps
+    /// as objects whose keys are converted to strings.
+    ///
+    /// Every violation is reported, not just the first.
+    pub fn validate(&self, schema: &Value) -> Result<()> {
+        let mut validator = Validator {
+            root: schema,
+            violations: Vec::new(),
+            depth: 0,
+        };
+        validator.validate(schema, "#", self, &mut String::new())?;
+        if validator.violations.is_empty() {
+            Ok(())
+        } else {
+            Err(SchemaError::Violations(validator.violations))
+        }
+    }
+}
+
+/// The keywords of a schema object.
+struct Keywords<'s>(&'s [(String, Value)]);
+
+impl<'s> Keywords<'s> {
+    fn get(&self, keyword: &str) -> Option<&'s Value> {
+        self.0
+            .iter()
+            .find(|(key, _)| key == keyword)
+            .map(|(_, value)| value)
+    }
+}
+
+struct Validator<'s> {
+    root: &'s Value,
+    violations: Vec<Violation>,
+    depth: usize,
+}
+
+impl<'s> Validator<'s> {
+    fn violation(&mut self, path: &str, message: impl Into<String>) {
+        self.violations.push(Violation {
+            path: path.into(),
+            message: message.into(),
+        });
+    }
+
+    /// Checks `value` against `schema` without recording violations.
+    fn is_valid(&mut self, schema: &'s Value, at: &str, value: &Value) -> Result<bool> {
+        let outer = std::mem::take(&mut self.violations);
+        let result = self.validate(schema, at, value, &mut String::new());
+        let valid = self.violations.is_empty();
+        self.violations = outer;
+        result.map(|_| valid)
+    }
+
+    fn validate(
+        &mut self,
+        schema: &'s Value,
+        at: &str,
+        value: &Value,
+        path: &mut String,
+    ) -> Result<()> {
+        let schema = match schema {
+            Value::Bool(true) => return Ok(()),
+            Value::Bool(false) => {
+                self.violation(path, "is not allowed by the schema");
+                return Ok(());
+            }
+            Value::Object(keywords) => Keywords(keywords),
+            _ => return Err(invalid(at, "a schema must be an object or a boolean")),
+        };
+
+        for keyword in ["$dynamicRef", "unevaluatedItems", "unevaluatedProperties"] {
+            if schema.get(keyword).is_some() {
+                return Err(invalid(at, format!("`{keyword}` is not supported")));
+            }
+        }
+
+        if let Some(reference) = schema.get("$ref") {
+            self.validate_ref(reference, at, value, path)?;
+        }
+        self.validate_any(&schema, at, value, path)?;
+        if number(value) {
+            self.validate_number(&schema, at, value, path)?;
+        }
+        if let Some(s) = string(value) {
+            self.validate_string(&schema, at, &s, path)?;
+        }
+        if let Value::Array(items) = value {
+            self.validate_array(&schema, at, items, path)?;
+        }
+        if let Some(entries) = self.entries(value, path) {
+            self.validate_object(&schema, at, value, &entries, path)?;
+        }
+        self.validate_applicators(&schema, at, value, path)
+    }
+
+    fn validate_ref(
+        &mut self,
+        reference: &Value,
+        at: &str,
+        value: &Value,
+        path: &mut String,
+    ) -> Result<()> {
+        let location = format!("{at}/$ref");
+        let Value::Str(reference) = reference else {
+            return Err(invalid(&location, "expected a string"));
+        };
+        let Some(pointer) = reference.strip_prefix('#') else {
+            return Err(invalid(
+                &location,
+                format!(
+                    "cannot resolve `{reference}`, only references within the schema are supported"
+                ),
+            ));
+        };
+        if !pointer.is_empty() && !pointer.starts_with('/') {
+            return Err(invalid(
+                &location,
+                format!("cannot resolve `{reference}`, anchors are not supported"),
+            ));
+        }
+
+        let mut target = self.root;
+        for token in pointer.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            let child = match target {
+                Value::Object(entries) => Keywords(entries).get(&token),
+                Value::Array(items) => token.parse::<usize>().ok().and_then(|idx| items.get(idx)),
+                _ => None,
+            };
+            target =
+                child.ok_or_else(|| invalid(&location, format!("`{reference}` does not exist")))?;
+        }
+
+        if self.depth == MAX_REF_DEPTH {
+            return Err(invalid(&location, "`$ref`s are nested too deeply"));
+        }
+        self.depth += 1;
+        let result = self.validate(target, reference, value, path);
+        self.depth -= 1;
+        result
+    }
+
+    /// Keywords that apply to any kind of value.
+    fn validate_any(
+        &mut self,
+        schema: &Keywords<'s>,
+        at: &str,
+        value: &Value,
+        path: &str,
+    ) -> Result<()> {
+        if let Some(ty) = schema.get("type") {
+            let location = format!("{at}/type");
+            let types = match ty {
+                Value::Str(ty) => vec![ty.as_str()],
+                Value::Array(types) => types
+                    .iter()
+                    .map(|ty| match ty {
+                        Value::Str(ty) => Ok(ty.as_str()),
+                        _ => Err(invalid(
+                            &location,
+                            "expected a string or an array of strings",
+                        )),
+                    })
+                    .collect::<Result<_>>()?,
+                _ => {
+                    return Err(invalid(
+                        &location,
+                        "expected a string or an array of strings",
+                    ));
+                }
+            };
+            for ty in &types {
+                if !matches!(
+                    *ty,
+                    "null" | "boolean" | "integer" | "number" | "string" | "array" | "object"
+                ) {
+                    return Err(invalid(&location, format!("unknown type `{ty}`")));
+                }
+            }
+            if !types.iter().any(|ty| has_type(value, ty)) {
+                self.violation(
+                    path,
+                    format!("expected {}, found {}", types.join(" or "), describe(value)),
+                );
+            }
+        }
+
+        if let Some(expected) = schema.get("const")
+            && !json_eq(expected, value)
+        {
+            self.violation(
+                path,
+                format!("expected {}, found {}", render(expected), describe(value)),
+            );
+        }
+
+        if let Some(options) = schema.get("enum") {
+            let Value::Array(options) = options else {
+                return Err(invalid(&format!("{at}/enum"), "expected an array"));
+            };
+            if !options.iter().any(|option| json_eq(option, value)) {
+                let options: Vec<_> = options.iter().map(render).collect();
+                self.violation(
+                    path,
+                    format!(
+                        "expected one of {}, found {}",
+                        options.join(", "),
+                        describe(value)
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_number(
+        &mut self,
+        schema: &Keywords<'s>,
+        at: &str,
+        value: &Value,
+        path: &str,
+    ) -> Result<()> {
+        let bounds = [
+            (
+                "minimum",
+                "at least",
+                &[Ordering::Greater, Ordering::Equal][..],
+            ),
+            ("exclusiveMinimum", "greater than", &[Ordering::Greater][..]),
+            ("maximum", "at most", &[Ordering::Less, Ordering::Equal][..]),
+            ("exclusiveMaximum", "less than", &[Ordering::Less][..]),
+        ];
+        for (keyword, relation, allowed) in bounds {
+            let Some(bound) = schema.get(keyword) else {
+                continue;
+            };
+            if !number(bound) {
+                return Err(invalid(&format!("{at}/{keyword}"), "expected a number"));
+            }
+            if let Some(ordering) = compare(value, bound)
+                && !allowed.contains(&ordering)
+            {
+                self.violation(
+                    path,
+                    format!(
+                        "must be {relation} {}, found {}",
+                        render(bound),
+                        render(value)
+                    ),
+                );
+            }
+        }
+
+        if let Some(divisor) = schema.get("multipleOf") {
+            if !number(divisor) || compare(divisor, &Value::UInt(0)) != Some(Ordering::Greater) {
+                return Err(invalid(
+                    &format!("{at}/multipleOf"),
+                    "expected a number greater than 0",
+                ));
+            }
+            if !is_multiple(value, divisor) {
+                self.violation(
+                    path,
+                    format!(
+                        "must be a multiple of {}, found {}",
+                        render(divisor),
+                        render(value)
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_string(
+        &mut self,
+        schema: &Keywords<'s>,
+        at: &str,
+        s: &str,
+        path: &str,
+    ) -> Result<()> {
+        let len = s.chars().count();
+        if let Some(min) = schema.get("minLength") {
+            let min = count(min, &format!("{at}/minLength"))?;
+            if len < min {
+                self.violation(
+                    path,
+                    format!("must be at least {min} characters long, found {len}"),
+                );
+            }
+        }
+        if let Some(max) = schema.get("maxLength") {
+            let max = count(max, &format!("{at}/maxLength"))?;
+            if len > max {
+                self.violation(
+                    path,
+                    format!("must be at most {max} characters long, found {len}"),
+                );
+            }
+        }
+        if let Some(pattern) = schema.get("pattern") {
+            let regex = regex(pattern, &format!("{at}/pattern"))?;
+            if !regex.is_match(s) {
+                self.violation(
+                    path,
+                    format!("must match the pattern `{}`, found {s:?}", regex.as_str()),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_array(
+        &mut self,
+        schema: &Keywords<'s>,
+        at: &str,
+        items: &[Value],
+        path: &mut String,
+    ) -> Result<()> {
+        let mut prefix_len = 0;
+        if let Some(prefix) = schema.get("prefixItems") {
+            let Value::Array(prefix) = prefix else {
+                return Err(invalid(&format!("{at}/prefixItems"), "expected an array"));
+            };
+            for (idx, (schema, item)) in prefix.iter().zip(items).enumerate() {
+                let len = path.len();
+                path.push_str(&format!("[{idx}]"));
+                self.validate(schema, &format!("{at}/prefixItems/{idx}"), item, path)?;
+                path.truncate(len);
+            }
+            prefix_len = prefix.len();
+        }
+        if let Some(schema) = schema.get("items") {
+            let at = format!("{at}/items");
+            for (idx, item) in items.iter().enumerate().skip(prefix_len) {
+                let len = path.len();
+                path.push_str(&format!("[{idx}]"));
+                self.validate(schema, &at, item, path)?;
+                path.truncate(len);
+            }
+        }
+
+        if let Some(contains) = schema.get("contains") {
+            let mut matches = 0;
+            for item in items {
+                if self.is_valid(contains, &format!("{at}/contains"), item)? {
+                    matches += 1;
+                }
+            }
+            let min = match schema.get("minContains") {
+                Some(min) => count(min, &format!("{at}/minContains"))?,
+                None => 1,
+            };
+            if matches < min {
+                self.violation(
+                    path,
+                    format!("must contain at least {min} matching item(s), found {matches}"),
+                );
+            }
+            if let Some(max) = schema.get("maxContains") {
+                let max = count(max, &format!("{at}/maxContains"))?;
+                if matches > max {
+                    self.violation(
+                        path,
+                        format!("must contain at most {max} matching item(s), found {matches}"),
+                    );
+                }
+            }
+        }
+
+        if let Some(min) = schema.get("minItems") {
+            let min = count(min, &format!("{at}/minItems"))?;
+            if items.len() < min {
+                self.violation(
+                    path,
+                    format!("must have at least {min} item(s), found {}", items.len()),
+                );
+            }
+        }
+        if let Some(max) = schema.get("maxItems") {
+            let max = count(max, &format!("{at}/maxItems"))?;
+            if items.len() > max {
+                self.violation(
+                    path,
+                    format!("must have at most {max} item(s), found {}", items.len()),
+                );
+            }
+        }
+        if let Some(unique) = schema.get("uniqueItems") {
+            let Value::Bool(unique) = unique else {
+                return Err(invalid(&format!("{at}/uniqueItems"), "expected a boolean"));
+            };
+            if *unique {
+                for (idx, item) in items.iter().enumerate() {
+                    if let Some(first) = items[..idx].iter().position(|other| json_eq(other, item))
+                    {
+                        self.violation(
+                            path,
+                            format!("items must be unique, but [{idx}] repeats [{first}]"),
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The entries of an object or map, with map keys converted to strings.
+    fn entries<'v>(&mut self, value: &'v Value, path: &str) -> Option<Vec<(String, &'v Value)>> {
+        match value {
+            Value::Object(entries) => Some(
+                entries
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value))
+                    .collect(),
+            ),
+            Value::Map(entries) => Some(
+                entries
+                    .iter()
+                    .filter_map(|(key, value)| match key_string(key) {
+                        Some(key) => Some((key, value)),
+                        None => {
+                            self.violation(
+                                path,
+                                format!("{} cannot be used as an object key", describe(key)),
+                            );
+                            None
+                        }
+                    })
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    fn validate_object(
+        &mut self,
+        schema: &Keywords<'s>,
+        at: &str,
+        value: &Value,
+        entries: &[(String, &Value)],
+        path: &mut String,
+    ) -> Result<()> {
+        let properties = match schema.get("properties") {
+            None => None,
+            Some(Value::Object(properties)) => Some(Keywords(properties)),
+            Some(_) => return Err(invalid(&format!("{at}/properties"), "expected an object")),
+        };
+        let patterns = match schema.get("patternProperties") {
+            None => Vec::new(),
+            Some(Value::Object(patterns)) => patterns
+                .iter()
+                .map(|(pattern, schema)| {
+                    let location = format!("{at}/patternProperties/{}", escape(pattern));
+                    let regex = regex(&Value::Str(pattern.clone()), &location)?;
+                    Ok((regex, location, schema))
+                })
+                .collect::<Result<_>>()?,
+            Some(_) => {
+                return Err(invalid(
+                    &format!("{at}/patternProperties"),
+                    "expected an object",
+                ));
+            }
+        };
+        let additional = schema.get("additionalProperties");
+        let names = schema.get("propertyNames");
+
+        for (key, value) in entries {
+            let len = path.len();
+            if !path.is_empty() {
+                path.push('.');
+            }
+            path.push_str(key);
+
+            let mut evaluated = false;
+            if let Some(schema) = properties.as_ref().and_then(|p| p.get(key)) {
+                evaluated = true;
+                let location = format!("{at}/properties/{}", escape(key));
+                self.validate(schema, &location, value, path)?;
+            }
+            for (regex, location, schema) in &patterns {
+                if regex.is_match(key) {
+                    evaluated = true;
+                    self.validate(schema, location, value, path)?;
+                }
+            }
+            match additional {
+                Some(Value::Bool(false)) if !evaluated => {
+                    self.violation(path, "key is not allowed by the schema");
+                }
+                Some(schema) if !evaluated => {
+                    self.validate(schema, &format!("{at}/additionalProperties"), value, path)?;
+                }
+                _ => {}
+            }
+            if let Some(names) = names
+                && !self.is_valid(
+                    names,
+                    &format!("{at}/propertyNames"),
+                    &Value::Str(key.clone()),
+                )?
+            {
+                self.violation(path, "key does not match `propertyNames`");
+            }
+
+            path.truncate(len);
+        }
+
+        let has = |key: &str| entries.iter().any(|(k, _)| k == key);
+        if let Some(required) = schema.get("required") {
+            for key in strings(required, &format!("{at}/required"))? {
+                if !has(key) {
+                    self.violation(path, format!("missing required key `{key}`"));
+                }
+            }
+        }
+        if let Some(dependents) = schema.get("dependentRequired") {
+            let location = format!("{at}/dependentRequired");
+            let Value::Object(dependents) = dependents else {
+                return Err(invalid(&location, "expected an object"));
+            };
+            for (key, required) in dependents {
+                let required = strings(required, &format!("{location}/{}", escape(key)))?;
+                if !has(key) {
+                    continue;
+                }
+                for other in required {
+                    if !has(other) {
+                        self.violation(path, format!("key `{key}` requires key `{other}`"));
+                    }
+                }
+            }
+        }
+        if let Some(dependents) = schema.get("dependentSchemas") {
+            let location = format!("{at}/dependentSchemas");
+            let Value::Object(dependents) = dependents else {
+                return Err(invalid(&location, "expected an object"));
+            };
+            for (key, schema) in dependents {
+                if has(key) {
+                    let location = format!("{location}/{}", escape(key));
+                    self.validate(schema, &location, value, path)?;
+                }
+            }
+        }
+
+        if let Some(min) = schema.get("minProperties") {
+            let min = count(min, &format!("{at}/minProperties"))?;
+            if entries.len() < min {
+                self.violation(
+                    path,
+                    format!("must have at least {min} key(s), found {}", entries.len()),
+                );
+            }
+        }
+        if let Some(max) = schema.get("maxProperties") {
+            let max = count(max, &format!("{at}/maxProperties"))?;
+            if entries.len() > max {
+                self.violation(
+                    path,
+                    format!("must have at most {max} key(s), found {}", entries.len()),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// `allOf`, `anyOf`, `oneOf`, `not` and `if`/`then`/`else`.
+    fn validate_applicators(
+        &mut self,
+        schema: &Keywords<'s>,
+        at: &str,
+        value: &Value,
+        path: &mut String,
+    ) -> Result<()> {
+        let schemas = |keyword: &str| -> Result<&'s [Value]> {
+            match schema.get(keyword) {
+                None => Ok(&[]),
+                Some(Value::Array(schemas)) if !schemas.is_empty() => Ok(schemas),
+                Some(_) => Err(invalid(
+                    &format!("{at}/{keyword}"),
+                    "expected a non-empty array",
+                )),
+            }
+        };
+
+        for (idx, schema) in schemas("allOf")?.iter().enumerate() {
+            self.validate(schema, &format!("{at}/allOf/{idx}"), value, path)?;
+        }
+
+        let any_of = schemas("anyOf")?;
+        if !any_of.is_empty() {
+            let mut matched = false;
+            for (idx, schema) in any_of.iter().enumerate() {
+                if self.is_valid(schema, &format!("{at}/anyOf/{idx}"), value)? {
+                    matched = true;
+                    break;
+                }
+            }
+            if !matched {
+                self.violation(path, "does not match any of the schemas in `anyOf`");
+            }
+        }
+
+        let one_of = schemas("oneOf")?;
+        if !one_of.is_empty() {
+            let mut matches = 0;
+            for (idx, schema) in one_of.iter().enumerate() {
+                if self.is_valid(schema, &format!("{at}/oneOf/{idx}"), value)? {
+                    matches += 1;
+                }
+            }
+            match matches {
+                0 => self.violation(path, "does not match any of the schemas in `oneOf`"),
+                1 => {}
+                n => self.violation(
+                    path,
+                    format!("matches {n} of the schemas in `oneOf`, expected exactly one"),
+                ),
+            }
+        }
+
+        if let Some(not) = schema.get("not")
+            && self.is_valid(not, &format!("{at}/not"), value)?
+        {
+            self.violation(path, "must not match the schema in `not`");
+        }
+
+        if let Some(condition) = schema.get("if") {
+            let branch = if self.is_valid(condition, &format!("{at}/if"), value)? {
+                "then"
+            } else {
+                "else"
+            };
+            if let Some(schema) = schema.get(branch) {
+                self.validate(schema, &format!("{at}/{branch}"), value, path)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Escapes an object key for use in a JSON Pointer.
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn count(value: &Value, location: &str) -> Result<usize> {
+    match value {
+        Value::UInt(n) => Ok(usize::try_from(*n).unwrap_or(usize::MAX)),
+        Value::Float(n) if *n >= 0.0 && n.fract() == 0.0 => Ok(*n as usize),
+        _ => Err(invalid(location, "expected a non-negative integer")),
+    }
+}
+
+fn strings<'s>(value: &'s Value, location: &str) -> Result<Vec<&'s str>> {
+    let err = || invalid(location, "expected an array of strings");
+    let Value::Array(items) = value else {
+        return Err(err());
+    };
+    items
+        .iter()
+        .map(|item| match item {
+            Value::Str(s) => Ok(s.as_str()),
+            _ => Err(err()),
+        })
+        .collect()
+}
+
+fn regex(pattern: &Value, location: &str) -> Result<Regex> {
+    let Value::Str(pattern) = pattern else {
+        return Err(invalid(location, "expected a string"));
+    };
+    Regex::new(pattern).map_err(|err| invalid(location, format!("invalid pattern: {err}")))
+}
+
+fn number(value: &Value) -> bool {
+    matches!(value, Value::UInt(_) | Value::Int(_) | Value::Float(_))
+}
+
+/// The string form of a value, if it counts as a string.
+fn string(value: &Value) -> Option<Cow<'_, str>> {
+    match value {
+        Value::Str(s) => Some(Cow::Borrowed(s)),
+        Value::Date(date) => Some(Cow::Owned(date.to_string())),
+        Value::Time(time) => Some(Cow::Owned(time.to_string())),
+        Value::DateTime(datetime) => Some(Cow::Owned(datetime.to_string())),
+        _ => None,
+    }
+}
+
+fn key_string(key: &Value) -> Option<String> {
+    match key {
+        Value::Null => Some("null".into()),
+        Value::Bool(key) => Some(key.to_string()),
+        Value::UInt(key) => Some(key.to_string()),
+        Value::Int(key) => Some(key.to_string()),
+        Value::Float(key) => Some(key.to_string()),
+        key => string(key).map(Cow::into_owned),
+    }
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "null" => matches!(value, Value::Null),
+        "boolean" => matches!(value, Value::Bool(_)),
+        "integer" => match value {
+            Value::UInt(_) | Value::Int(_) => true,
+            Value::Float(value) => value.is_finite() && value.fract() == 0.0,
+            _ => false,
+        },
+        "number" => number(value),
+        "string" => string(value).is_some(),
+        "array" => matches!(value, Value::Array(_)),
+        "object" => matches!(value, Value::Object(_) | Value::Map(_)),
+        _ => false,
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::UInt(value) => Some(*value as f64),
+        Value::Int(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+fn as_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::UInt(value) => i128::try_from(*value).ok(),
+        Value::Int(value) => Some(*value),
+        Value::Float(value) if value.fract() == 0.0 && value.abs() < i128::MAX as f64 => {
+            Some(*value as i128)
+        }
+        _ => None,
+    }
+}
+
+/// Compares two numbers by value.
+fn compare(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::UInt(lhs), Value::UInt(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::Int(lhs), Value::Int(rhs)) => Some(lhs.cmp(rhs)),
+        (Value::UInt(lhs), Value::Int(rhs)) => Some(match u128::try_from(*rhs) {
+            Ok(rhs) => lhs.cmp(&rhs),
+            Err(_) => Ordering::Greater,
+        }),
+        (Value::Int(_), Value::UInt(_)) => compare(rhs, lhs).map(Ordering::reverse),
+        _ => as_f64(lhs)?.partial_cmp(&as_f64(rhs)?),
+    }
+}
+
+fn is_multiple(value: &Value, divisor: &Value) -> bool {
+    if let (Some(value), Some(divisor)) = (as_i128(value), as_i128(divisor)) {
+        return value % divisor == 0;
+    }
+    let (Some(value), Some(divisor)) = (as_f64(value), as_f64(divisor)) else {
+        return false;
+    };
+    let quotient = value / divisor;
+    (quotient - quotient.round()).abs() <= 1e-9 * quotient.abs().max(1.0)
+}
+
+/// Equality as defined by JSON Schema: numbers compare by value, objects
+/// ignore the order of their keys, and dates compare as strings.
+fn json_eq(lhs: &Value, rhs: &Value) -> bool {
+    match (lhs, rhs) {
+        (Value::Array(lhs), Value::Array(rhs)) => {
+            lhs.len() == rhs.len() && lhs.iter().zip(rhs).all(|(lhs, rhs)| json_eq(lhs, rhs))
+        }
+        (Value::Object(_) | Value::Map(_), Value::Object(_) | Value::Map(_)) => {
+            let entries = |value: &Value| -> Option<Vec<(String, Value)>> {
+                match value {
+                    Value::Object(entries) => Some(entries.clone()),
+                    Value::Map(entries) => entries
+                        .iter()
+                        .map(|(key, value)| Some((key_string(key)?, value.clone())))
+                        .collect(),
+                    _ => None,
+                }
+            };
+            let (Some(lhs), Some(rhs)) = (entries(lhs), entries(rhs)) else {
+                return false;
+            };
+            lhs.len() == rhs.len()
+                && lhs.iter().all(|(key, lhs)| {
+                    rhs.iter()
+                        .any(|(other, rhs)| key == other && json_eq(lhs, rhs))
+                })
+        }
+        _ => match (string(lhs), string(rhs)) {
+            (Some(lhs), Some(rhs)) => lhs == rhs,
+            _ => key_eq(lhs, rhs),
+        },
+    }
+}
+
+/// A value in JSON notation, for messages.
+fn render(value: &Value) -> String {
+    match value {
+        Value::Null => "null".into(),
+        Value::Bool(value) => value.to_string(),
+        Value::UInt(value) => value.to_string(),
+        Value::Int(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::Bytes(_) => "<bytes>".into(),
+        Value::Array(items) => {
+            let items: Vec<_> = items.iter().map(render).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(entries) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(key, value)| format!("{key:?}: {}", render(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        Value::Map(entries) => {
+            let entries: Vec<_> = entries
+                .iter()
+                .map(|(key, value)| format!("{}: {}", render(key), render(value)))
+                .collect();
+            format!("{{{}}}", entries.join(", "))
+        }
+        value => format!("{:?}", string(value).unwrap_or_default()),
+    }
+}
+
+/// The kind of a value along with the value itself, for messages.
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".into(),
+        Value::Bool(value) => format!("boolean `{value}`"),
+        Value::UInt(_) | Value::Int(_) => format!("integer `{}`", render(value)),
+        Value::Float(_) => format!("number `{}`", render(value)),
+        Value::Date(date) => format!("date `{date}`"),
+        Value::Time(time) => format!("time `{time}`"),
+        Value::DateTime(datetime) => format!("date-time `{datetime}`"),
+        Value::Str(s) if s.chars().count() > 32 => {
+            let s: String = s.chars().take(32).collect();
+            format!("string \"{}...\"", s.escape_debug())
+        }
+        Value::Str(s) => format!("string {s:?}"),
+        Value::Bytes(_) => "bytes".into(),
+        Value::Array(_) => "array".into(),
+        Value::Object(_) | Value::Map(_) => "object".into(),
+    }
+}
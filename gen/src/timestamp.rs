@@ -0,0 +1,172 @@
+use crate::{Date, DateTime, Time, Value};
+
+impl Value {
+    /// Turns string values holding a timestamp into [`Value::Date`],
+    /// [`Value::Time`] or [`Value::DateTime`], the way TOML has them.
+    ///
+    /// Recognized are the RFC 3339 forms, i.e. dates like `2024-01-02`, times
+    /// like `10:30:05` or `10:30:05.25`, and date-times like
+    /// `2024-01-02T10:30:05Z` or `2024-01-02 10:30:05+01:00`, where the
+    /// offset is optional. Date-times also follow the looser YAML timestamp
+    /// syntax, which allows single digit months, days and hours, any amount
+    /// of whitespace instead of the `T`, and offsets like ` -5` or `+5:30`.
+    ///
+    /// Anything that is not a valid date or time, like `2024-02-30`, stays a
+    /// string. Keys of objects and maps are left untouched.
+    pub fn detect_timestamps(&mut self) {
+        match self {
+            Value::Str(s) => {
+                if let Some(value) = parse_timestamp(s) {
+                    *self = value;
+                }
+            }
+            Value::Array(array) => array.iter_mut().for_each(Value::detect_timestamps),
+            Value::Object(entries) => entries
+                .iter_mut()
+                .for_each(|(_, value)| value.detect_timestamps()),
+            Value::Map(entries) => entries
+                .iter_mut()
+                .for_each(|(_, value)| value.detect_timestamps()),
+            _ => {}
+        }
+    }
+}
+
+pub(crate) fn parse_timestamp(s: &str) -> Option<Value> {
+    let mut cursor = Cursor(s.as_bytes());
+    if s.len() > 2 && s.as_bytes()[2] == b':' {
+        let time = cursor.time(2)?;
+        return cursor.is_empty().then_some(Value::Time(time));
+    }
+
+    let year = cursor.digits(4, 4)?;
+    cursor.expect(b'-')?;
+    let month = cursor.digits(1, 2)?;
+    cursor.expect(b'-')?;
+    let day = cursor.digits(1, 2)?;
+    let date = date(year, month, day)?;
+    if cursor.is_empty() {
+        // A lone date needs two digit months and days.
+        return (s.len() == 10).then_some(Value::Date(date));
+    }
+
+    match cursor.0.first()? {
+        b'T' | b't' => cursor.0 = &cursor.0[1..],
+        b' ' | b'\t' => cursor.skip_whitespace(),
+        _ => return None,
+    }
+    let time = cursor.time(1)?;
+    cursor.skip_whitespace();
+    let offset = match cursor.0.first() {
+        None => None,
+        Some(b'Z' | b'z') => {
+            cursor.0 = &cursor.0[1..];
+            Some(0)
+        }
+        Some(&sign @ (b'+' | b'-')) => {
+            cursor.0 = &cursor.0[1..];
+            let hours = cursor.digits(1, 2)?;
+            let minutes = if cursor.expect(b':').is_some() {
+                cursor.digits(2, 2)?
+            } else {
+                0
+            };
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = (hours * 60 + minutes) as i16;
+            Some(if sign == b'-' { -offset } else { offset })
+        }
+        Some(_) => return None,
+    };
+    cursor
+        .is_empty()
+        .then_some(Value::DateTime(DateTime { date, time, offset }))
+}
+
+fn date(year: u32, month: u32, day: u32) -> Option<Date> {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    (1..=days).contains(&day).then_some(Date {
+        year: year as u16,
+        month: month as u8,
+        day: day as u8,
+    })
+}
+
+struct Cursor<'a>(&'a [u8]);
+
+impl Cursor<'_> {
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        let rest = self.0.strip_prefix(&[byte])?;
+        self.0 = rest;
+        Some(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let [b' ' | b'\t', rest @ ..] = self.0 {
+            self.0 = rest;
+        }
+    }
+
+    /// Parses between `min` and `max` decimal digits.
+    fn digits(&mut self, min: usize, max: usize) -> Option<u32> {
+        let len = self
+            .0
+            .iter()
+            .take(max)
+            .take_while(|byte| byte.is_ascii_digit())
+            .count();
+        if len < min {
+            return None;
+        }
+        let value = self.0[..len]
+            .iter()
+            .fold(0, |value, byte| value * 10 + (byte - b'0') as u32);
+        self.0 = &self.0[len..];
+        Some(value)
+    }
+
+    /// Parses `HH:MM:SS` with an optional fraction, where the hour has at
+    /// least `min_hour_digits` digits.
+    fn time(&mut self, min_hour_digits: usize) -> Option<Time> {
+        let hour = self.digits(min_hour_digits, 2)?;
+        self.expect(b':')?;
+        let minute = self.digits(2, 2)?;
+        self.expect(b':')?;
+        let second = self.digits(2, 2)?;
+        let mut nanosecond = 0;
+        if self.expect(b'.').is_some() {
+            let len = self
+                .0
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count();
+            if len == 0 {
+                return None;
+            }
+            // Digits past nanoseconds are dropped.
+            for idx in 0..9 {
+                let digit = self.0[..len].get(idx).map_or(0, |byte| byte - b'0');
+                nanosecond = nanosecond * 10 + digit as u32;
+            }
+            self.0 = &self.0[len..];
+        }
+        (hour <= 23 && minute <= 59 && second <= 59).then_some(Time {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond,
+        })
+    }
+}
@@ -4,7 +4,7 @@
 // and then convert to a `crate::Value` to ensure that
 // Times and DateTimes are preserved.
 
-use crate::{Date, DateTime, Time, Value};
+use crate::{Date, DateTime, Location, Time, Value};
 use std::io::{self, Read, Write};
 use thiserror::Error;
 use toml::Value as TomlValue;
@@ -13,20 +13,46 @@ use toml::Value as TomlValue;
 pub enum Error {
     #[error(transparent)]
     IO(#[from] io::Error),
-    #[error(transparent)]
-    Toml(#[from] toml::de::Error),
+    #[error("{}", .source.message())]
+    Toml {
+        source: toml::de::Error,
+        location: Option<Location>,
+    },
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Toml { location, .. } => *location,
+        }
+    }
 }
 
 type Result<T> = std::result::Result<T, Error>;
 
-pub fn generate<I, O>(mut input: I, output: O) -> Result<()>
+pub fn parse<I>(mut input: I) -> Result<Value>
 where
     I: Read,
-    O: Write,
 {
     let mut toml_s = String::new();
     input.read_to_string(&mut toml_s)?;
-    crate::generate(transform(&::toml::from_str(&toml_s)?), output)?;
+    let toml = ::toml::from_str(&toml_s).map_err(|source: toml::de::Error| Error::Toml {
+        location: source
+            .span()
+            .map(|span| Location::from_offset(&toml_s, span.start)),
+        source,
+    })?;
+    Ok(transform(&toml))
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
     Ok(())
 }
 
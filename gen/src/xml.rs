@@ -0,0 +1,162 @@
+use crate::{Location, Value};
+use std::io::{Read, Write};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error(transparent)]
+    IO(#[from] std::io::Error),
+    #[error(transparent)]
+    Xml(#[from] roxmltree::Error),
+}
+
+impl Error {
+    /// Where in the input the error was found, if known.
+    pub fn location(&self) -> Option<Location> {
+        match self {
+            Error::IO(_) => None,
+            Error::Xml(err) => {
+                let pos = err.pos();
+                Some(Location {
+                    line: pos.row as usize,
+                    column: pos.col as usize,
+                })
+            }
+        }
+    }
+}
+
+type Result<T> = std::result::Result<T, Error>;
+
+/// How namespaced element and attribute names are turned into keys.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Namespaces {
+    /// Names are kept as written, e.g. `dev:port`.
+    #[default]
+    Prefix,
+    /// Prefixes are removed, e.g. `port`.
+    Strip,
+    /// Prefixes are replaced by the namespace URI in braces, e.g.
+    /// `{urn:example:device}port`.
+    Uri,
+}
+
+impl Namespaces {
+    /// Looks up a namespace handling by name, i.e. `prefix`, `strip` or
+    /// `uri`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "prefix" => Some(Self::Prefix),
+            "strip" => Some(Self::Strip),
+            "uri" => Some(Self::Uri),
+            _ => None,
+        }
+    }
+}
+
+/// Parses an XML document, keeping names as written. See [`parse_with`].
+pub fn parse<I>(input: I) -> Result<Value>
+where
+    I: Read,
+{
+    parse_with(input, Namespaces::Prefix)
+}
+
+/// Parses an XML document.
+///
+/// The result is an object with the root element as its only key. Every
+/// element becomes a value as follows:
+/// * Attributes become keys prefixed with `@`, e.g. `@id`.
+/// * Child elements become keys named after the element. Elements with the
+///   same name are collected in an array, at the position of the first one.
+/// * Text and CDATA sections are joined, and stored under `#text` unless
+///   they are only whitespace. Leading and trailing whitespace is removed.
+/// * An element with text but no attributes or child elements is just the
+///   text, and an element without any of these is null.
+///
+/// Comments, processing instructions and namespace declarations are
+/// dropped. All values are strings. See [`Value::coerce_scalars`] to turn
+/// numbers and booleans into numbers and booleans.
+pub fn parse_with<I>(mut input: I, namespaces: Namespaces) -> Result<Value>
+where
+    I: Read,
+{
+    let mut text = String::new();
+    input.read_to_string(&mut text)?;
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    let doc = roxmltree::Document::parse_with_options(&text, options)?;
+    let root = doc.root_element();
+    Ok(Value::Object(vec![(
+        key(root.tag_name(), root, namespaces),
+        element(root, namespaces),
+    )]))
+}
+
+/// The key for the element or attribute `name` of `node`.
+fn key(name: roxmltree::ExpandedName, node: roxmltree::Node, namespaces: Namespaces) -> String {
+    match (namespaces, name.namespace()) {
+        (Namespaces::Uri, Some(uri)) => format!("{{{uri}}}{}", name.name()),
+        (Namespaces::Prefix, Some(uri)) => match node.lookup_prefix(uri) {
+            Some(prefix) if !prefix.is_empty() => format!("{prefix}:{}", name.name()),
+            _ => name.name().into(),
+        },
+        _ => name.name().into(),
+    }
+}
+
+fn element(node: roxmltree::Node, namespaces: Namespaces) -> Value {
+    let mut entries: Vec<(String, Value)> = node
+        .attributes()
+        .map(|attr| {
+            let name = match attr.namespace() {
+                Some(uri) => roxmltree::ExpandedName::from((uri, attr.name())),
+                None => roxmltree::ExpandedName::from(attr.name()),
+            };
+            let key = format!("@{}", key(name, node, namespaces));
+            (key, Value::Str(attr.value().into()))
+        })
+        .collect();
+
+    let mut text = String::new();
+    for child in node.children() {
+        if child.is_text() {
+            text.push_str(child.text().unwrap_or_default());
+        } else if child.is_element() {
+            let key = key(child.tag_name(), child, namespaces);
+            let value = element(child, namespaces);
+            match entries.iter_mut().find(|(k, _)| *k == key) {
+                Some((_, Value::Array(values))) => values.push(value),
+                Some((_, existing)) => {
+                    let first = std::mem::replace(existing, Value::Null);
+                    *existing = Value::Array(vec![first, value]);
+                }
+                None => entries.push((key, value)),
+            }
+        }
+    }
+
+    let text = text.trim();
+    if entries.is_empty() {
+        return if text.is_empty() {
+            Value::Null
+        } else {
+            Value::Str(text.into())
+        };
+    }
+    if !text.is_empty() {
+        entries.push(("#text".into(), Value::Str(text.into())));
+    }
+    Value::Object(entries)
+}
+
+pub fn generate<I, O>(input: I, output: O) -> Result<()>
+where
+    I: Read,
+    O: Write,
+{
+    crate::generate(parse(input)?, output)?;
+    Ok(())
+}
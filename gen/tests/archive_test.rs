@@ -0,0 +1,18 @@
+#![cfg(feature = "yaml")]
+
+use const_config_gen::{Format, generate_archive};
+
+#[test]
+fn archive_test() {
+    // The archive read by the `archive_test` of `const-config`, which must
+    // be regenerated when the format changes.
+    let input = include_bytes!("../../tests/archive/config.yaml");
+    let expected = include_bytes!("../../tests/archive/config.bin");
+
+    let mut value = Format::Yaml.parse(&input[..]).unwrap();
+    value.detect_timestamps();
+    let mut output = Vec::new();
+    generate_archive(value, &mut output).unwrap();
+    assert_eq!(&output[..16], b"CCFGARCH\x01\0\0\0\0\0\0\0");
+    assert!(output == expected);
+}
@@ -0,0 +1,81 @@
+#![cfg(all(feature = "toml", feature = "json"))]
+
+use const_config_gen::{ArrayMerge, BuildError, Builder, Emit};
+use std::path::{Path, PathBuf};
+
+fn input(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/builder")
+        .join(name)
+}
+
+fn out_dir(test: &str) -> PathBuf {
+    let dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join(test);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn builder_test() {
+    let path = Builder::new()
+        .input(input("app.toml"))
+        .input(input("local.json"))
+        .arrays(ArrayMerge::Append)
+        .out_dir(out_dir("builder_test"))
+        .output("config.rs")
+        .emit(Emit::Module)
+        .cargo_directives(false)
+        .build()
+        .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(path).unwrap(),
+        r#"/// `name`
+pub const NAME: &str = "app";
+
+/// `networking`
+pub mod networking {
+    /// `networking.default-port`
+    pub const DEFAULT_PORT: i64 = 443;
+
+    /// `networking.hosts`
+    pub const HOSTS: ::const_config::Value<'static> = ::const_config::Value::<'static>::Array(const {
+        &[
+            ::const_config::Value::<'static>::Str("a.example.com"),
+            ::const_config::Value::<'static>::Str("b.example.com"),
+        ]
+    });
+}
+"#
+    );
+}
+
+#[test]
+fn const_item_test() {
+    let path = Builder::new()
+        .input(input("local.json"))
+        .out_dir(out_dir("const_item_test"))
+        .emit(Emit::Const)
+        .const_name("LOCAL")
+        .cargo_directives(false)
+        .build()
+        .unwrap();
+    assert!(path.ends_with("const_config.rs"));
+    assert!(
+        std::fs::read_to_string(path)
+            .unwrap()
+            .starts_with("pub const LOCAL: ::const_config::Value<'static> = ")
+    );
+}
+
+#[test]
+fn errors_test() {
+    let err = Builder::new().build().unwrap_err();
+    assert!(matches!(err, BuildError::NoInputs));
+
+    let err = Builder::new()
+        .input(input("app.conf"))
+        .cargo_directives(false)
+        .build()
+        .unwrap_err();
+    assert!(matches!(err, BuildError::Detect { .. }));
+}
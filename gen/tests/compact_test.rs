@@ -0,0 +1,31 @@
+#![cfg(feature = "json")]
+
+use const_config_gen::{Format, generate_compact};
+
+fn compact(json: &str) -> String {
+    let mut output = Vec::new();
+    generate_compact(Format::Json.parse(json.as_bytes()).unwrap(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn compact_test() {
+    let output = compact(r#"{"a": [1, -2, 0.5, "x"], "b": true, "c": "x"}"#);
+    let (prelude, code) = output.split_once(';').unwrap();
+    assert!(prelude.starts_with("const{use ::const_config::"));
+    assert_eq!(
+        code,
+        concat!(
+            r#"const V0:Value<'static>=Str("x");"#,
+            r#"Object(O::new(const{&[("a",Array(const{&[Number(UInt(1)),Number(Int(-2)),Number(Float(0.5)),V0]})),("b",Bool(true)),("c",V0)]},"#,
+            r#"const{&[1,2,0]},const{&[2,1,0]}))}"#,
+        )
+    );
+}
+
+#[test]
+fn empty() {
+    let output = compact("[]");
+    assert!(output.ends_with(";Array(const{&[]})}"));
+    assert!(!output.contains('\n'));
+}
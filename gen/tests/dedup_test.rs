@@ -0,0 +1,83 @@
+#![cfg(feature = "json")]
+
+use const_config_gen::{ArrayMerge, Format, Value, generate};
+
+fn expr(json: &str) -> String {
+    let mut output = Vec::new();
+    generate(Format::Json.parse(json.as_bytes()).unwrap(), &mut output).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn shared_items() {
+    let output = expr(
+        r#"{
+            "a": {"x": "hello", "y": [1, 2]},
+            "b": {"x": "hello", "y": [1, 2]},
+            "c": "hello",
+            "d": [1, 2],
+            "e": "once"
+        }"#,
+    );
+    assert_eq!(output.matches("const V").count(), 3);
+    assert_eq!(output.matches("Str(\"hello\")").count(), 1);
+    assert_eq!(output.matches("Str(\"once\")").count(), 1);
+    assert_eq!(output.matches("UInt(1)").count(), 1);
+    assert!(output.contains("(\"a\", V2)"));
+    assert!(output.contains("(\"b\", V2)"));
+    assert!(output.contains("(\"c\", V0)"));
+    assert!(output.contains("(\"d\", V1)"));
+}
+
+#[test]
+fn children_of_shared_items() {
+    // The strings only occur inside the repeated array, so only the array
+    // is shared.
+    let output = expr(r#"[["a", "b"], ["a", "b"]]"#);
+    assert_eq!(output.matches("const V").count(), 1);
+    assert!(output.contains("const V0: ::const_config::Value<'static> = "));
+    assert_eq!(output.matches("Str(\"a\")").count(), 1);
+}
+
+#[test]
+fn nothing_shared() {
+    let output = expr(r#"{"a": "x", "b": 1, "c": 1, "d": null, "e": null}"#);
+    assert!(!output.contains("const V"));
+}
+
+fn large_object(value: i128) -> Value {
+    Value::Object(
+        (0..100_000)
+            .map(|idx| (format!("k{idx}"), Value::Int(value)))
+            .collect(),
+    )
+}
+
+#[test]
+fn large_objects() {
+    // Keys are looked up by their hash, so neither of these is quadratic in
+    // the number of keys.
+    let mut merged = large_object(1);
+    merged.merge(large_object(2), ArrayMerge::Replace);
+    let Value::Object(entries) = &merged else {
+        panic!("{merged:?}");
+    };
+    assert_eq!(entries.len(), 100_000);
+    assert!(
+        entries
+            .iter()
+            .all(|(_, value)| matches!(value, Value::Int(2)))
+    );
+
+    let Value::Object(mut entries) = large_object(1) else {
+        unreachable!();
+    };
+    entries.push(("k0".into(), Value::Int(-1)));
+    let mut output = Vec::new();
+    generate(Value::Object(entries), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.matches(r#"("k0", "#).count(), 1);
+    assert!(output.contains(
+        r#"("k0", ::const_config::Value::<'static>::Number(::const_config::Number::Int(-1)))"#
+    ));
+}
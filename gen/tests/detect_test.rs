@@ -0,0 +1,56 @@
+#![cfg(feature = "all-formats")]
+
+use const_config_gen::{DetectError, Format};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+fn config() -> BTreeMap<String, u32> {
+    BTreeMap::from([("port".into(), 443), ("workers".into(), 4)])
+}
+
+fn sniff(data: &[u8]) -> Format {
+    let format = Format::detect(None, data).unwrap();
+    format.parse(data).unwrap();
+    format
+}
+
+#[test]
+fn by_extension() {
+    let detect = |path: &str| Format::detect(Some(Path::new(path)), b"");
+    assert_eq!(detect("app.toml").unwrap(), Format::Toml);
+    assert_eq!(detect("config/app.YML").unwrap(), Format::Yaml);
+    assert_eq!(detect("app.pkl").unwrap(), Format::Pickle);
+    assert_eq!(detect("app.flexbuf").unwrap(), Format::Flexbuffers);
+    assert_eq!(detect("app.properties").unwrap(), Format::Properties);
+    assert_eq!(detect(".env").unwrap(), Format::Dotenv);
+    assert_eq!(detect("deploy/.env.production").unwrap(), Format::Dotenv);
+    assert_eq!(detect(".env.local.toml").unwrap(), Format::Toml);
+    assert_eq!(detect(".env.JSON").unwrap(), Format::Json);
+    assert!(matches!(
+        detect("app.conf"),
+        Err(DetectError::UnknownExtension(ext)) if ext == "conf",
+    ));
+    assert!(matches!(
+        Format::detect(None, b"port = 443"),
+        Err(DetectError::Unknown),
+    ));
+}
+
+#[test]
+fn by_contents() {
+    let mut cbor = Vec::new();
+    ciborium::into_writer(&config(), &mut cbor).unwrap();
+    assert_eq!(sniff(&cbor), Format::Cbor);
+
+    let msgpack = rmp_serde::to_vec(&config()).unwrap();
+    assert_eq!(sniff(&msgpack), Format::Msgpack);
+
+    let pickle = serde_pickle::to_vec(&config(), Default::default()).unwrap();
+    assert_eq!(sniff(&pickle), Format::Pickle);
+
+    let bson = bson::to_vec(&config()).unwrap();
+    assert_eq!(sniff(&bson), Format::Bson);
+
+    let flexbuffers = flexbuffers::to_vec(config()).unwrap();
+    assert_eq!(sniff(&flexbuffers), Format::Flexbuffers);
+}
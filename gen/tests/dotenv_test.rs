@@ -0,0 +1,62 @@
+#![cfg(feature = "dotenv")]
+
+use const_config_gen::{Format, Location, Value};
+
+fn location(input: &str) -> Option<Location> {
+    Format::Dotenv
+        .parse(input.as_bytes())
+        .unwrap_err()
+        .location()
+}
+
+fn coerce(s: &str) -> String {
+    let mut value = Value::Str(s.into());
+    value.coerce_scalars();
+    format!("{value:?}")
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        location("A=1\nB=$C\n"),
+        Some(Location { line: 2, column: 3 }),
+    );
+    assert_eq!(
+        location("A=1\nB ~ 2\n"),
+        Some(Location { line: 2, column: 4 }),
+    );
+    assert_eq!(
+        location("A=\"open\nB=2\n"),
+        Some(Location { line: 1, column: 3 }),
+    );
+    assert_eq!(location("A='x' y\n"), Some(Location { line: 1, column: 7 }),);
+}
+
+#[test]
+fn references() {
+    let value = Format::Dotenv
+        .parse(&b"A=1\nA=${A}2\nB=\"\\$A $\"\nC=a#b"[..])
+        .unwrap();
+    assert_eq!(
+        format!("{value:?}"),
+        r#"Object([("A", Str("12")), ("B", Str("$A $")), ("C", Str("a#b"))])"#,
+    );
+}
+
+#[test]
+fn coerce_scalars() {
+    assert_eq!(coerce("true"), "Bool(true)");
+    assert_eq!(coerce("42"), "UInt(42)");
+    assert_eq!(coerce("+42"), "UInt(42)");
+    assert_eq!(coerce("-42"), "Int(-42)");
+    assert_eq!(coerce("1.5e3"), "Float(1500.0)");
+    assert_eq!(coerce("-0.25"), "Float(-0.25)");
+    assert_eq!(coerce("0"), "UInt(0)");
+    assert_eq!(coerce("0.5"), "Float(0.5)");
+    for s in [
+        "True", " 1", "1.", ".5", "1e", "inf", "NaN", "0x10", "1_000", "", "0012", "-007", "00.5",
+        "1e400", "-1e400",
+    ] {
+        assert_eq!(coerce(s), format!("Str({s:?})"));
+    }
+}
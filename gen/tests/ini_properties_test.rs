@@ -0,0 +1,74 @@
+#![cfg(all(feature = "ini", feature = "properties", feature = "json"))]
+
+use const_config_gen::{ExpandKeysError, Format, Location, Value};
+
+fn location(format: Format, input: &str) -> Option<Location> {
+    format.parse(input.as_bytes()).unwrap_err().location()
+}
+
+#[test]
+fn ini_errors() {
+    assert_eq!(
+        location(Format::Ini, "a = 1\n  [server\n"),
+        Some(Location {
+            line: 2,
+            column: 10
+        }),
+    );
+    assert_eq!(
+        location(Format::Ini, "[server]\nport\n"),
+        Some(Location { line: 2, column: 5 }),
+    );
+    assert_eq!(
+        location(Format::Ini, "server = 1\n[server]\n"),
+        Some(Location { line: 2, column: 2 }),
+    );
+}
+
+#[test]
+fn properties_errors() {
+    assert_eq!(
+        location(Format::Properties, "a = 1\nb = x\\u12g4\n"),
+        Some(Location { line: 2, column: 6 }),
+    );
+    assert_eq!(
+        location(Format::Properties, "a = \\\n    x\\ud800\n"),
+        Some(Location { line: 2, column: 6 }),
+    );
+    assert_eq!(
+        location(Format::Properties, "a = 1\n  b = x\\u12g4\n"),
+        Some(Location { line: 2, column: 8 }),
+    );
+}
+
+#[test]
+fn surrogate_pairs() {
+    let value = Format::Properties
+        .parse(&b"emoji = \\ud83d\\ude00"[..])
+        .unwrap();
+    let Value::Object(entries) = value else {
+        panic!("{value:?}");
+    };
+    assert!(matches!(&entries[0].1, Value::Str(s) if s == "\u{1f600}"));
+}
+
+#[test]
+fn expand_dotted_keys() {
+    let mut value = Format::Json
+        .parse(&br#"{"a.b": 1, "a": {"c": 2}, "a.d.e": 3, "f": [{"g.h": 4}]}"#[..])
+        .unwrap();
+    value.expand_dotted_keys().unwrap();
+    let expected = Format::Json
+        .parse(&br#"{"a": {"b": 1, "c": 2, "d": {"e": 3}}, "f": [{"g": {"h": 4}}]}"#[..])
+        .unwrap();
+    assert_eq!(format!("{value:?}"), format!("{expected:?}"));
+}
+
+#[test]
+fn expand_dotted_keys_conflict() {
+    let mut value = Format::Json
+        .parse(&br#"{"a.b.c": 1, "a.b": 2}"#[..])
+        .unwrap();
+    let err = value.expand_dotted_keys().unwrap_err();
+    assert!(matches!(&err, ExpandKeysError::Conflict(key) if key == "a.b"));
+}
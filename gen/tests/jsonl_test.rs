@@ -0,0 +1,18 @@
+#![cfg(feature = "jsonl")]
+
+use const_config_gen::{Format, Location};
+
+#[test]
+fn error_names_line() {
+    let err = Format::Jsonl
+        .parse(&b"{\"a\": 1}\n\n{\"a\": }\n"[..])
+        .unwrap_err();
+    assert_eq!(err.location(), Some(Location { line: 3, column: 7 }));
+    assert_eq!(err.to_string(), "expected value at line 3 column 7");
+}
+
+#[test]
+fn detect() {
+    let path = std::path::Path::new("events.ndjson");
+    assert_eq!(Format::detect(Some(path), b"").unwrap(), Format::Jsonl);
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "kdl")]
+
+use const_config_gen::{Format, Location};
+
+fn parse(input: &str) -> String {
+    format!("{:?}", Format::Kdl.parse(input.as_bytes()).unwrap())
+}
+
+fn location(input: &str) -> Option<Location> {
+    Format::Kdl.parse(input.as_bytes()).unwrap_err().location()
+}
+
+#[test]
+fn values() {
+    assert_eq!(
+        parse("n 0x1f -0b101 1_000 1.5e2 #-inf true r#\"a\"b\"# ##\"c\"## \"\\u{e9}\\t\""),
+        r#"Array([Object([("name", Str("n")), ("args", Array([UInt(31), Int(-5), UInt(1000), Float(150.0), Float(-inf), Bool(true), Str("a\"b"), Str("c"), Str("é\t")]))])])"#,
+    );
+}
+
+#[test]
+fn structure() {
+    assert_eq!(
+        parse("a; b x=1 x=2 \\\n  y=3 /* inline */ { c }\n/* block /* nested */ */ d"),
+        concat!(
+            r#"Array([Object([("name", Str("a")), ("args", Array([]))]), "#,
+            r#"Object([("name", Str("b")), ("args", Array([])), ("x", UInt(2)), ("y", UInt(3)), "#,
+            r#"("children", Array([Object([("name", Str("c")), ("args", Array([]))])]))]), "#,
+            r#"Object([("name", Str("d")), ("args", Array([]))])])"#,
+        ),
+    );
+}
+
+#[test]
+fn multi_line_strings() {
+    assert_eq!(
+        parse("n \"\"\"\n    hello\n      world\n    \"\"\""),
+        r#"Array([Object([("name", Str("n")), ("args", Array([Str("hello\n  world")]))])])"#,
+    );
+}
+
+#[test]
+fn errors() {
+    assert_eq!(
+        location("a\nb (date)\"2024-13-01\""),
+        Some(Location { line: 2, column: 9 }),
+    );
+    assert_eq!(location("a name=x"), Some(Location { line: 1, column: 3 }),);
+    assert_eq!(
+        location("a {\n  b\n"),
+        Some(Location { line: 3, column: 1 }),
+    );
+    assert_eq!(location("a \"open"), Some(Location { line: 1, column: 3 }),);
+    assert_eq!(location("a 1x"), Some(Location { line: 1, column: 3 }),);
+}
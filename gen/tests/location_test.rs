@@ -0,0 +1,50 @@
+#![cfg(all(feature = "toml", feature = "json", feature = "yaml", feature = "cbor"))]
+
+use const_config_gen::{Format, Location};
+
+fn location(format: Format, input: &[u8]) -> Option<Location> {
+    format.parse(input).unwrap_err().location()
+}
+
+#[test]
+fn toml_location() {
+    assert_eq!(
+        location(Format::Toml, b"[server]\nport = 80 80\n"),
+        Some(Location {
+            line: 2,
+            column: 11
+        }),
+    );
+}
+
+#[test]
+fn json_location() {
+    assert_eq!(
+        location(Format::Json, b"{\"a\": 1,\n  \"b\": x}"),
+        Some(Location { line: 2, column: 8 }),
+    );
+}
+
+#[test]
+fn yaml_location() {
+    let location = location(Format::Yaml, b"a: 1\nb: [1, 2\n").unwrap();
+    assert!(location.line >= 2);
+}
+
+#[test]
+fn cbor_location() {
+    // A map header followed by a reserved additional info value.
+    assert_eq!(
+        location(Format::Cbor, &[0xa1, 0x1c]),
+        Some(Location { line: 1, column: 2 }),
+    );
+}
+
+#[test]
+fn from_offset() {
+    let text = "a = 1\nb = \"é\" x\n";
+    let offset = text.find('x').unwrap();
+    let location = Location::from_offset(text, offset);
+    assert_eq!(location, Location { line: 2, column: 9 });
+    assert_eq!(location.offset_in(text), Some(offset));
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "toml")]
+
+use const_config_gen::{EmitError, Format, generate_module};
+
+fn module(toml: &str) -> Result<String, EmitError> {
+    let mut output = Vec::new();
+    generate_module(Format::Toml.parse(toml.as_bytes()).unwrap(), &mut output)?;
+    Ok(String::from_utf8(output).unwrap())
+}
+
+#[test]
+fn module_test() {
+    let output = module(
+        r#"
+        name = "app"
+
+        [networking]
+        allow-insecure = false
+        defaultPort = 443
+
+        [networking.type]
+        ratio = 0.5
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        output,
+        r#"/// `name`
+pub const NAME: &str = "app";
+
+/// `networking`
+pub mod networking {
+    /// `networking.allow-insecure`
+    pub const ALLOW_INSECURE: bool = false;
+
+    /// `networking.defaultPort`
+    pub const DEFAULT_PORT: i64 = 443;
+
+    /// `networking.type`
+    pub mod r#type {
+        /// `networking.type.ratio`
+        pub const RATIO: f64 = 0.5;
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn collision_test() {
+    let err = module("default-port = 1\ndefault_port = 2").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`default-port` and `default_port` would both be named `DEFAULT_PORT`"
+    );
+}
+
+#[test]
+fn non_finite_test() {
+    let output = module("a = nan\nb = -inf").unwrap();
+    assert_eq!(
+        output,
+        "/// `a`\npub const A: f64 = f64::NAN;\n\n/// `b`\npub const B: f64 = f64::NEG_INFINITY;\n"
+    );
+}
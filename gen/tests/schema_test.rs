@@ -0,0 +1,70 @@
+#![cfg(feature = "json")]
+
+use const_config_gen::{Format, SchemaError, Value};
+
+fn json(text: &str) -> Value {
+    Format::Json.parse(text.as_bytes()).unwrap()
+}
+
+fn violations(config: &str, schema: &str) -> Vec<String> {
+    match json(config).validate(&json(schema)) {
+        Ok(()) => Vec::new(),
+        Err(SchemaError::Violations(violations)) => {
+            violations.iter().map(ToString::to_string).collect()
+        }
+        Err(err) => panic!("{err}"),
+    }
+}
+
+#[test]
+fn lists_every_violation() {
+    let schema = r##"{
+        "type": "object",
+        "properties": {
+            "port": { "type": "integer", "maximum": 65535 },
+            "hosts": { "type": "array", "items": { "type": "string" }, "uniqueItems": true },
+            "mode": { "$ref": "#/$defs/mode" }
+        },
+        "required": ["port", "mode"],
+        "$defs": { "mode": { "enum": ["dev", "prod"] } }
+    }"##;
+    assert_eq!(
+        violations(r#"{"port": 70000, "hosts": ["a", 1, "a"]}"#, schema),
+        [
+            "`port`: must be at most 65535, found 70000",
+            "`hosts[1]`: expected string, found integer `1`",
+            "`hosts`: items must be unique, but [2] repeats [0]",
+            "<root>: missing required key `mode`",
+        ],
+    );
+    assert_eq!(
+        violations(r#"{"port": 80.0, "mode": "prod"}"#, schema),
+        Vec::<String>::new(),
+    );
+}
+
+#[test]
+fn applicators() {
+    let schema = r#"{
+        "oneOf": [{ "type": "integer" }, { "multipleOf": 0.5 }],
+        "not": { "const": 3 }
+    }"#;
+    assert_eq!(violations("1.5", schema), Vec::<String>::new());
+    assert_eq!(
+        violations("2", schema),
+        ["<root>: matches 2 of the schemas in `oneOf`, expected exactly one"],
+    );
+    assert_eq!(
+        violations("1.25", schema),
+        ["<root>: does not match any of the schemas in `oneOf`"],
+    );
+}
+
+#[test]
+fn invalid_schema() {
+    let err = json("1").validate(&json(r#"{"type": "int"}"#)).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid schema at `#/type`: unknown type `int`"
+    );
+}
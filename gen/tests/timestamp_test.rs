@@ -0,0 +1,78 @@
+#![cfg(feature = "json")]
+
+use const_config_gen::{Format, Value};
+
+fn detect(s: &str) -> String {
+    let mut value = Value::Str(s.into());
+    value.detect_timestamps();
+    match value {
+        Value::Date(date) => format!("date {date}"),
+        Value::Time(time) => format!("time {time}"),
+        Value::DateTime(dt) => format!("datetime {dt}"),
+        Value::Str(s) => format!("str {s}"),
+        value => panic!("{value:?}"),
+    }
+}
+
+#[test]
+fn rfc3339() {
+    assert_eq!(detect("2024-01-02"), "date 2024-01-02");
+    assert_eq!(detect("2024-02-29"), "date 2024-02-29");
+    assert_eq!(detect("10:30:05"), "time 10:30:05");
+    assert_eq!(detect("10:30:05.250"), "time 10:30:05.25");
+    assert_eq!(
+        detect("2024-01-02T10:30:05"),
+        "datetime 2024-01-02T10:30:05"
+    );
+    assert_eq!(
+        detect("2024-01-02t10:30:05z"),
+        "datetime 2024-01-02T10:30:05Z"
+    );
+    assert_eq!(
+        detect("2024-01-02 10:30:05.123456789123-01:30"),
+        "datetime 2024-01-02T10:30:05.123456789-01:30"
+    );
+}
+
+#[test]
+fn yaml() {
+    assert_eq!(
+        detect("2001-12-14 21:59:43.10 -5"),
+        "datetime 2001-12-14T21:59:43.1-05:00"
+    );
+    assert_eq!(
+        detect("2002-1-2T3:04:05 Z"),
+        "datetime 2002-01-02T03:04:05Z"
+    );
+}
+
+#[test]
+fn not_timestamps() {
+    for s in [
+        "2023-02-29",
+        "2024-13-01",
+        "2024-1-2",
+        "24:00:00",
+        "10:30",
+        "10:30:05.",
+        "2024-01-02T10:30:05+24:00",
+        "2024-01-02T10:30:05 UTC",
+        "2024",
+        "v2024-01-02",
+    ] {
+        assert_eq!(detect(s), format!("str {s}"));
+    }
+}
+
+#[test]
+fn nested() {
+    let mut value = Format::Json
+        .parse(&br#"{"a": ["2024-01-02"], "b": {"2024-01-02": "x"}}"#[..])
+        .unwrap();
+    value.detect_timestamps();
+    let Value::Object(entries) = value else {
+        panic!()
+    };
+    assert!(matches!(&entries[0].1, Value::Array(a) if matches!(a[0], Value::Date(_))));
+    assert!(matches!(&entries[1].1, Value::Object(o) if o[0].0 == "2024-01-02"));
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "xml")]
+
+use const_config_gen::Format;
+use const_config_gen::xml::{self, Namespaces};
+
+const DOC: &str =
+    r#"<d:device xmlns:d="urn:dev" xmlns="urn:default" d:id="1"><port>80</port></d:device>"#;
+
+fn keys(namespaces: Namespaces) -> String {
+    format!("{:?}", xml::parse_with(DOC.as_bytes(), namespaces).unwrap())
+}
+
+#[test]
+fn namespaces() {
+    assert_eq!(
+        keys(Namespaces::Prefix),
+        r#"Object([("d:device", Object([("@d:id", Str("1")), ("port", Str("80"))]))])"#,
+    );
+    assert_eq!(
+        keys(Namespaces::Strip),
+        r#"Object([("device", Object([("@id", Str("1")), ("port", Str("80"))]))])"#,
+    );
+    assert_eq!(
+        keys(Namespaces::Uri),
+        r#"Object([("{urn:dev}device", Object([("@{urn:dev}id", Str("1")), ("{urn:default}port", Str("80"))]))])"#,
+    );
+}
+
+#[test]
+fn mixed_content() {
+    let value = Format::Xml
+        .parse(&b"<p>Hello <b>big</b> world</p>"[..])
+        .unwrap();
+    assert_eq!(
+        format!("{value:?}"),
+        r##"Object([("p", Object([("b", Str("big")), ("#text", Str("Hello  world"))]))])"##,
+    );
+}
+
+#[test]
+fn location() {
+    let err = Format::Xml.parse(&b"<a>\n  <b></c>\n</a>"[..]).unwrap_err();
+    assert_eq!(err.location().map(|location| location.line), Some(2));
+}
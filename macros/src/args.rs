@@ -0,0 +1,176 @@
+use crate::expand::Deps;
+use proc_macro2::Span;
+use syn::{
+    Expr, Ident, Lit, LitStr, Token,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+};
+
+/// A string option given as a literal, or read from the environment with
+/// `env!` or `option_env!`. `value` is `None` for an unset `option_env!`,
+/// or when the option is given as `None`.
+pub(crate) struct StrOpt {
+    pub value: Option<String>,
+    pub span: Span,
+}
+
+/// Arguments of a function-like macro: positional literals, followed by
+/// `name = value` options, all separated by commas.
+pub(crate) struct Args {
+    pub inputs: Vec<Lit>,
+    options: Vec<(Ident, Expr)>,
+}
+
+impl Parse for Args {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut inputs = Vec::new();
+        let mut options: Vec<(Ident, Expr)> = Vec::new();
+        while !input.is_empty() {
+            if input.peek(Ident) && input.peek2(Token![=]) {
+                let name: Ident = input.parse()?;
+                input.parse::<Token![=]>()?;
+                let value: Expr = input.parse()?;
+                if options.iter().any(|(n, _)| *n == name) {
+                    return Err(syn::Error::new(
+                        name.span(),
+                        format!("duplicate option `{name}`"),
+                    ));
+                }
+                options.push((name, value));
+            } else if options.is_empty() {
+                inputs.push(input.parse()?);
+            } else {
+                return Err(input.error("expected an option, i.e. `name = value`"));
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        Ok(Self { inputs, options })
+    }
+}
+
+impl Args {
+    /// Removes the option `name` and returns its value.
+    pub fn take(&mut self, name: &str) -> Option<(Ident, Expr)> {
+        let idx = self.options.iter().position(|(n, _)| n == name)?;
+        Some(self.options.remove(idx))
+    }
+
+    /// Removes the option `name`, whose value must be a string literal.
+    pub fn take_str(&mut self, name: &str) -> syn::Result<Option<LitStr>> {
+        match self.take(name) {
+            None => Ok(None),
+            Some((
+                _,
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Str(lit), ..
+                }),
+            )) => Ok(Some(lit)),
+            Some((name, value)) => Err(syn::Error::new(
+                value.span(),
+                format!("expected a string literal for `{name}`"),
+            )),
+        }
+    }
+
+    /// Removes the option `name`, whose value must be a bool literal, and
+    /// returns its value, or `false` if it is not given.
+    pub fn take_bool(&mut self, name: &str) -> syn::Result<bool> {
+        match self.take(name) {
+            None => Ok(false),
+            Some((
+                _,
+                Expr::Lit(syn::ExprLit {
+                    lit: Lit::Bool(lit),
+                    ..
+                }),
+            )) => Ok(lit.value),
+            Some((name, value)) => Err(syn::Error::new(
+                value.span(),
+                format!("expected `true` or `false` for `{name}`"),
+            )),
+        }
+    }
+
+    /// Removes the option `name`, whose value must be a string literal,
+    /// `None`, or an `env!` or `option_env!` invocation that is evaluated
+    /// right away.
+    pub fn take_env_str(&mut self, name: &str, deps: &mut Deps) -> syn::Result<Option<StrOpt>> {
+        let Some((name, value)) = self.take(name) else {
+            return Ok(None);
+        };
+        let span = value.span();
+        match value {
+            Expr::Lit(syn::ExprLit {
+                lit: Lit::Str(lit), ..
+            }) => Ok(Some(StrOpt {
+                value: Some(lit.value()),
+                span,
+            })),
+            Expr::Path(path) if path.path.is_ident("None") => {
+                Ok(Some(StrOpt { value: None, span }))
+            }
+            Expr::Macro(syn::ExprMacro { mac, .. })
+                if mac.path.is_ident("env") || mac.path.is_ident("option_env") =>
+            {
+                let optional = mac.path.is_ident("option_env");
+                let args =
+                    mac.parse_body_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?;
+                let Some(var) = args.first() else {
+                    return Err(syn::Error::new(
+                        span,
+                        "expected an environment variable name",
+                    ));
+                };
+                let value = deps.env(&var.value());
+                if value.is_none() && !optional {
+                    return Err(syn::Error::new(
+                        span,
+                        format!(
+                            "environment variable `{}` not defined at compile time",
+                            var.value()
+                        ),
+                    ));
+                }
+                Ok(Some(StrOpt { value, span }))
+            }
+            Expr::Macro(syn::ExprMacro { mac, .. }) if mac.path.is_ident("cfg") => {
+                Err(syn::Error::new(
+                    span,
+                    format!(
+                        "`cfg!` is not visible to macros; put one constant per `{name}` behind `#[cfg]` instead"
+                    ),
+                ))
+            }
+            _ => Err(syn::Error::new(
+                span,
+                format!("expected a string literal, `None`, `env!` or `option_env!` for `{name}`"),
+            )),
+        }
+    }
+
+    /// Returns an error for the first option that has not been taken.
+    pub fn finish(self) -> syn::Result<()> {
+        match self.options.into_iter().next() {
+            None => Ok(()),
+            Some((name, _)) => Err(syn::Error::new(
+                name.span(),
+                format!("unrecognized option `{name}`"),
+            )),
+        }
+    }
+
+    /// Returns the positional inputs, which must all be string literals.
+    pub fn input_strs(&self) -> syn::Result<Vec<LitStr>> {
+        self.inputs
+            .iter()
+            .map(|lit| match lit {
+                Lit::Str(lit) => Ok(lit.clone()),
+                lit => Err(syn::Error::new(lit.span(), "expected a string literal")),
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,860 @@
+use crate::expand::Deps;
+use const_config_gen::{Format, Value};
+use proc_macro2::{Span, TokenStream};
+use quote::{ToTokens, format_ident, quote, quote_spanned};
+use syn::{
+    Data, DeriveInput, Fields, GenericArgument, Ident, LitByteStr, LitStr, PathArguments, Type,
+    parse::{Parse, ParseStream},
+    spanned::Spanned,
+};
+
+#[derive(Clone, Copy)]
+enum RenameRule {
+    None,
+    Lower,
+    Upper,
+    Camel,
+    Pascal,
+    ScreamingSnake,
+    Kebab,
+    ScreamingKebab,
+}
+
+enum Kind<'a> {
+    Bool,
+    Int(&'static str),
+    Float(&'static str),
+    Char,
+    Str,
+    Value,
+    Date,
+    Time,
+    DateTime,
+    Option(&'a Type),
+    Slice(&'a Type),
+    Array(&'a Type, usize),
+    Nested(&'a Type),
+}
+
+pub(crate) fn expand(input: DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let mut deps = Deps::default();
+    let def = Definition::parse(&input, Some(&mut deps))?;
+
+    let config = match &def.source {
+        Some(source) => {
+            let value = source
+                .format
+                .parse(&source.data[..])
+                .map_err(|err| syn::Error::new(source.span, err.to_string()))?;
+            let Value::Object(entries) = &value else {
+                return Err(syn::Error::new(
+                    source.span,
+                    format!(
+                        "expected the config to be an object, found {}",
+                        describe(&value)
+                    ),
+                ));
+            };
+            let ctx = Context {
+                source: &source.forward,
+                module: None,
+            };
+            let init = def.literal(&quote!(Self), entries, &[], &ctx)?;
+            quote! {
+                impl #name {
+                    pub const CONFIG: Self = #init;
+                }
+            }
+        }
+        None => TokenStream::new(),
+    };
+
+    // Nested config types are built by the type naming the source, which
+    // needs to know their fields. This macro hands them over, together with
+    // the source and where in it the nested value is.
+    let nested = nested_macro_name(name);
+    let deps = deps.items();
+    Ok(quote! {
+        #deps
+
+        #config
+
+        #[doc(hidden)]
+        #[allow(unused_macros)]
+        macro_rules! #nested {
+            ($($args:tt)*) => {
+                ::const_config::__const_config_nested! { $($args)* #input }
+            };
+        }
+
+        #[doc(hidden)]
+        #[allow(unused_imports)]
+        pub(crate) use #nested;
+    })
+}
+
+/// Expands `__const_config_nested!`, which builds the literal for a nested
+/// config type from the value at `key` in `source`.
+pub(crate) fn expand_nested(input: Nested) -> syn::Result<TokenStream> {
+    let def = Definition::parse(&input.def, None)?;
+    let source = input.source;
+    let value = source
+        .format
+        .parse(&source.data[..])
+        .map_err(|err| syn::Error::new(source.span, err.to_string()))?;
+    let mut value = &value;
+    for segment in &input.key {
+        let next = match (segment, value) {
+            (Segment::Key(key), Value::Object(entries)) => {
+                entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+            }
+            (Segment::Index(idx), Value::Array(elems)) => elems.get(*idx),
+            _ => None,
+        };
+        value = next.ok_or_else(|| {
+            syn::Error::new(
+                source.span,
+                format!("`{}` is not in the config", display_path(&input.key)),
+            )
+        })?;
+    }
+    let Value::Object(entries) = value else {
+        return Err(mismatch(
+            source.span,
+            &display_path(&input.key),
+            "an object",
+            value,
+        ));
+    };
+
+    let ty = &input.ty;
+    let module = Some(module_of(ty));
+    let ctx = Context {
+        source: &source.forward,
+        module: module.as_ref(),
+    };
+    def.literal(&quote!(#ty), entries, &input.key, &ctx)
+}
+
+/// A config source given as `include_<format> = "path"` or
+/// `from_<format> = "text"`.
+struct Source {
+    format: Format,
+    data: Vec<u8>,
+    span: Span,
+    /// The source as passed on to nested config types, with an absolute
+    /// path for included files.
+    forward: TokenStream,
+}
+
+impl Source {
+    /// Parses the value of the source attribute `name`. Returns `None` if
+    /// `name` does not name a source.
+    fn parse(
+        name: &Ident,
+        value: ParseStream,
+        deps: Option<&mut Deps>,
+    ) -> syn::Result<Option<Self>> {
+        let attr = name.to_string();
+        let (include, format_name) = if let Some(format_name) = attr.strip_prefix("include_") {
+            (true, format_name)
+        } else if let Some(format_name) = attr.strip_prefix("from_") {
+            (false, format_name)
+        } else {
+            return Ok(None);
+        };
+        let Some(format) = Format::from_name(format_name) else {
+            return Err(syn::Error::new(
+                name.span(),
+                format!(
+                    "unsupported format `{format_name}`, is the `{}` feature of `const-config` enabled?",
+                    format_name.replace('_', "-"),
+                ),
+            ));
+        };
+        Ok(Some(if include {
+            let path: LitStr = value.parse()?;
+            let resolved = crate::resolve_path(path.span(), &path.value());
+            if let Some(deps) = deps {
+                deps.file(&resolved);
+            }
+            let data = std::fs::read(&resolved).map_err(|err| {
+                syn::Error::new(path.span(), format!("{}: {err}", resolved.display()))
+            })?;
+            let resolved = LitStr::new(&resolved.to_string_lossy(), path.span());
+            Source {
+                format,
+                data,
+                span: path.span(),
+                forward: quote!(#name = #resolved),
+            }
+        } else if format.is_binary() {
+            let data: LitByteStr = value.parse()?;
+            Source {
+                format,
+                data: data.value(),
+                span: data.span(),
+                forward: quote!(#name = #data),
+            }
+        } else {
+            let data: LitStr = value.parse()?;
+            Source {
+                format,
+                data: data.value().into_bytes(),
+                span: data.span(),
+                forward: quote!(#name = #data),
+            }
+        }))
+    }
+}
+
+/// A step from a value into one of its elements.
+#[derive(Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+impl ToTokens for Segment {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        match self {
+            Segment::Key(key) => key.to_tokens(tokens),
+            Segment::Index(idx) => idx.to_tokens(tokens),
+        }
+    }
+}
+
+/// The path of a value in the config for messages, e.g. `endpoints[1].port`.
+fn display_path(path: &[Segment]) -> String {
+    let mut out = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(key) if out.is_empty() => out.push_str(key),
+            Segment::Key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            Segment::Index(idx) => out.push_str(&format!("[{idx}]")),
+        }
+    }
+    out
+}
+
+fn with_segment(path: &[Segment], segment: Segment) -> Vec<Segment> {
+    let mut path = path.to_vec();
+    path.push(segment);
+    path
+}
+
+/// The input of `__const_config_nested!`:
+/// `path::Type, ["key", 0], include_<format> = "path", <struct>`.
+pub(crate) struct Nested {
+    ty: syn::Path,
+    key: Vec<Segment>,
+    source: Source,
+    def: DeriveInput,
+}
+
+impl Parse for Nested {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ty = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let content;
+        syn::bracketed!(content in input);
+        let mut key = Vec::new();
+        while !content.is_empty() {
+            let lit: syn::Lit = content.parse()?;
+            key.push(match lit {
+                syn::Lit::Str(key) => Segment::Key(key.value()),
+                syn::Lit::Int(idx) => Segment::Index(idx.base10_parse()?),
+                lit => return Err(syn::Error::new(lit.span(), "expected a key or an index")),
+            });
+            if !content.is_empty() {
+                content.parse::<syn::Token![,]>()?;
+            }
+        }
+        input.parse::<syn::Token![,]>()?;
+        let name: Ident = input.parse()?;
+        input.parse::<syn::Token![=]>()?;
+        let Some(source) = Source::parse(&name, input, None)? else {
+            return Err(syn::Error::new(name.span(), "expected a config source"));
+        };
+        input.parse::<syn::Token![,]>()?;
+        let def = input.parse()?;
+        Ok(Nested {
+            ty,
+            key,
+            source,
+            def,
+        })
+    }
+}
+
+/// Where the fields of a config type are taken from.
+struct Context<'a> {
+    /// The source, passed on to nested config types.
+    source: &'a TokenStream,
+    /// The module of the type being built, relative to where the literal is
+    /// used. `None` for the type naming the source.
+    module: Option<&'a syn::Path>,
+}
+
+/// A struct deriving `ConstConfig`.
+struct Definition<'a> {
+    fields: Vec<(&'a syn::Field, String)>,
+    source: Option<Source>,
+}
+
+impl<'a> Definition<'a> {
+    /// Parses the struct and its attributes. Sources are only read if
+    /// `deps` is given, and otherwise ignored.
+    fn parse(input: &'a DeriveInput, mut deps: Option<&mut Deps>) -> syn::Result<Self> {
+        if !input.generics.params.is_empty() {
+            return Err(syn::Error::new(
+                input.generics.span(),
+                "`ConstConfig` cannot be derived for generic types, use `'static` references instead",
+            ));
+        }
+
+        let Data::Struct(data) = &input.data else {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`ConstConfig` can only be derived for structs",
+            ));
+        };
+        let Fields::Named(fields) = &data.fields else {
+            return Err(syn::Error::new(
+                input.ident.span(),
+                "`ConstConfig` can only be derived for structs with named fields",
+            ));
+        };
+
+        let mut source = None;
+        let mut rename_all = RenameRule::None;
+        for attr in &input.attrs {
+            if !attr.path().is_ident("const_config") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                let Some(name) = meta.path.get_ident() else {
+                    return Err(meta.error("unrecognized `const_config` attribute"));
+                };
+                if name == "rename_all" {
+                    let rule: LitStr = meta.value()?.parse()?;
+                    rename_all = parse_rename_rule(&rule)?;
+                    return Ok(());
+                }
+                let Some(deps) = deps.as_deref_mut() else {
+                    // Only the type naming the source reads it.
+                    let _: syn::Lit = meta.value()?.parse()?;
+                    return Ok(());
+                };
+                match Source::parse(name, meta.value()?, Some(deps))? {
+                    Some(_) if source.is_some() => {
+                        Err(meta.error("only one config source may be given"))
+                    }
+                    Some(parsed) => {
+                        source = Some(parsed);
+                        Ok(())
+                    }
+                    None => {
+                        Err(meta.error(format!("unrecognized `const_config` attribute `{name}`")))
+                    }
+                }
+            })?;
+        }
+
+        let mut keyed = Vec::with_capacity(fields.named.len());
+        for field in &fields.named {
+            let mut rename = None;
+            for attr in &field.attrs {
+                if !attr.path().is_ident("const_config") {
+                    continue;
+                }
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        let key: LitStr = meta.value()?.parse()?;
+                        rename = Some(key.value());
+                        Ok(())
+                    } else {
+                        Err(meta.error("unrecognized `const_config` field attribute"))
+                    }
+                })?;
+            }
+            let ident = field.ident.as_ref().unwrap();
+            let key = rename.unwrap_or_else(|| apply_rename_rule(rename_all, &ident.to_string()));
+            keyed.push((field, key));
+        }
+
+        Ok(Definition {
+            fields: keyed,
+            source,
+        })
+    }
+
+    /// Builds the struct literal `ty { .. }` from the entries of the object
+    /// at `path`, reporting every field that cannot be built.
+    fn literal(
+        &self,
+        ty: &TokenStream,
+        entries: &[(String, Value)],
+        path: &[Segment],
+        ctx: &Context,
+    ) -> syn::Result<TokenStream> {
+        let mut inits = Vec::with_capacity(self.fields.len());
+        let mut errors: Option<syn::Error> = None;
+        for (field, key) in &self.fields {
+            let ident = field.ident.as_ref().unwrap();
+            let value = entries.iter().find(|(k, _)| k == key).map(|(_, v)| v);
+            let path = with_segment(path, Segment::Key(key.clone()));
+            match literal(&field.ty, value, &path, field.span(), ctx) {
+                Ok(expr) => inits.push(quote_spanned! { field.span() => #ident: #expr }),
+                Err(err) => match &mut errors {
+                    Some(errors) => errors.combine(err),
+                    None => errors = Some(err),
+                },
+            }
+        }
+        if let Some(errors) = errors {
+            return Err(errors);
+        }
+        Ok(quote! {
+            #ty {
+                #(#inits,)*
+            }
+        })
+    }
+}
+
+fn nested_macro_name(ty: &Ident) -> Ident {
+    format_ident!("__const_config_{}", ty)
+}
+
+/// The module part of `ty`, e.g. `inner` for `inner::Networking`.
+fn module_of(ty: &syn::Path) -> syn::Path {
+    let mut module = ty.clone();
+    module.segments.pop();
+    module.segments.pop_punct();
+    module
+}
+
+/// Makes `ty`, a path written in `module`, usable where `module` is.
+fn relative_to(module: &syn::Path, ty: &syn::Path) -> syn::Path {
+    if ty.leading_colon.is_some() || ty.segments.first().is_some_and(|s| s.ident == "crate") {
+        return ty.clone();
+    }
+    let mut module = module.clone();
+    let mut rest = ty.segments.iter().peekable();
+    if rest.peek().is_some_and(|s| s.ident == "self") {
+        rest.next();
+    }
+    while rest.peek().is_some_and(|s| s.ident == "super")
+        && module
+            .segments
+            .last()
+            .is_some_and(|s| !["super", "self", "crate"].iter().any(|kw| s.ident == kw))
+    {
+        rest.next();
+        module.segments.pop();
+        module.segments.pop_punct();
+    }
+    for segment in rest {
+        module.segments.push(segment.clone());
+    }
+    module
+}
+
+/// Builds the literal for a nested config type `ty`, through the macro its
+/// derive defines next to it.
+fn nested(ty: &Type, path: &[Segment], span: Span, ctx: &Context) -> syn::Result<TokenStream> {
+    let unsupported = || {
+        syn::Error::new(
+            span,
+            "unsupported field type, expected a primitive, `&'static str`, a slice, an array, \
+             `Option`, `Value`, `Date`, `Time`, `DateTime` or a type deriving `ConstConfig`",
+        )
+    };
+    let Type::Path(ty) = ty else {
+        return Err(unsupported());
+    };
+    if ty.qself.is_some()
+        || ty
+            .path
+            .segments
+            .iter()
+            .any(|s| !matches!(s.arguments, PathArguments::None))
+    {
+        return Err(unsupported());
+    }
+    let ty = match ctx.module {
+        Some(module) => relative_to(module, &ty.path),
+        None => ty.path.clone(),
+    };
+    let mut mac = ty.clone();
+    let last = mac.segments.last_mut().unwrap();
+    last.ident = nested_macro_name(&last.ident);
+    let source = ctx.source;
+    Ok(quote_spanned! { span => #mac! { #ty, [#(#path),*], #source, } })
+}
+
+fn parse_rename_rule(rule: &LitStr) -> syn::Result<RenameRule> {
+    Ok(match rule.value().as_str() {
+        "lowercase" => RenameRule::Lower,
+        "UPPERCASE" => RenameRule::Upper,
+        "camelCase" => RenameRule::Camel,
+        "PascalCase" => RenameRule::Pascal,
+        "snake_case" => RenameRule::None,
+        "SCREAMING_SNAKE_CASE" => RenameRule::ScreamingSnake,
+        "kebab-case" => RenameRule::Kebab,
+        "SCREAMING-KEBAB-CASE" => RenameRule::ScreamingKebab,
+        _ => {
+            return Err(syn::Error::new(
+                rule.span(),
+                "unknown rename rule, expected one of \"lowercase\", \"UPPERCASE\", \
+                 \"camelCase\", \"PascalCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \
+                 \"kebab-case\" or \"SCREAMING-KEBAB-CASE\"",
+            ));
+        }
+    })
+}
+
+fn apply_rename_rule(rule: RenameRule, field: &str) -> String {
+    let field = field.strip_prefix("r#").unwrap_or(field);
+    match rule {
+        RenameRule::None => field.to_string(),
+        RenameRule::Lower => field.to_ascii_lowercase(),
+        RenameRule::Upper => field.to_ascii_uppercase(),
+        RenameRule::Camel | RenameRule::Pascal => {
+            let mut out = String::with_capacity(field.len());
+            let mut upper = matches!(rule, RenameRule::Pascal);
+            for ch in field.chars() {
+                if ch == '_' {
+                    upper = !out.is_empty();
+                } else if upper {
+                    out.push(ch.to_ascii_uppercase());
+                    upper = false;
+                } else {
+                    out.push(ch);
+                }
+            }
+            out
+        }
+        RenameRule::ScreamingSnake => field.to_ascii_uppercase(),
+        RenameRule::Kebab => field.replace('_', "-"),
+        RenameRule::ScreamingKebab => field.replace('_', "-").to_ascii_uppercase(),
+    }
+}
+
+fn classify(ty: &Type) -> Kind<'_> {
+    match ty {
+        Type::Group(group) => classify(&group.elem),
+        Type::Paren(paren) => classify(&paren.elem),
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) if path.qself.is_none() && path.path.is_ident("str") => Kind::Str,
+            Type::Slice(slice) => Kind::Slice(&slice.elem),
+            _ => Kind::Nested(ty),
+        },
+        Type::Array(array) => match &array.len {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Int(len),
+                ..
+            }) => match len.base10_parse() {
+                Ok(len) => Kind::Array(&array.elem, len),
+                Err(_) => Kind::Nested(ty),
+            },
+            _ => Kind::Nested(ty),
+        },
+        Type::Path(path) if path.qself.is_none() => {
+            let Some(last) = path.path.segments.last() else {
+                return Kind::Nested(ty);
+            };
+            if let PathArguments::AngleBracketed(args) = &last.arguments {
+                if last.ident == "Option"
+                    && args.args.len() == 1
+                    && let Some(GenericArgument::Type(inner)) = args.args.first()
+                {
+                    return Kind::Option(inner);
+                }
+                if last.ident == "Value" {
+                    return Kind::Value;
+                }
+                return Kind::Nested(ty);
+            }
+            match last.ident.to_string().as_str() {
+                "bool" => Kind::Bool,
+                "u8" => Kind::Int("u8"),
+                "u16" => Kind::Int("u16"),
+                "u32" => Kind::Int("u32"),
+                "u64" => Kind::Int("u64"),
+                "u128" => Kind::Int("u128"),
+                "usize" => Kind::Int("usize"),
+                "i8" => Kind::Int("i8"),
+                "i16" => Kind::Int("i16"),
+                "i32" => Kind::Int("i32"),
+                "i64" => Kind::Int("i64"),
+                "i128" => Kind::Int("i128"),
+                "isize" => Kind::Int("isize"),
+                "f32" => Kind::Float("f32"),
+                "f64" => Kind::Float("f64"),
+                "char" => Kind::Char,
+                "Value" => Kind::Value,
+                "Date" => Kind::Date,
+                "Time" => Kind::Time,
+                "DateTime" => Kind::DateTime,
+                _ => Kind::Nested(ty),
+            }
+        }
+        _ => Kind::Nested(ty),
+    }
+}
+
+fn describe(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a bool",
+        Value::UInt(_) | Value::Int(_) => "an integer",
+        Value::Float(_) => "a float",
+        Value::Date(_) => "a date",
+        Value::Time(_) => "a time",
+        Value::DateTime(_) => "a datetime",
+        Value::Str(_) => "a string",
+        Value::Bytes(_) => "bytes",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+        Value::Map(_) => "a map",
+    }
+}
+
+fn mismatch(span: Span, path: &str, expected: &str, value: &Value) -> syn::Error {
+    let found = match value {
+        Value::Bool(value) => format!("`{value}`"),
+        Value::UInt(value) => format!("`{value}`"),
+        Value::Int(value) => format!("`{value}`"),
+        Value::Float(value) => format!("`{value:?}`"),
+        Value::Str(value) => format!("{value:?}"),
+        _ => describe(value).to_string(),
+    };
+    syn::Error::new(
+        span,
+        format!("`{path}`: expected {expected}, found {found}"),
+    )
+}
+
+fn int_value<T>(value: &Value) -> Option<T>
+where
+    T: TryFrom<u128> + TryFrom<i128>,
+{
+    match value {
+        Value::UInt(value) => T::try_from(*value).ok(),
+        Value::Int(value) => T::try_from(*value).ok(),
+        Value::Float(value) if *value >= 0.0 && (*value as u128) as f64 == *value => {
+            T::try_from(*value as u128).ok()
+        }
+        Value::Float(value) if (*value as i128) as f64 == *value => {
+            T::try_from(*value as i128).ok()
+        }
+        _ => None,
+    }
+}
+
+fn float_value(value: &Value) -> Option<f64> {
+    match value {
+        Value::UInt(value) => Some(*value as f64),
+        Value::Int(value) => Some(*value as f64),
+        Value::Float(value) => Some(*value),
+        _ => None,
+    }
+}
+
+/// Builds the literal for a field of type `ty` from `value`, found at
+/// `key` in the config. `value` is `None` if the key is missing.
+fn literal(
+    ty: &Type,
+    value: Option<&Value>,
+    key: &[Segment],
+    span: Span,
+    ctx: &Context,
+) -> syn::Result<TokenStream> {
+    let kind = classify(ty);
+
+    if let Kind::Option(inner) = kind {
+        return match value {
+            None | Some(Value::Null) => Ok(quote_spanned! { span => ::core::option::Option::None }),
+            Some(value) => {
+                let inner = literal(inner, Some(value), key, span, ctx)?;
+                Ok(quote_spanned! { span => ::core::option::Option::Some(#inner) })
+            }
+        };
+    }
+
+    let Some(value) = value else {
+        return Err(syn::Error::new(
+            span,
+            format!("missing key `{}`", display_path(key)),
+        ));
+    };
+    let path = &display_path(key)[..];
+
+    match kind {
+        Kind::Bool => match value {
+            Value::Bool(value) => Ok(quote_spanned! { span => #value }),
+            _ => Err(mismatch(span, path, "a bool", value)),
+        },
+        Kind::Int(name) => {
+            macro_rules! int_literal {
+                ($($ty:ident),*) => {
+                    match name {
+                        $(stringify!($ty) => int_value::<$ty>(value).map(|v| quote_spanned! { span => #v }),)*
+                        _ => unreachable!(),
+                    }
+                };
+            }
+            int_literal!(
+                u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+            )
+            .ok_or_else(|| {
+                mismatch(
+                    span,
+                    path,
+                    &format!("an integer fitting in `{name}`"),
+                    value,
+                )
+            })
+        }
+        Kind::Float(name) => {
+            let Some(float) = float_value(value) else {
+                return Err(mismatch(span, path, "a number", value));
+            };
+            let ty = Ident::new(name, span);
+            Ok(if float.is_nan() {
+                quote_spanned! { span => #ty::NAN }
+            } else if float == f64::INFINITY {
+                quote_spanned! { span => #ty::INFINITY }
+            } else if float == f64::NEG_INFINITY {
+                quote_spanned! { span => #ty::NEG_INFINITY }
+            } else if name == "f32" {
+                let float = float as f32;
+                if !float.is_finite() {
+                    return Err(mismatch(span, path, "a number in the range of f32", value));
+                }
+                quote_spanned! { span => #float }
+            } else {
+                quote_spanned! { span => #float }
+            })
+        }
+        Kind::Char => {
+            let ch = match value {
+                Value::Str(s) => {
+                    let mut chars = s.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(ch), None) => Some(ch),
+                        _ => None,
+                    }
+                }
+                _ => int_value::<u32>(value).and_then(char::from_u32),
+            };
+            ch.map(|ch| quote_spanned! { span => #ch })
+                .ok_or_else(|| mismatch(span, path, "a single character", value))
+        }
+        Kind::Str => {
+            let s = match value {
+                Value::Str(s) => Some(s.as_str()),
+                Value::Bytes(b) => std::str::from_utf8(b).ok(),
+                _ => None,
+            };
+            s.map(|s| {
+                let s = LitStr::new(s, span);
+                quote_spanned! { span => #s }
+            })
+            .ok_or_else(|| mismatch(span, path, "a string", value))
+        }
+        Kind::Value => crate::generate_tokens(value.clone(), span),
+        Kind::Date => match value {
+            Value::Date(date) => Ok(date_literal(date, span)),
+            _ => Err(mismatch(span, path, "a date", value)),
+        },
+        Kind::Time => match value {
+            Value::Time(time) => Ok(time_literal(time, span)),
+            _ => Err(mismatch(span, path, "a time", value)),
+        },
+        Kind::DateTime => match value {
+            Value::DateTime(dt) => {
+                let date = date_literal(&dt.date, span);
+                let time = time_literal(&dt.time, span);
+                let offset = match dt.offset {
+                    Some(offset) => {
+                        quote_spanned! { span => ::core::option::Option::Some(#offset) }
+                    }
+                    None => quote_spanned! { span => ::core::option::Option::None },
+                };
+                Ok(quote_spanned! { span =>
+                    ::const_config::DateTime { date: #date, time: #time, offset: #offset }
+                })
+            }
+            _ => Err(mismatch(span, path, "a datetime", value)),
+        },
+        Kind::Slice(elem) => match (value, classify(elem)) {
+            (Value::Bytes(b), Kind::Int("u8")) => {
+                let b = LitByteStr::new(b, span);
+                Ok(quote_spanned! { span => #b })
+            }
+            (Value::Str(s), Kind::Int("u8")) => {
+                let b = LitByteStr::new(s.as_bytes(), span);
+                Ok(quote_spanned! { span => #b })
+            }
+            (Value::Array(elems), _) => {
+                let elems = elems
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| {
+                        let key = with_segment(key, Segment::Index(idx));
+                        literal(elem, Some(value), &key, span, ctx)
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                Ok(quote_spanned! { span => &[#(#elems),*] })
+            }
+            _ => Err(mismatch(span, path, "an array", value)),
+        },
+        Kind::Array(elem, len) => match (value, classify(elem)) {
+            (Value::Bytes(b), Kind::Int("u8")) if b.len() == len => {
+                let b = LitByteStr::new(b, span);
+                Ok(quote_spanned! { span => *#b })
+            }
+            (Value::Array(elems), _) if elems.len() == len => {
+                let elems = elems
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, value)| {
+                        let key = with_segment(key, Segment::Index(idx));
+                        literal(elem, Some(value), &key, span, ctx)
+                    })
+                    .collect::<syn::Result<Vec<_>>>()?;
+                Ok(quote_spanned! { span => [#(#elems),*] })
+            }
+            _ => Err(mismatch(
+                span,
+                path,
+                &format!("an array of length {len}"),
+                value,
+            )),
+        },
+        Kind::Nested(ty) => match value {
+            Value::Object(_) => nested(ty, key, span, ctx),
+            _ => Err(mismatch(span, path, "an object", value)),
+        },
+        Kind::Option(_) => unreachable!(),
+    }
+}
+
+fn date_literal(date: &const_config_gen::Date, span: Span) -> TokenStream {
+    let (year, month, day) = (date.year, date.month, date.day);
+    quote_spanned! { span => ::const_config::Date { year: #year, month: #month, day: #day } }
+}
+
+fn time_literal(time: &const_config_gen::Time, span: Span) -> TokenStream {
+    let (hour, minute, second, nanosecond) = (time.hour, time.minute, time.second, time.nanosecond);
+    quote_spanned! { span =>
+        ::const_config::Time { hour: #hour, minute: #minute, second: #second, nanosecond: #nanosecond }
+    }
+}
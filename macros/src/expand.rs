@@ -0,0 +1,375 @@
+use crate::args::Args;
+use const_config_gen::{Format, Location, Value};
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::quote;
+use std::path::PathBuf;
+use syn::LitStr;
+
+/// The config data given to a `from_*` or `include_*` macro, along with
+/// any options that follow it.
+pub(crate) struct Input {
+    pub data: Vec<u8>,
+    pub span: Span,
+    pub origin: Origin,
+    pub args: Args,
+    pub deps: Deps,
+}
+
+impl Input {
+    /// Reads the file named by `lit`, which is relative to the invoking
+    /// source file, and tracks it as a dependency. With `text`, the file
+    /// must be UTF-8.
+    pub fn file(lit: &LitStr, args: Args, text: bool) -> syn::Result<Self> {
+        let span = lit.span();
+        let path = crate::resolve_path(span, &lit.value());
+        let mut deps = Deps::default();
+        deps.file(&path);
+
+        let data = std::fs::read(&path).map_err(|err| syn::Error::new(span, err.to_string()))?;
+        if text && let Err(err) = std::str::from_utf8(&data) {
+            return Err(syn::Error::new(span, err.to_string()));
+        }
+
+        Ok(Self {
+            data,
+            span,
+            origin: Origin::File(path),
+            args,
+            deps,
+        })
+    }
+}
+
+/// Where the config data of a macro came from.
+pub(crate) enum Origin {
+    /// Inline data given as a string or byte string literal.
+    Literal(Literal),
+    /// Data read from a file.
+    File(PathBuf),
+}
+
+/// Files and environment variables the generated code depends on. They are
+/// referenced from the generated code, so that cargo rebuilds the crate
+/// when they change.
+#[derive(Default)]
+pub(crate) struct Deps {
+    env: Vec<String>,
+    files: Vec<String>,
+}
+
+impl Deps {
+    /// Tracks a file that was read at expansion time.
+    pub fn file(&mut self, path: &std::path::Path) {
+        // `include_bytes!` needs a UTF-8 path. Anything else cannot be
+        // tracked this way.
+        if let Some(path) = path.to_str()
+            && !self.files.iter().any(|p| p == path)
+        {
+            self.files.push(path.into());
+        }
+    }
+
+    /// Item level markers for every dependency, for code generated outside
+    /// of an expression.
+    pub fn items(&self) -> TokenStream {
+        let env = &self.env;
+        let files = &self.files;
+        quote! {
+            #(const _: &[u8] = ::core::include_bytes!(#files);)*
+            #(const _: ::core::option::Option<&str> = ::core::option_env!(#env);)*
+        }
+    }
+
+    /// Reads an environment variable at expansion time and tracks it.
+    pub fn env(&mut self, var: &str) -> Option<String> {
+        if !self.env.iter().any(|v| v == var) {
+            self.env.push(var.into());
+        }
+        std::env::var(var).ok()
+    }
+
+    /// Wraps the generated `expr` so that it references every dependency.
+    pub fn wrap(&self, expr: TokenStream) -> TokenStream {
+        if self.env.is_empty() && self.files.is_empty() {
+            return expr;
+        }
+        let items = self.items();
+        quote! {
+            {
+                #items
+                #expr
+            }
+        }
+    }
+}
+
+/// Parses `input` as `format`, applies the options and generates the
+/// `const` expression for the resulting [`Value`].
+pub(crate) fn expand(input: Input, format: Format) -> syn::Result<TokenStream> {
+    let Input {
+        data,
+        span,
+        origin,
+        mut args,
+        mut deps,
+    } = input;
+
+    let profile = args.take_env_str("profile", &mut deps)?;
+    let interpolate = args.take_bool("interpolate")?;
+    let timestamps = args.take_bool("timestamps")?;
+    let expand_keys = args.take_bool("expand_keys")?;
+    let coerce = args.take_bool("coerce")?;
+    let schema = args
+        .take_str("schema")?
+        .map(|lit| load_schema(&lit, &mut deps))
+        .transpose()?;
+    let namespaces = args.take_str("namespaces")?;
+    args.finish()?;
+
+    let parsed = match namespaces {
+        Some(lit) => parse_xml(format, &data, &lit)?,
+        None => format.parse(&data[..]),
+    };
+    let mut value = parsed.map_err(|err| parse_error(&err, format, &data, &origin, span))?;
+
+    if expand_keys {
+        value
+            .expand_dotted_keys()
+            .map_err(|err| syn::Error::new(span, err.to_string()))?;
+    }
+
+    if let Some(profile) = profile {
+        value
+            .select_profile(profile.value.as_deref())
+            .map_err(|err| syn::Error::new(profile.span, err.to_string()))?;
+    }
+
+    if interpolate {
+        value
+            .interpolate_env(|var| deps.env(var))
+            .map_err(|err| syn::Error::new(span, err.to_string()))?;
+    }
+
+    if coerce {
+        value.coerce_scalars();
+    }
+
+    if timestamps {
+        value.detect_timestamps();
+    }
+
+    if let Some(schema) = schema {
+        value
+            .validate(&schema)
+            .map_err(|err| syn::Error::new(span, err.to_string()))?;
+    }
+
+    Ok(deps.wrap(crate::generate_tokens(value, span)?))
+}
+
+/// Parses XML with the namespace handling named by a `namespaces` option,
+/// which is an error for any other format.
+fn parse_xml(
+    format: Format,
+    data: &[u8],
+    lit: &LitStr,
+) -> syn::Result<Result<Value, const_config_gen::Error>> {
+    #[cfg(feature = "xml")]
+    if format == Format::Xml {
+        use const_config_gen::xml;
+        let namespaces = xml::Namespaces::from_name(&lit.value()).ok_or_else(|| {
+            syn::Error::new(
+                lit.span(),
+                "expected `\"prefix\"`, `\"strip\"` or `\"uri\"`",
+            )
+        })?;
+        return Ok(xml::parse_with(data, namespaces).map_err(Into::into));
+    }
+    Err(syn::Error::new(
+        lit.span(),
+        "the `namespaces` option only applies to XML",
+    ))
+}
+
+/// Loads the JSON Schema file named by a `schema` option. Like config files,
+/// the path is relative to the invoking source file, and the format is
+/// detected with [`Format::detect`].
+pub(crate) fn load_schema(lit: &LitStr, deps: &mut Deps) -> syn::Result<Value> {
+    let path = crate::resolve_path(lit.span(), &lit.value());
+    deps.file(&path);
+    let data = std::fs::read(&path)
+        .map_err(|err| syn::Error::new(lit.span(), format!("{}: {err}", path.display())))?;
+    let format = Format::detect(Some(&path), &data).map_err(|err| {
+        syn::Error::new(
+            lit.span(),
+            format!("cannot determine the format of `{}`: {err}", lit.value()),
+        )
+    })?;
+    format
+        .parse(&data[..])
+        .map_err(|err| parse_error(&err, format, &data, &Origin::File(path), lit.span()))
+}
+
+/// Builds the error for `data` failing to parse, pointing at where in the
+/// input the error was found.
+///
+/// For inline data, the error points into the literal when the compiler
+/// supports it, and otherwise names the line and column. For files, the
+/// offending line is quoted with a caret under the error.
+pub(crate) fn parse_error(
+    err: &const_config_gen::Error,
+    format: Format,
+    data: &[u8],
+    origin: &Origin,
+    span: Span,
+) -> syn::Error {
+    let file = match origin {
+        Origin::Literal(_) => None,
+        Origin::File(path) => Some(path.display()),
+    };
+    let message = |message: String| match &file {
+        Some(file) => format!("{file}: {message}"),
+        None => message,
+    };
+    let Some(location) = err.location() else {
+        return syn::Error::new(span, message(err.to_string()));
+    };
+
+    let text = std::str::from_utf8(data)
+        .ok()
+        .filter(|_| !format.is_binary());
+    let Some(text) = text else {
+        let offset = location.column - 1;
+        return syn::Error::new(span, message(format!("{err} (at byte {offset})")));
+    };
+
+    match origin {
+        Origin::Literal(lit) => match literal_subspan(lit, text, location) {
+            Some(span) => syn::Error::new(span, err.to_string()),
+            None => syn::Error::new(span, format!("{location}: {err}")),
+        },
+        Origin::File(path) => syn::Error::new(
+            span,
+            format!(
+                "{}:{}:{}: {err}\n{}",
+                path.display(),
+                location.line,
+                location.column,
+                snippet(text, location),
+            ),
+        ),
+    }
+}
+
+/// The span of the character at `location` within the string literal
+/// `lit`, whose value is `text`.
+///
+/// This only works for literals without escapes, where the source text of
+/// the literal matches its value, and only on compilers that support
+/// subspans.
+fn literal_subspan(lit: &Literal, text: &str, location: Location) -> Option<Span> {
+    let repr = lit.to_string();
+    let (open, close) = match repr.strip_prefix('r') {
+        Some(raw) => {
+            let hashes = raw.len() - raw.trim_start_matches('#').len();
+            (hashes + 2, hashes + 1)
+        }
+        None => (1, 1),
+    };
+    if repr.get(open..repr.len().checked_sub(close)?)? != text {
+        return None;
+    }
+    let offset = location.offset_in(text)?;
+    let len = text[offset..].chars().next().map_or(0, char::len_utf8);
+    lit.subspan(open + offset..open + offset + len)
+}
+
+/// The line of `text` at `location`, with a caret under the column.
+fn snippet(text: &str, location: Location) -> String {
+    let line = text.lines().nth(location.line - 1).unwrap_or_default();
+    let indent: String = line
+        .chars()
+        .take(location.column.max(1) - 1)
+        .map(|c| if c == '\t' { '\t' } else { ' ' })
+        .collect();
+    let gutter = " ".repeat(location.line.to_string().len());
+    format!(
+        "{gutter} |\n{} | {line}\n{gutter} | {indent}^",
+        location.line
+    )
+}
+
+#[cfg(all(test, feature = "toml", feature = "json"))]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use syn::Lit;
+
+    fn fixture(name: &str) -> PathBuf {
+        Path::new(env!("CARGO_MANIFEST_DIR"))
+            .parent()
+            .unwrap()
+            .join("tests")
+            .join(name)
+    }
+
+    fn file_marker(path: &Path) -> String {
+        let path = path.to_str().unwrap();
+        quote!(::core::include_bytes!(#path)).to_string()
+    }
+
+    fn env_marker(var: &str) -> String {
+        quote!(::core::option_env!(#var)).to_string()
+    }
+
+    #[test]
+    fn include_tracks_files() {
+        let config = fixture("profile.toml");
+        let schema = fixture("schema/app.schema.json");
+        let args: Args = syn::parse_str(&format!(
+            "{:?}, profile = \"prod\", schema = {:?}",
+            config.to_str().unwrap(),
+            schema.to_str().unwrap(),
+        ))
+        .unwrap();
+        let Lit::Str(lit) = args.inputs[0].clone() else {
+            unreachable!()
+        };
+
+        let input = Input::file(&lit, args, true).unwrap();
+        let code = expand(input, Format::Toml).unwrap().to_string();
+        assert!(code.contains(&file_marker(&config)), "{code}");
+        assert!(code.contains(&file_marker(&schema)), "{code}");
+    }
+
+    #[test]
+    fn from_tracks_env() {
+        let args: Args = syn::parse_str(
+            r#""name = \"${CONST_CONFIG_TEST_NAME:-app}\"",
+            profile = option_env!("CONST_CONFIG_TEST_PROFILE"),
+            interpolate = true"#,
+        )
+        .unwrap();
+        let Lit::Str(lit) = args.inputs[0].clone() else {
+            unreachable!()
+        };
+
+        let input = Input {
+            data: lit.value().into_bytes(),
+            span: lit.span(),
+            origin: Origin::Literal(lit.token()),
+            args,
+            deps: Deps::default(),
+        };
+        let code = expand(input, Format::Toml).unwrap().to_string();
+        assert!(
+            code.contains(&env_marker("CONST_CONFIG_TEST_NAME")),
+            "{code}"
+        );
+        assert!(
+            code.contains(&env_marker("CONST_CONFIG_TEST_PROFILE")),
+            "{code}"
+        );
+        assert!(!code.contains("include_bytes"), "{code}");
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    args::Args,
+    expand::{Deps, Origin},
+};
+use const_config_gen::{ArrayMerge, Format, Value};
+use proc_macro2::{Span, TokenStream};
+
+pub(crate) fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let mut args: Args = syn::parse2(input)?;
+    let arrays = match args.take_str("arrays")? {
+        None => ArrayMerge::default(),
+        Some(lit) => ArrayMerge::from_name(&lit.value())
+            .ok_or_else(|| syn::Error::new(lit.span(), "expected `\"replace\"` or `\"append\"`"))?,
+    };
+    let timestamps = args.take_bool("timestamps")?;
+    let expand_keys = args.take_bool("expand_keys")?;
+    let coerce = args.take_bool("coerce")?;
+    let paths = args.input_strs()?;
+    let mut deps = Deps::default();
+    let schema = args
+        .take_str("schema")?
+        .map(|lit| crate::expand::load_schema(&lit, &mut deps))
+        .transpose()?;
+    args.finish()?;
+
+    if paths.is_empty() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "expected at least one config file",
+        ));
+    }
+
+    let mut merged: Option<Value> = None;
+    for path in paths {
+        let resolved = crate::resolve_path(path.span(), &path.value());
+        deps.file(&resolved);
+        let data = std::fs::read(&resolved).map_err(|err| {
+            syn::Error::new(path.span(), format!("{}: {err}", resolved.display()))
+        })?;
+        let format = Format::detect(Some(&resolved), &data).map_err(|err| {
+            syn::Error::new(
+                path.span(),
+                format!("cannot determine the format of `{}`: {err}", path.value()),
+            )
+        })?;
+        let mut value = format.parse(&data[..]).map_err(|err| {
+            crate::expand::parse_error(&err, format, &data, &Origin::File(resolved), path.span())
+        })?;
+        if expand_keys {
+            value
+                .expand_dotted_keys()
+                .map_err(|err| syn::Error::new(path.span(), err.to_string()))?;
+        }
+        match &mut merged {
+            Some(merged) => merged.merge(value, arrays),
+            None => merged = Some(value),
+        }
+    }
+
+    let mut merged = merged.unwrap();
+    if coerce {
+        merged.coerce_scalars();
+    }
+    if timestamps {
+        merged.detect_timestamps();
+    }
+    if let Some(schema) = schema {
+        merged
+            .validate(&schema)
+            .map_err(|err| syn::Error::new(Span::call_site(), err.to_string()))?;
+    }
+
+    Ok(deps.wrap(crate::generate_tokens(merged, Span::call_site())?))
+}
@@ -1,29 +1,49 @@
 #[allow(unused)]
-use litrs::Literal;
+mod args;
+mod derive;
+#[allow(unused)]
+mod expand;
+#[allow(unused)]
+mod layered;
+
+#[allow(unused)]
+use const_config_gen::Format;
 #[allow(unused)]
 use proc_macro::TokenStream;
 #[allow(unused)]
 use quote::quote;
+#[allow(unused)]
+use syn::Lit;
+
+#[allow(unused)]
+macro_rules! parse_args {
+    ($args:expr, $expected:literal) => {{
+        let args = match syn::parse::<args::Args>($args) {
+            Ok(args) => args,
+            Err(err) => return err.into_compile_error().into(),
+        };
+        if args.inputs.len() != 1 {
+            return quote! { ::core::compile_error!($expected) }.into();
+        }
+        args
+    }};
+}
 
 #[allow(unused)]
 macro_rules! parse_str {
     ($args:expr) => {{
-        let mut toks = $args.into_iter();
-        let Some(tok) = toks.next() else {
+        let args = parse_args!($args, "exepcted one string literal argument");
+        let Lit::Str(lit) = &args.inputs[0] else {
             return quote! { ::core::compile_error!("exepcted one string literal argument") }
                 .into();
         };
-        if toks.next().is_some() {
-            return quote! { ::core::compile_error!("exepcted one string literal argument") }
-                .into();
-        }
 
-        match Literal::try_from(tok) {
-            Ok(Literal::String(lit)) => String::from(lit.value()),
-            _ => {
-                return quote! { ::core::compile_error!("exepcted one string literal argument") }
-                    .into();
-            }
+        expand::Input {
+            data: lit.value().into_bytes(),
+            span: lit.span(),
+            origin: expand::Origin::Literal(lit.token()),
+            args,
+            deps: expand::Deps::default(),
         }
     }};
 }
@@ -31,20 +51,17 @@ macro_rules! parse_str {
 #[allow(unused)]
 macro_rules! parse_bytes {
     ($args:expr) => {{
-        let mut toks = $args.into_iter();
-        let Some(tok) = toks.next() else {
+        let args = parse_args!($args, "exepcted one bytes literal argument");
+        let Lit::ByteStr(lit) = &args.inputs[0] else {
             return quote! { ::core::compile_error!("exepcted one bytes literal argument") }.into();
         };
-        if toks.next().is_some() {
-            return quote! { ::core::compile_error!("exepcted one bytes literal argument") }.into();
-        }
 
-        match Literal::try_from(tok) {
-            Ok(Literal::ByteString(lit)) => Vec::<u8>::from(lit.value()),
-            _ => {
-                return quote! { ::core::compile_error!("exepcted one bytes literal argument") }
-                    .into();
-            }
+        expand::Input {
+            data: lit.value(),
+            span: lit.span(),
+            origin: expand::Origin::Literal(lit.token()),
+            args,
+            deps: expand::Deps::default(),
         }
     }};
 }
@@ -52,277 +69,388 @@ macro_rules! parse_bytes {
 #[allow(unused)]
 macro_rules! parse_path_str {
     ($args:expr) => {{
-        let base_dir = ($args)
-            .clone()
-            .into_iter()
-            .next()
-            .and_then(|tt| tt.span().local_file())
-            .map(|mut p| {
-                p.pop();
-                p
-            });
-        let path = parse_str!($args);
-        let path = std::path::Path::new(&path);
-        let path = if path.is_absolute() {
-            path.to_path_buf()
-        } else if let Some(base_dir) = base_dir {
-            base_dir.join(path)
-        } else {
-            path.to_path_buf()
-        };
-
-        use std::io::Read;
-
-        let mut s = String::new();
-        let mut f = match std::fs::File::open(path) {
-            Ok(f) => f,
-            Err(err) => {
-                let err = err.to_string();
-                return quote! { ::core::compile_error!(#err); }.into();
-            }
+        let args = parse_args!($args, "exepcted one string literal argument");
+        let Lit::Str(lit) = &args.inputs[0] else {
+            return quote! { ::core::compile_error!("exepcted one string literal argument") }
+                .into();
         };
-
-        if let Err(err) = f.read_to_string(&mut s) {
-            let err = err.to_string();
-            return quote! { ::core::compile_error!(#err); }.into();
+        let lit = lit.clone();
+        match expand::Input::file(&lit, args, true) {
+            Ok(input) => input,
+            Err(err) => return err.into_compile_error().into(),
         }
-
-        s
     }};
 }
 
 #[allow(unused)]
 macro_rules! parse_path_bytes {
     ($args:expr) => {{
-        let base_dir = ($args)
-            .clone()
-            .into_iter()
-            .next()
-            .and_then(|tt| tt.span().local_file())
-            .map(|mut p| {
-                p.pop();
-                p
-            });
-        let path = parse_str!($args);
-        let path = std::path::Path::new(&path);
-        let path = if path.is_absolute() {
-            path.to_path_buf()
-        } else if let Some(base_dir) = base_dir {
-            base_dir.join(path)
-        } else {
-            path.to_path_buf()
-        };
-
-        use std::io::Read;
-
-        let mut b = Vec::<u8>::new();
-        let mut f = match std::fs::File::open(path) {
-            Ok(f) => f,
-            Err(err) => {
-                let err = err.to_string();
-                return quote! { ::core::compile_error!(#err); }.into();
-            }
+        let args = parse_args!($args, "exepcted one string literal argument");
+        let Lit::Str(lit) = &args.inputs[0] else {
+            return quote! { ::core::compile_error!("exepcted one string literal argument") }
+                .into();
         };
-
-        if let Err(err) = f.read_to_end(&mut b) {
-            let err = err.to_string();
-            return quote! { ::core::compile_error!(#err); }.into();
+        let lit = lit.clone();
+        match expand::Input::file(&lit, args, false) {
+            Ok(input) => input,
+            Err(err) => return err.into_compile_error().into(),
         }
-
-        b
     }};
 }
 
 #[allow(unused)]
 macro_rules! gen_impl {
-    ($value:expr, $gen:expr) => {{
-        let mut code: Vec<u8> = Vec::new();
-        if let Err(err) = ($gen)(as_bytes($value), &mut code) {
-            let err = err.to_string();
-            return quote! { ::core::compile_error!(#err) }.into();
-        }
+    ($input:expr, $format:expr) => {
+        expand::expand($input, $format)
+            .unwrap_or_else(syn::Error::into_compile_error)
+            .into()
+    };
+}
 
-        match String::from_utf8(code) {
-            Ok(code) => match code.parse::<TokenStream>() {
-                Ok(code) => code,
-                Err(err) => {
-                    let err = err.to_string();
-                    quote! { ::core::compile_error!(#err) }.into()
-                }
-            },
-            Err(err) => {
-                let err = err.to_string();
-                quote! { ::core::compile_error!(#err) }.into()
+/// Resolves `path` relative to the directory of the source file containing
+/// `span`, the same way `include_str!` does. The result is absolute, so it
+/// can be passed on to `include_bytes!` in generated code.
+fn resolve_path(span: proc_macro2::Span, path: &str) -> std::path::PathBuf {
+    let path = std::path::Path::new(path);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        match span.unwrap().local_file() {
+            Some(mut base_dir) => {
+                base_dir.pop();
+                base_dir.join(path)
             }
+            None => path.to_path_buf(),
         }
-    }};
-    ($value:expr, $gen:expr,) => {
-        impl_str!($value, $gen)
     };
+    std::path::absolute(&path).unwrap_or(path)
+}
+
+/// Generates the code for a `const` expression of `value`.
+fn generate_tokens(
+    value: const_config_gen::Value,
+    span: proc_macro2::Span,
+) -> syn::Result<proc_macro2::TokenStream> {
+    let mut code = Vec::new();
+    const_config_gen::generate_compact(value, &mut code)
+        .map_err(|err| syn::Error::new(span, err.to_string()))?;
+    String::from_utf8(code)
+        .map_err(|err| syn::Error::new(span, err.to_string()))?
+        .parse()
+        .map_err(|err: proc_macro2::LexError| syn::Error::new(span, err.to_string()))
+}
+
+#[proc_macro_derive(ConstConfig, attributes(const_config))]
+pub fn derive_const_config(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    derive::expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
 }
 
-fn as_bytes<T: AsRef<[u8]>>(data: &T) -> &[u8] {
-    data.as_ref()
+#[doc(hidden)]
+#[proc_macro]
+pub fn __const_config_nested(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as derive::Nested);
+    // Expands to an expression, so several errors need to be in a block.
+    derive::expand_nested(input)
+        .unwrap_or_else(|err| {
+            let err = err.into_compile_error();
+            quote::quote! { { #err } }
+        })
+        .into()
+}
+
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "yaml",
+    feature = "ron",
+    feature = "json5",
+    feature = "s-expr",
+    feature = "hjson",
+    feature = "csv",
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "pickle",
+    feature = "bson",
+    feature = "flexbuffers",
+    feature = "ini",
+    feature = "properties",
+    feature = "dotenv",
+    feature = "xml",
+    feature = "kdl",
+    feature = "jsonl",
+))]
+#[proc_macro]
+pub fn include_layered(args: TokenStream) -> TokenStream {
+    layered::expand(args.into())
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "yaml",
+    feature = "ron",
+    feature = "json5",
+    feature = "s-expr",
+    feature = "hjson",
+    feature = "csv",
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "pickle",
+    feature = "bson",
+    feature = "flexbuffers",
+    feature = "ini",
+    feature = "properties",
+    feature = "dotenv",
+    feature = "xml",
+    feature = "kdl",
+    feature = "jsonl",
+))]
+#[proc_macro]
+pub fn include_config(args: TokenStream) -> TokenStream {
+    let input = parse_path_bytes!(args);
+    let expand::Origin::File(path) = &input.origin else {
+        unreachable!()
+    };
+    match Format::detect(Some(path), &input.data) {
+        Ok(format) => gen_impl!(input, format),
+        Err(err) => syn::Error::new(
+            input.span,
+            format!("cannot determine the format of `{}`: {err}", path.display()),
+        )
+        .into_compile_error()
+        .into(),
+    }
 }
 
 #[cfg(feature = "toml")]
 #[proc_macro]
 pub fn from_toml(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::toml::generate)
+    gen_impl!(parse_str!(args), Format::Toml)
 }
 
 #[cfg(feature = "toml")]
 #[proc_macro]
 pub fn include_toml(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::toml::generate)
+    gen_impl!(parse_path_str!(args), Format::Toml)
 }
 
 #[cfg(feature = "json")]
 #[proc_macro]
 pub fn from_json(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::json::generate)
+    gen_impl!(parse_str!(args), Format::Json)
 }
 
 #[cfg(feature = "json")]
 #[proc_macro]
 pub fn include_json(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::json::generate)
+    gen_impl!(parse_path_str!(args), Format::Json)
 }
 
 #[cfg(feature = "yaml")]
 #[proc_macro]
 pub fn from_yaml(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::yaml::generate)
+    gen_impl!(parse_str!(args), Format::Yaml)
 }
 
 #[cfg(feature = "yaml")]
 #[proc_macro]
 pub fn include_yaml(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::yaml::generate)
+    gen_impl!(parse_path_str!(args), Format::Yaml)
 }
 
 #[cfg(feature = "ron")]
 #[proc_macro]
 pub fn from_ron(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::ron::generate)
+    gen_impl!(parse_str!(args), Format::Ron)
 }
 
 #[cfg(feature = "ron")]
 #[proc_macro]
 pub fn include_ron(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::ron::generate)
+    gen_impl!(parse_path_str!(args), Format::Ron)
 }
 
 #[cfg(feature = "json5")]
 #[proc_macro]
 pub fn from_json5(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::json5::generate)
+    gen_impl!(parse_str!(args), Format::Json5)
 }
 
 #[cfg(feature = "json5")]
 #[proc_macro]
 pub fn include_json5(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::json5::generate)
+    gen_impl!(parse_path_str!(args), Format::Json5)
 }
 
 #[cfg(feature = "s-expr")]
 #[proc_macro]
 pub fn from_s_expr(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::s_expr::generate)
+    gen_impl!(parse_str!(args), Format::SExpr)
 }
 
 #[cfg(feature = "s-expr")]
 #[proc_macro]
 pub fn include_s_expr(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::s_expr::generate)
+    gen_impl!(parse_path_str!(args), Format::SExpr)
 }
 
 #[cfg(feature = "hjson")]
 #[proc_macro]
 pub fn from_hjson(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::hjson::generate)
+    gen_impl!(parse_str!(args), Format::Hjson)
 }
 
 #[cfg(feature = "hjson")]
 #[proc_macro]
 pub fn include_hjson(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::hjson::generate)
+    gen_impl!(parse_path_str!(args), Format::Hjson)
 }
 
 #[cfg(feature = "csv")]
 #[proc_macro]
 pub fn from_csv(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_str!(args), const_config_gen::csv::generate)
+    gen_impl!(parse_str!(args), Format::Csv)
 }
 
 #[cfg(feature = "csv")]
 #[proc_macro]
 pub fn include_csv(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_str!(args), const_config_gen::csv::generate)
+    gen_impl!(parse_path_str!(args), Format::Csv)
 }
 
 #[cfg(feature = "cbor")]
 #[proc_macro]
 pub fn from_cbor(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_bytes!(args), const_config_gen::cbor::generate)
+    gen_impl!(parse_bytes!(args), Format::Cbor)
 }
 
 #[cfg(feature = "cbor")]
 #[proc_macro]
 pub fn include_cbor(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_bytes!(args), const_config_gen::cbor::generate)
+    gen_impl!(parse_path_bytes!(args), Format::Cbor)
 }
 
 #[cfg(feature = "msgpack")]
 #[proc_macro]
 pub fn from_msgpack(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_bytes!(args), const_config_gen::msgpack::generate)
+    gen_impl!(parse_bytes!(args), Format::Msgpack)
 }
 
 #[cfg(feature = "msgpack")]
 #[proc_macro]
 pub fn include_msgpack(args: TokenStream) -> TokenStream {
-    gen_impl!(
-        &parse_path_bytes!(args),
-        const_config_gen::msgpack::generate
-    )
+    gen_impl!(parse_path_bytes!(args), Format::Msgpack)
 }
 
 #[cfg(feature = "pickle")]
 #[proc_macro]
 pub fn from_pickle(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_bytes!(args), const_config_gen::pickle::generate)
+    gen_impl!(parse_bytes!(args), Format::Pickle)
 }
 
 #[cfg(feature = "pickle")]
 #[proc_macro]
 pub fn include_pickle(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_bytes!(args), const_config_gen::pickle::generate)
+    gen_impl!(parse_path_bytes!(args), Format::Pickle)
 }
 
 #[cfg(feature = "bson")]
 #[proc_macro]
 pub fn from_bson(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_bytes!(args), const_config_gen::bson::generate)
+    gen_impl!(parse_bytes!(args), Format::Bson)
 }
 
 #[cfg(feature = "bson")]
 #[proc_macro]
 pub fn include_bson(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_path_bytes!(args), const_config_gen::bson::generate)
+    gen_impl!(parse_path_bytes!(args), Format::Bson)
 }
 
 #[cfg(feature = "flexbuffers")]
 #[proc_macro]
 pub fn from_flexbuffers(args: TokenStream) -> TokenStream {
-    gen_impl!(&parse_bytes!(args), const_config_gen::flexbuffers::generate)
+    gen_impl!(parse_bytes!(args), Format::Flexbuffers)
 }
 
 #[cfg(feature = "flexbuffers")]
 #[proc_macro]
 pub fn include_flexbuffers(args: TokenStream) -> TokenStream {
-    gen_impl!(
-        &parse_path_bytes!(args),
-        const_config_gen::flexbuffers::generate
-    )
+    gen_impl!(parse_path_bytes!(args), Format::Flexbuffers)
+}
+
+#[cfg(feature = "ini")]
+#[proc_macro]
+pub fn from_ini(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_str!(args), Format::Ini)
+}
+
+#[cfg(feature = "ini")]
+#[proc_macro]
+pub fn include_ini(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_path_str!(args), Format::Ini)
+}
+
+#[cfg(feature = "properties")]
+#[proc_macro]
+pub fn from_properties(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_str!(args), Format::Properties)
+}
+
+#[cfg(feature = "properties")]
+#[proc_macro]
+pub fn include_properties(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_path_str!(args), Format::Properties)
+}
+
+#[cfg(feature = "dotenv")]
+#[proc_macro]
+pub fn from_dotenv(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_str!(args), Format::Dotenv)
+}
+
+#[cfg(feature = "dotenv")]
+#[proc_macro]
+pub fn include_dotenv(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_path_str!(args), Format::Dotenv)
+}
+
+#[cfg(feature = "xml")]
+#[proc_macro]
+pub fn from_xml(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_str!(args), Format::Xml)
+}
+
+#[cfg(feature = "xml")]
+#[proc_macro]
+pub fn include_xml(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_path_str!(args), Format::Xml)
+}
+
+#[cfg(feature = "kdl")]
+#[proc_macro]
+pub fn from_kdl(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_str!(args), Format::Kdl)
+}
+
+#[cfg(feature = "kdl")]
+#[proc_macro]
+pub fn include_kdl(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_path_str!(args), Format::Kdl)
+}
+
+#[cfg(feature = "jsonl")]
+#[proc_macro]
+pub fn from_jsonl(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_str!(args), Format::Jsonl)
+}
+
+#[cfg(feature = "jsonl")]
+#[proc_macro]
+pub fn include_jsonl(args: TokenStream) -> TokenStream {
+    gen_impl!(parse_path_str!(args), Format::Jsonl)
 }
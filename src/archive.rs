@@ -0,0 +1,819 @@
+//! Configs read at runtime from a binary archive.
+//!
+//! An archive is written by `const-config-gen` with `--emit archive`, or
+//! with `Emit::Archive` from a build script, and holds the same tree as a
+//! generated [`Value`], perfect hash tables included. [`ArchivedValue`]
+//! reads it in place, so the archive can be shipped beside the binary, or
+//! memory mapped, and swapped without recompiling.
+//!
+//! # Layout
+//! All integers are little endian, and all offsets are `u32`s counted from
+//! the start of the archive. The archive starts with a 16 byte header: the
+//! magic bytes `CCFGARCH`, the format version as a `u32`, and four zero
+//! bytes. The root value follows at offset 16.
+//!
+//! Every value is a 16 byte slot, aligned to 8 bytes: a tag byte, a flag
+//! byte, an `i16`, a `u32` `a` and a `u64` `b`.
+//!
+//! | Tag | Value | Contents |
+//! |-----|-------|----------|
+//! | 0 | null | |
+//! | 1 | bool | the flag |
+//! | 2 | unsigned integer | `b` |
+//! | 3 | signed integer | `b` as an `i64` |
+//! | 4 | 128-bit unsigned integer | `a` is the offset of 16 bytes |
+//! | 5 | 128-bit signed integer | `a` is the offset of 16 bytes |
+//! | 6 | float | `b` holds the bits of an `f64` |
+//! | 7 | date | `a` holds the year, month and day from the lowest bits |
+//! | 8 | time | `a` holds the hour, minute and second, `b` the nanosecond |
+//! | 9 | date-time | `a` holds the date, `b` the hour, minute, second and nanosecond from bit 32, the flag whether there is an offset, and the `i16` the offset |
+//! | 10 | string | `a` is the offset, `b` the length of UTF-8 |
+//! | 11 | bytes | `a` is the offset, `b` the length |
+//! | 12 | array | `a` is the offset, `b` the length of an array of slots |
+//! | 13 | object | `a` is the offset, `b` the length of an array of entries |
+//! | 14 | map | `a` is the offset, `b` the length of an array of entries |
+//!
+//! An object entry is 24 bytes: the offset and length of the key, and the
+//! slot of the value. A map entry is 32 bytes: the slots of the key and the
+//! value. The entries are followed by the parameters, then the values, of
+//! the perfect hash table as arrays of `u32`s of the same length.
+
+use crate::{Date, DateTime, Map, Number, Object, Time, Value};
+use core::fmt::{self, Display};
+
+const MAGIC: &[u8; 8] = b"CCFGARCH";
+const VERSION: u32 = 1;
+const HEADER_LEN: usize = 16;
+const SLOT_LEN: usize = 16;
+const OBJECT_ENTRY_LEN: usize = 24;
+const MAP_ENTRY_LEN: usize = 32;
+
+/// How deeply values may be nested in an archive.
+const MAX_DEPTH: usize = 128;
+
+const NULL: u8 = 0;
+const BOOL: u8 = 1;
+const UINT: u8 = 2;
+const INT: u8 = 3;
+const UINT128: u8 = 4;
+const INT128: u8 = 5;
+const FLOAT: u8 = 6;
+const DATE: u8 = 7;
+const TIME: u8 = 8;
+const DATETIME: u8 = 9;
+const STR: u8 = 10;
+const BYTES: u8 = 11;
+const ARRAY: u8 = 12;
+const OBJECT: u8 = 13;
+const MAP: u8 = 14;
+
+/// Error produced when an archive is invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ArchiveError {
+    /// The data does not start with the magic bytes of an archive.
+    BadMagic,
+    /// The archive was written in another version of the format.
+    UnsupportedVersion(u32),
+    /// Something at `offset` extends past the end of the archive.
+    OutOfBounds { offset: usize },
+    /// The slots at `offset` are not aligned to 8 bytes.
+    Misaligned { offset: usize },
+    /// The slot at `offset` has an unknown tag or invalid contents.
+    InvalidSlot { offset: usize },
+    /// The string at `offset` is not valid UTF-8.
+    InvalidUtf8 { offset: usize },
+    /// The hash table of the object or map at `offset` is invalid.
+    InvalidHashTable { offset: usize },
+    /// Values are nested too deeply.
+    TooDeep,
+    /// The archive holds more values than fit in it, which means that
+    /// values are referred to more than once.
+    TooManyValues,
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::BadMagic => f.write_str("not a const-config archive"),
+            Self::UnsupportedVersion(version) => {
+                write!(f, "unsupported archive version {version}")
+            }
+            Self::OutOfBounds { offset } => {
+                write!(f, "data at offset {offset} is out of bounds")
+            }
+            Self::Misaligned { offset } => write!(f, "values at offset {offset} are misaligned"),
+            Self::InvalidSlot { offset } => write!(f, "invalid value at offset {offset}"),
+            Self::InvalidUtf8 { offset } => {
+                write!(f, "string at offset {offset} is not valid UTF-8")
+            }
+            Self::InvalidHashTable { offset } => {
+                write!(f, "invalid hash table at offset {offset}")
+            }
+            Self::TooDeep => write!(f, "values are nested more than {MAX_DEPTH} levels deep"),
+            Self::TooManyValues => f.write_str("values are referred to more than once"),
+        }
+    }
+}
+
+impl core::error::Error for ArchiveError {}
+
+fn read_u32(data: &[u8], at: usize) -> u32 {
+    u32::from_le_bytes(data[at..at + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], at: usize) -> u64 {
+    u64::from_le_bytes(data[at..at + 8].try_into().unwrap())
+}
+
+fn read_u128(data: &[u8], at: usize) -> u128 {
+    u128::from_le_bytes(data[at..at + 16].try_into().unwrap())
+}
+
+fn unpack_date(packed: u32) -> Date {
+    Date {
+        year: packed as u16,
+        month: (packed >> 16) as u8,
+        day: (packed >> 24) as u8,
+    }
+}
+
+fn unpack_time(packed: u64) -> Time {
+    Time {
+        hour: packed as u8,
+        minute: (packed >> 8) as u8,
+        second: (packed >> 16) as u8,
+        nanosecond: (packed >> 32) as u32,
+    }
+}
+
+/// Checks every slot reachable from the root once, so that reading the
+/// archive afterwards cannot fail.
+struct Validator<'a> {
+    data: &'a [u8],
+    /// How many more slots may be visited. Every slot takes 16 bytes, so an
+    /// archive with more slots than that refers to some of them twice, and
+    /// could take exponential time to validate.
+    budget: usize,
+}
+
+impl Validator<'_> {
+    /// Checks that `len` bytes at `offset` are in bounds and aligned to
+    /// `align`, returning the offset as a `usize`.
+    fn region(&self, offset: u32, len: Option<usize>, align: usize) -> Result<usize, ArchiveError> {
+        let offset = offset as usize;
+        match len.and_then(|len| offset.checked_add(len)) {
+            Some(end) if end <= self.data.len() => {}
+            _ => return Err(ArchiveError::OutOfBounds { offset }),
+        }
+        if !offset.is_multiple_of(align) {
+            return Err(ArchiveError::Misaligned { offset });
+        }
+        Ok(offset)
+    }
+
+    fn len(&self, len: u64, offset: usize) -> Result<usize, ArchiveError> {
+        usize::try_from(len).map_err(|_| ArchiveError::OutOfBounds { offset })
+    }
+
+    fn str(&self, offset: u32, len: usize) -> Result<(), ArchiveError> {
+        let start = self.region(offset, Some(len), 1)?;
+        core::str::from_utf8(&self.data[start..start + len])
+            .map(|_| ())
+            .map_err(|_| ArchiveError::InvalidUtf8 { offset: start })
+    }
+
+    fn hash_table(&self, at: usize, len: usize) -> Result<(), ArchiveError> {
+        for idx in 0..len {
+            if read_u32(self.data, at + 4 * (len + idx)) as usize >= len {
+                return Err(ArchiveError::InvalidHashTable { offset: at });
+            }
+        }
+        Ok(())
+    }
+
+    fn slot(&mut self, at: usize, depth: usize) -> Result<(), ArchiveError> {
+        if depth > MAX_DEPTH {
+            return Err(ArchiveError::TooDeep);
+        }
+        self.budget = self
+            .budget
+            .checked_sub(1)
+            .ok_or(ArchiveError::TooManyValues)?;
+
+        let data = self.data;
+        let invalid = ArchiveError::InvalidSlot { offset: at };
+        let flag = data[at + 1];
+        let a = read_u32(data, at + 4);
+        let b = read_u64(data, at + 8);
+        match data[at] {
+            NULL | UINT | INT | FLOAT | DATE | TIME => Ok(()),
+            BOOL | DATETIME if flag <= 1 => Ok(()),
+            UINT128 | INT128 => self.region(a, Some(16), 8).map(|_| ()),
+            STR => self.str(a, self.len(b, at)?),
+            BYTES => self.region(a, Some(self.len(b, at)?), 1).map(|_| ()),
+            ARRAY => {
+                let len = self.len(b, at)?;
+                let start = self.region(a, len.checked_mul(SLOT_LEN), 8)?;
+                for idx in 0..len {
+                    self.slot(start + idx * SLOT_LEN, depth + 1)?;
+                }
+                Ok(())
+            }
+            OBJECT => {
+                let len = self.len(b, at)?;
+                let size = len.checked_mul(OBJECT_ENTRY_LEN + 8);
+                let start = self.region(a, size, 8)?;
+                self.hash_table(start + len * OBJECT_ENTRY_LEN, len)?;
+                for idx in 0..len {
+                    let entry = start + idx * OBJECT_ENTRY_LEN;
+                    self.str(read_u32(data, entry), read_u32(data, entry + 4) as usize)?;
+                    self.slot(entry + 8, depth + 1)?;
+                }
+                Ok(())
+            }
+            MAP => {
+                let len = self.len(b, at)?;
+                let start = self.region(a, len.checked_mul(MAP_ENTRY_LEN + 8), 8)?;
+                self.hash_table(start + len * MAP_ENTRY_LEN, len)?;
+                for idx in 0..len {
+                    let entry = start + idx * MAP_ENTRY_LEN;
+                    self.slot(entry, depth + 1)?;
+                    self.slot(entry + SLOT_LEN, depth + 1)?;
+                }
+                Ok(())
+            }
+            _ => Err(invalid),
+        }
+    }
+}
+
+/// A value read in place from a binary archive.
+///
+/// This has the same accessors as [`Value`], but reads from a `&[u8]`
+/// holding an archive, for configs that are loaded at runtime rather than
+/// compiled in. The whole archive is validated once by
+/// [`from_bytes`](Self::from_bytes), after which no accessor can fail on
+/// bad data.
+///
+/// ```
+/// # let data = &[
+/// #     b'C', b'C', b'F', b'G', b'A', b'R', b'C', b'H', 1, 0, 0, 0, 0, 0, 0, 0,
+/// #     2, 0, 0, 0, 0, 0, 0, 0, 42, 0, 0, 0, 0, 0, 0, 0,
+/// # ];
+/// use const_config::ArchivedValue;
+///
+/// // let data = std::fs::read("config.bin").unwrap();
+/// let config = ArchivedValue::from_bytes(data).unwrap();
+/// assert_eq!(config.as_u8(), 42);
+/// ```
+#[derive(Clone, Copy)]
+pub struct ArchivedValue<'a> {
+    data: &'a [u8],
+    at: usize,
+}
+
+/// An array read in place from a binary archive.
+#[derive(Clone, Copy)]
+pub struct ArchivedArray<'a> {
+    data: &'a [u8],
+    at: usize,
+    len: usize,
+}
+
+/// An object read in place from a binary archive.
+#[derive(Clone, Copy)]
+pub struct ArchivedObject<'a> {
+    data: &'a [u8],
+    at: usize,
+    len: usize,
+}
+
+/// A map read in place from a binary archive.
+#[derive(Clone, Copy)]
+pub struct ArchivedMap<'a> {
+    data: &'a [u8],
+    at: usize,
+    len: usize,
+}
+
+impl<'a> ArchivedValue<'a> {
+    /// Validates an archive and returns its root value.
+    ///
+    /// The data does not need to be aligned in memory.
+    pub fn from_bytes(data: &'a [u8]) -> Result<Self, ArchiveError> {
+        if data.len() < HEADER_LEN + SLOT_LEN || !data.starts_with(MAGIC) {
+            return Err(ArchiveError::BadMagic);
+        }
+        let version = read_u32(data, 8);
+        if version != VERSION {
+            return Err(ArchiveError::UnsupportedVersion(version));
+        }
+        let mut validator = Validator {
+            data,
+            budget: data.len() / SLOT_LEN,
+        };
+        validator.slot(HEADER_LEN, 0)?;
+        Ok(Self {
+            data,
+            at: HEADER_LEN,
+        })
+    }
+
+    fn tag(&self) -> u8 {
+        self.data[self.at]
+    }
+
+    fn a(&self) -> usize {
+        read_u32(self.data, self.at + 4) as usize
+    }
+
+    fn b(&self) -> u64 {
+        read_u64(self.data, self.at + 8)
+    }
+
+    fn bytes(&self) -> &'a [u8] {
+        let start = self.a();
+        &self.data[start..start + self.b() as usize]
+    }
+
+    /// The value as a [`Value`], if it is neither an array, an object nor a
+    /// map.
+    fn scalar(&self) -> Option<Value<'a>> {
+        let data = self.data;
+        Some(match self.tag() {
+            NULL => Value::Null,
+            BOOL => Value::Bool(data[self.at + 1] != 0),
+            UINT => Value::Number(Number::UInt(self.b() as u128)),
+            INT => Value::Number(Number::Int(self.b() as i64 as i128)),
+            UINT128 => Value::Number(Number::UInt(read_u128(data, self.a()))),
+            INT128 => Value::Number(Number::Int(read_u128(data, self.a()) as i128)),
+            FLOAT => Value::Number(Number::Float(f64::from_bits(self.b()))),
+            DATE => Value::Date(unpack_date(self.a() as u32)),
+            TIME => Value::Time(Time {
+                hour: self.a() as u8,
+                minute: (self.a() >> 8) as u8,
+                second: (self.a() >> 16) as u8,
+                nanosecond: self.b() as u32,
+            }),
+            DATETIME => Value::DateTime(DateTime {
+                date: unpack_date(self.a() as u32),
+                time: unpack_time(self.b()),
+                offset: (data[self.at + 1] != 0)
+                    .then(|| i16::from_le_bytes([data[self.at + 2], data[self.at + 3]])),
+            }),
+            // Validated as UTF-8.
+            STR => Value::Str(core::str::from_utf8(self.bytes()).unwrap()),
+            BYTES => Value::Bytes(self.bytes()),
+            _ => return None,
+        })
+    }
+
+    fn collection(&self) -> (usize, usize) {
+        (self.a(), self.b() as usize)
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.tag() == NULL
+    }
+
+    pub fn is_bool(&self) -> bool {
+        self.tag() == BOOL
+    }
+
+    pub fn is_number(&self) -> bool {
+        matches!(self.tag(), UINT | INT | UINT128 | INT128 | FLOAT)
+    }
+
+    pub fn is_date(&self) -> bool {
+        self.tag() == DATE
+    }
+
+    pub fn is_time(&self) -> bool {
+        self.tag() == TIME
+    }
+
+    pub fn is_datetime(&self) -> bool {
+        self.tag() == DATETIME
+    }
+
+    pub fn is_str(&self) -> bool {
+        self.tag() == STR
+    }
+
+    pub fn is_bytes(&self) -> bool {
+        self.tag() == BYTES
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.tag() == ARRAY
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.tag() == OBJECT
+    }
+
+    pub fn is_map(&self) -> bool {
+        self.tag() == MAP
+    }
+
+    pub fn try_as_unit(&self) -> Option<()> {
+        self.scalar()?.try_as_unit()
+    }
+
+    pub fn try_as_bool(&self) -> Option<bool> {
+        self.scalar()?.try_as_bool()
+    }
+
+    pub fn try_as_number(&self) -> Option<Number> {
+        self.scalar()?.try_as_number()
+    }
+
+    pub fn try_as_u8(&self) -> Option<u8> {
+        self.scalar()?.try_as_u8()
+    }
+
+    pub fn try_as_u16(&self) -> Option<u16> {
+        self.scalar()?.try_as_u16()
+    }
+
+    pub fn try_as_u32(&self) -> Option<u32> {
+        self.scalar()?.try_as_u32()
+    }
+
+    pub fn try_as_u64(&self) -> Option<u64> {
+        self.scalar()?.try_as_u64()
+    }
+
+    pub fn try_as_u128(&self) -> Option<u128> {
+        self.scalar()?.try_as_u128()
+    }
+
+    pub fn try_as_i8(&self) -> Option<i8> {
+        self.scalar()?.try_as_i8()
+    }
+
+    pub fn try_as_i16(&self) -> Option<i16> {
+        self.scalar()?.try_as_i16()
+    }
+
+    pub fn try_as_i32(&self) -> Option<i32> {
+        self.scalar()?.try_as_i32()
+    }
+
+    pub fn try_as_i64(&self) -> Option<i64> {
+        self.scalar()?.try_as_i64()
+    }
+
+    pub fn try_as_i128(&self) -> Option<i128> {
+        self.scalar()?.try_as_i128()
+    }
+
+    pub fn try_as_f32(&self) -> Option<f32> {
+        self.scalar()?.try_as_f32()
+    }
+
+    pub fn try_as_f64(&self) -> Option<f64> {
+        self.scalar()?.try_as_f64()
+    }
+
+    pub fn try_as_char(&self) -> Option<char> {
+        self.scalar()?.try_as_char()
+    }
+
+    pub fn try_as_date(&self) -> Option<Date> {
+        self.scalar()?.try_as_date()
+    }
+
+    pub fn try_as_time(&self) -> Option<Time> {
+        self.scalar()?.try_as_time()
+    }
+
+    pub fn try_as_datetime(&self) -> Option<DateTime> {
+        self.scalar()?.try_as_datetime()
+    }
+
+    pub fn try_as_str(&self) -> Option<&'a str> {
+        self.scalar()?.try_as_str()
+    }
+
+    pub fn try_as_bytes(&self) -> Option<&'a [u8]> {
+        self.scalar()?.try_as_bytes()
+    }
+
+    pub fn try_as_array(&self) -> Option<ArchivedArray<'a>> {
+        let (at, len) = self.collection();
+        (self.tag() == ARRAY).then_some(ArchivedArray {
+            data: self.data,
+            at,
+            len,
+        })
+    }
+
+    pub fn try_as_object(&self) -> Option<ArchivedObject<'a>> {
+        let (at, len) = self.collection();
+        (self.tag() == OBJECT).then_some(ArchivedObject {
+            data: self.data,
+            at,
+            len,
+        })
+    }
+
+    pub fn try_as_map(&self) -> Option<ArchivedMap<'a>> {
+        let (at, len) = self.collection();
+        (self.tag() == MAP).then_some(ArchivedMap {
+            data: self.data,
+            at,
+            len,
+        })
+    }
+
+    pub fn as_unit(&self) {
+        self.try_as_unit().unwrap()
+    }
+
+    pub fn as_bool(&self) -> bool {
+        self.try_as_bool().unwrap()
+    }
+
+    pub fn as_number(&self) -> Number {
+        self.try_as_number().unwrap()
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        self.try_as_u8().unwrap()
+    }
+
+    pub fn as_u16(&self) -> u16 {
+        self.try_as_u16().unwrap()
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.try_as_u32().unwrap()
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.try_as_u64().unwrap()
+    }
+
+    pub fn as_u128(&self) -> u128 {
+        self.try_as_u128().unwrap()
+    }
+
+    pub fn as_i8(&self) -> i8 {
+        self.try_as_i8().unwrap()
+    }
+
+    pub fn as_i16(&self) -> i16 {
+        self.try_as_i16().unwrap()
+    }
+
+    pub fn as_i32(&self) -> i32 {
+        self.try_as_i32().unwrap()
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.try_as_i64().unwrap()
+    }
+
+    pub fn as_i128(&self) -> i128 {
+        self.try_as_i128().unwrap()
+    }
+
+    pub fn as_f32(&self) -> f32 {
+        self.try_as_f32().unwrap()
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.try_as_f64().unwrap()
+    }
+
+    pub fn as_char(&self) -> char {
+        self.try_as_char().unwrap()
+    }
+
+    pub fn as_date(&self) -> Date {
+        self.try_as_date().unwrap()
+    }
+
+    pub fn as_time(&self) -> Time {
+        self.try_as_time().unwrap()
+    }
+
+    pub fn as_datetime(&self) -> DateTime {
+        self.try_as_datetime().unwrap()
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.try_as_str().unwrap()
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.try_as_bytes().unwrap()
+    }
+
+    pub fn as_array(&self) -> ArchivedArray<'a> {
+        self.try_as_array().unwrap()
+    }
+
+    pub fn as_object(&self) -> ArchivedObject<'a> {
+        self.try_as_object().unwrap()
+    }
+
+    pub fn as_map(&self) -> ArchivedMap<'a> {
+        self.try_as_map().unwrap()
+    }
+
+    /// Whether this is the same map key as `key`, see [`Map`].
+    fn key_eq(&self, key: &Value) -> bool {
+        if let Some(value) = self.scalar() {
+            return Map::key_eq(&value, key);
+        }
+        match (self.tag(), key) {
+            (ARRAY, Value::Array(array)) => {
+                let lhs = self.as_array();
+                lhs.len() == array.len() && lhs.iter().zip(*array).all(|(l, r)| l.key_eq(r))
+            }
+            (OBJECT, Value::Object(obj)) => {
+                let lhs = self.as_object();
+                lhs.len() == obj.len()
+                    && obj
+                        .entries()
+                        .iter()
+                        .all(|(key, value)| lhs.try_get(key).is_some_and(|lhs| lhs.key_eq(value)))
+            }
+            (MAP, Value::Map(map)) => {
+                let lhs = self.as_map();
+                lhs.len() == map.len()
+                    && map
+                        .entries()
+                        .iter()
+                        .all(|(key, value)| lhs.try_get(key).is_some_and(|lhs| lhs.key_eq(value)))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for ArchivedValue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(value) = self.scalar() {
+            return fmt::Debug::fmt(&value, f);
+        }
+        match self.tag() {
+            ARRAY => f.debug_list().entries(self.as_array().iter()).finish(),
+            OBJECT => f.debug_map().entries(self.as_object().iter()).finish(),
+            _ => f.debug_map().entries(self.as_map().iter()).finish(),
+        }
+    }
+}
+
+impl<'a> ArchivedArray<'a> {
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn get(&self, idx: usize) -> Option<ArchivedValue<'a>> {
+        (idx < self.len).then(|| ArchivedValue {
+            data: self.data,
+            at: self.at + idx * SLOT_LEN,
+        })
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = ArchivedValue<'a>> + use<'a> {
+        let Self { data, at, len } = *self;
+        (0..len).map(move |idx| ArchivedValue {
+            data,
+            at: at + idx * SLOT_LEN,
+        })
+    }
+}
+
+impl fmt::Debug for ArchivedArray<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> ArchivedObject<'a> {
+    fn entry(&self, idx: usize) -> (&'a str, ArchivedValue<'a>) {
+        let entry = self.at + idx * OBJECT_ENTRY_LEN;
+        let start = read_u32(self.data, entry) as usize;
+        let len = read_u32(self.data, entry + 4) as usize;
+        // Validated as UTF-8.
+        let key = core::str::from_utf8(&self.data[start..start + len]).unwrap();
+        (
+            key,
+            ArchivedValue {
+                data: self.data,
+                at: entry + 8,
+            },
+        )
+    }
+
+    fn phf(&self, table: usize, idx: usize) -> u32 {
+        let start = self.at + self.len * OBJECT_ENTRY_LEN + table * self.len * 4;
+        read_u32(self.data, start + idx * 4)
+    }
+
+    pub fn try_get(&self, key: &str) -> Option<ArchivedValue<'a>> {
+        if self.len == 0 {
+            return None;
+        }
+        let i = Object::hash(0, key) % self.len;
+        let i = Object::hash(self.phf(0, i), key) % self.len;
+        let (name, value) = self.entry(self.phf(1, i) as usize);
+        (name == key).then_some(value)
+    }
+
+    pub fn get(&self, key: &str) -> ArchivedValue<'a> {
+        self.try_get(key).unwrap()
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.try_get(key).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&'a str, ArchivedValue<'a>)> + use<'a> {
+        let obj = *self;
+        (0..self.len).map(move |idx| obj.entry(idx))
+    }
+}
+
+impl fmt::Debug for ArchivedObject<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+impl<'a> ArchivedMap<'a> {
+    fn entry(&self, idx: usize) -> (ArchivedValue<'a>, ArchivedValue<'a>) {
+        let entry = self.at + idx * MAP_ENTRY_LEN;
+        (
+            ArchivedValue {
+                data: self.data,
+                at: entry,
+            },
+            ArchivedValue {
+                data: self.data,
+                at: entry + SLOT_LEN,
+            },
+        )
+    }
+
+    fn phf(&self, table: usize, idx: usize) -> u32 {
+        let start = self.at + self.len * MAP_ENTRY_LEN + table * self.len * 4;
+        read_u32(self.data, start + idx * 4)
+    }
+
+    pub fn try_get(&self, key: &Value) -> Option<ArchivedValue<'a>> {
+        if self.len == 0 {
+            return None;
+        }
+        let i = Map::hash(0, key) as usize % self.len;
+        let i = Map::hash(self.phf(0, i), key) as usize % self.len;
+        let (entry, value) = self.entry(self.phf(1, i) as usize);
+        entry.key_eq(key).then_some(value)
+    }
+
+    pub fn get(&self, key: &Value) -> ArchivedValue<'a> {
+        self.try_get(key).unwrap()
+    }
+
+    pub fn contains(&self, key: &Value) -> bool {
+        self.try_get(key).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn iter(
+        &self,
+    ) -> impl ExactSizeIterator<Item = (ArchivedValue<'a>, ArchivedValue<'a>)> + use<'a> {
+        let map = *self;
+        (0..self.len).map(move |idx| map.entry(idx))
+    }
+}
+
+impl fmt::Debug for ArchivedMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
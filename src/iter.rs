@@ -0,0 +1,252 @@
+use crate::{Map, Object, Value};
+
+/// Iterates over the elements of an array, see [`Value::iter`].
+///
+/// Besides being an [`Iterator`], a cursor can be advanced with its `const`
+/// [`next`](Self::next), so it can be used in `const` contexts:
+/// ```
+/// use const_config::Value;
+///
+/// # const PORTS: Value<'static> = Value::Array(&[
+/// #     Value::Number(const_config::Number::UInt(80)),
+/// #     Value::Number(const_config::Number::UInt(443)),
+/// # ]);
+/// // const PORTS: Value<'static> = from_json!("[80, 443]");
+/// const SUM: u32 = {
+///     let mut ports = PORTS.iter();
+///     let mut sum = 0;
+///     while let Some(port) = ports.next() {
+///         sum += port.as_u32();
+///     }
+///     sum
+/// };
+/// assert_eq!(SUM, 523);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ArrayCursor<'a> {
+    elems: &'a [Value<'a>],
+    idx: usize,
+}
+
+/// Iterates over the entries of an [`Object`], see [`Object::iter`].
+///
+/// Like [`ArrayCursor`], this can be advanced in `const` contexts.
+#[derive(Debug, Clone, Copy)]
+pub struct ObjectCursor<'a> {
+    entries: &'a [(&'a str, Value<'a>)],
+    idx: usize,
+}
+
+/// Iterates over the entries of a [`Map`], see [`Map::iter`].
+///
+/// Like [`ArrayCursor`], this can be advanced in `const` contexts.
+#[derive(Debug, Clone, Copy)]
+pub struct MapCursor<'a> {
+    entries: &'a [(Value<'a>, Value<'a>)],
+    idx: usize,
+}
+
+impl<'a> ArrayCursor<'a> {
+    /// Returns the next element and advances past it.
+    pub const fn next(&mut self) -> Option<&'a Value<'a>> {
+        if self.idx < self.elems.len() {
+            self.idx += 1;
+            Some(&self.elems[self.idx - 1])
+        } else {
+            None
+        }
+    }
+
+    /// The index of the next element.
+    pub const fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// The number of elements left.
+    pub const fn remaining(&self) -> usize {
+        self.elems.len() - self.idx
+    }
+
+    pub const fn is_done(&self) -> bool {
+        self.idx == self.elems.len()
+    }
+}
+
+impl<'a> ObjectCursor<'a> {
+    /// Returns the next entry and advances past it.
+    pub const fn next(&mut self) -> Option<(&'a str, &'a Value<'a>)> {
+        if self.idx < self.entries.len() {
+            self.idx += 1;
+            let entry = &self.entries[self.idx - 1];
+            Some((entry.0, &entry.1))
+        } else {
+            None
+        }
+    }
+
+    /// The index of the next entry.
+    pub const fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// The number of entries left.
+    pub const fn remaining(&self) -> usize {
+        self.entries.len() - self.idx
+    }
+
+    pub const fn is_done(&self) -> bool {
+        self.idx == self.entries.len()
+    }
+}
+
+impl<'a> MapCursor<'a> {
+    /// Returns the next entry and advances past it.
+    pub const fn next(&mut self) -> Option<(&'a Value<'a>, &'a Value<'a>)> {
+        if self.idx < self.entries.len() {
+            self.idx += 1;
+            let entry = &self.entries[self.idx - 1];
+            Some((&entry.0, &entry.1))
+        } else {
+            None
+        }
+    }
+
+    /// The index of the next entry.
+    pub const fn index(&self) -> usize {
+        self.idx
+    }
+
+    /// The number of entries left.
+    pub const fn remaining(&self) -> usize {
+        self.entries.len() - self.idx
+    }
+
+    pub const fn is_done(&self) -> bool {
+        self.idx == self.entries.len()
+    }
+}
+
+impl<'a> Iterator for ArrayCursor<'a> {
+    type Item = &'a Value<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        ArrayCursor::next(self)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl ExactSizeIterator for ArrayCursor<'_> {}
+
+impl<'a> Iterator for ObjectCursor<'a> {
+    type Item = (&'a str, &'a Value<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        ObjectCursor::next(self)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl ExactSizeIterator for ObjectCursor<'_> {}
+
+impl<'a> Iterator for MapCursor<'a> {
+    type Item = (&'a Value<'a>, &'a Value<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        MapCursor::next(self)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl ExactSizeIterator for MapCursor<'_> {}
+
+impl<'a> Value<'a> {
+    /// Iterates over the elements of an array.
+    ///
+    /// # Panics
+    /// Panics if the value is not an array.
+    pub const fn iter(&self) -> ArrayCursor<'a> {
+        ArrayCursor {
+            elems: self.as_array(),
+            idx: 0,
+        }
+    }
+}
+
+impl<'a> Object<'a> {
+    pub const fn iter(&self) -> ObjectCursor<'a> {
+        ObjectCursor {
+            entries: self.entries,
+            idx: 0,
+        }
+    }
+
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &'a str> + use<'a> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &'a Value<'a>> + use<'a> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<'a> Map<'a> {
+    pub const fn iter(&self) -> MapCursor<'a> {
+        MapCursor {
+            entries: self.entries,
+            idx: 0,
+        }
+    }
+
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &'a Value<'a>> + use<'a> {
+        self.iter().map(|(key, _)| key)
+    }
+
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &'a Value<'a>> + use<'a> {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<'a> IntoIterator for Object<'a> {
+    type Item = (&'a str, &'a Value<'a>);
+    type IntoIter = ObjectCursor<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Object<'a> {
+    type Item = (&'a str, &'a Value<'a>);
+    type IntoIter = ObjectCursor<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for Map<'a> {
+    type Item = (&'a Value<'a>, &'a Value<'a>);
+    type IntoIter = MapCursor<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<'a> IntoIterator for &Map<'a> {
+    type Item = (&'a Value<'a>, &'a Value<'a>);
+    type IntoIter = MapCursor<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
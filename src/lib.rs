@@ -3,8 +3,25 @@
 #[cfg(feature = "serde")]
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::fmt::{self, Display};
 
+pub mod archive;
+mod iter;
+#[cfg(feature = "std")]
+mod overlay;
+mod path;
+mod text;
+
+pub use archive::{ArchiveError, ArchivedArray, ArchivedMap, ArchivedObject, ArchivedValue};
+pub use iter::{ArrayCursor, MapCursor, ObjectCursor};
+#[cfg(feature = "std")]
+pub use overlay::{Overlay, OverlayBuilder, OverlayError, OverlayValue};
+#[doc(hidden)]
+pub use path::__private;
+
 #[cfg(feature = "serde")]
 use alloc::{format, string::ToString};
 
@@ -62,6 +79,73 @@ pub use const_config_macros::from_toml;
 ///
 /// fn main() {}
 /// ```
+///
+/// # Profiles
+/// Every `from_*` and `include_*` macro accepts a `profile` option that
+/// selects a named table under the top-level `profile` key. The selected
+/// profile is deep-merged on top of the rest of the config, and the
+/// `profile` table itself, including every other profile, is left out of
+/// the generated constant. The profile name can be a string literal, or
+/// read from the build environment with `env!` or `option_env!`. With
+/// `profile = None` or an unset `option_env!`, no profile is applied, but
+/// the `profile` table is still left out. Without the option, the config is
+/// embedded as is. Naming a profile that does not exist is a compile error.
+///
+/// Macros cannot see the cargo features or `cfg` flags of the crate they
+/// expand in, so `profile` does not accept `cfg!`. To select a profile by
+/// feature or `cfg`, define one constant per profile behind `#[cfg]`:
+///
+/// ```ignore
+/// // config.toml:
+/// //
+/// // log-level = "info"
+/// //
+/// // [profile.dev]
+/// // log-level = "debug"
+///
+/// use const_config::{Value, include_toml};
+///
+/// const CONFIG: Value<'static> = include_toml!("config.toml", profile = env!("APP_PROFILE"));
+///
+/// #[cfg(feature = "dev")]
+/// const DEV_CONFIG: Value<'static> = include_toml!("config.toml", profile = "dev");
+/// #[cfg(not(feature = "dev"))]
+/// const DEV_CONFIG: Value<'static> = include_toml!("config.toml", profile = None);
+/// ```
+///
+/// # Environment Variables
+/// With `interpolate = true`, placeholders in string values are expanded
+/// from the build environment. `${VAR}` expands to the value of `VAR`, or
+/// nothing if it is unset, `${VAR:-default}` falls back to `default` if
+/// `VAR` is unset or empty, and `${VAR:?message}` is a compile error with
+/// `message` if `VAR` is unset or empty. `$${` is a literal `${`. The crate
+/// is rebuilt whenever one of the variables changes.
+///
+/// ```ignore
+/// // config.toml:
+/// //
+/// // build = "${BUILD_ID:?BUILD_ID must be set}"
+/// // data-dir = "${DATA_DIR:-/var/lib/app}"
+///
+/// use const_config::{Value, include_toml};
+///
+/// const CONFIG: Value<'static> = include_toml!("config.toml", interpolate = true);
+/// ```
+///
+/// # Schemas
+/// With `schema = "<path>"`, the config is validated against a JSON Schema
+/// after profiles and environment variables are applied, and every value
+/// that does not match is listed in a compile error. The path is relative
+/// to the invoking source file, and the schema can be written in any
+/// enabled format, determined by its extension. Draft 2020-12 is supported
+/// except for `unevaluatedItems`, `unevaluatedProperties`, `$dynamicRef`
+/// and references to other files.
+///
+/// ```ignore
+/// use const_config::{Value, include_toml};
+///
+/// const CONFIG: Value<'static> = include_toml!("config.toml", schema = "config.schema.json");
+/// ```
 #[cfg(feature = "toml")]
 pub use const_config_macros::include_toml;
 
@@ -163,6 +247,26 @@ pub use const_config_macros::from_yaml;
 ///
 /// fn main() {}
 /// ```
+///
+/// # Timestamps
+/// Only TOML has dates and times of its own, so in other formats they end
+/// up as strings. With `timestamps = true`, strings holding an RFC 3339
+/// date, time or date-time, such as `2024-01-02`, `10:30:00` or
+/// `2024-01-02T10:30:00Z`, become a [`Value::Date`], [`Value::Time`] or
+/// [`Value::DateTime`] instead. YAML timestamps such as
+/// `2024-01-02 10:30:00 -5` are recognized too. The option is accepted by
+/// every `from_*` and `include_*` macro.
+///
+/// ```ignore
+/// // config.yml:
+/// //
+/// // released: 2024-01-02
+///
+/// use const_config::{Value, include_yaml};
+///
+/// const CONFIG: Value<'static> = include_yaml!("config.yml", timestamps = true);
+/// const RELEASED: const_config::Date = CONFIG.as_object().get("released").as_date();
+/// ```
 #[cfg(feature = "yaml")]
 pub use const_config_macros::include_yaml;
 
@@ -226,6 +330,242 @@ pub use const_config_macros::from_flexbuffers;
 #[cfg(feature = "flexbuffers")]
 pub use const_config_macros::include_flexbuffers;
 
+#[cfg(feature = "ini")]
+pub use const_config_macros::from_ini;
+
+#[cfg(feature = "ini")]
+pub use const_config_macros::include_ini;
+
+#[cfg(feature = "properties")]
+pub use const_config_macros::from_properties;
+
+#[cfg(feature = "properties")]
+pub use const_config_macros::include_properties;
+
+#[cfg(feature = "dotenv")]
+pub use const_config_macros::from_dotenv;
+
+#[cfg(feature = "dotenv")]
+pub use const_config_macros::include_dotenv;
+
+#[cfg(feature = "xml")]
+pub use const_config_macros::from_xml;
+
+#[cfg(feature = "xml")]
+pub use const_config_macros::include_xml;
+
+#[cfg(feature = "kdl")]
+pub use const_config_macros::from_kdl;
+
+#[cfg(feature = "kdl")]
+pub use const_config_macros::include_kdl;
+
+#[cfg(feature = "jsonl")]
+pub use const_config_macros::from_jsonl;
+
+#[cfg(feature = "jsonl")]
+pub use const_config_macros::include_jsonl;
+
+/// Build config from a file in any enabled format.
+///
+/// [`include_config`] works like the format specific `include_*` macros, such
+/// as [`include_toml`], but picks the format from the extension of the file,
+/// such as `.toml` or `.yml`. Files in a binary format with an unknown or
+/// missing extension are recognized from their contents, as long as their
+/// root is a map. The feature for the format must be enabled, and the build
+/// fails with an error naming the missing feature otherwise.
+///
+/// The same options as for the format specific macros are accepted.
+///
+/// # Example
+/// ```ignore
+/// use const_config::{Value, include_config};
+///
+/// const CONFIG: Value<'static> = include_config!("config/app.yml");
+/// const ASSETS: Value<'static> = include_config!("assets/index.bin");
+/// ```
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "yaml",
+    feature = "ron",
+    feature = "json5",
+    feature = "s-expr",
+    feature = "hjson",
+    feature = "csv",
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "pickle",
+    feature = "bson",
+    feature = "flexbuffers",
+    feature = "ini",
+    feature = "properties",
+    feature = "dotenv",
+    feature = "xml",
+    feature = "kdl",
+    feature = "jsonl",
+))]
+pub use const_config_macros::include_config;
+
+/// Build config by layering several config files.
+///
+/// [`include_layered`] reads each file in order, possibly in different
+/// formats, and deep-merges every file on top of the ones before it. Objects
+/// and maps are merged key by key, while any other value simply overrides
+/// the earlier one. The format of each file is determined from its
+/// extension, such as `.toml` or `.yml`, and the feature for that format
+/// must be enabled. Paths are relative to the current source file, like
+/// with [`include_str`].
+///
+/// By default, an array replaces the earlier array. Passing
+/// `arrays = "append"` appends its elements instead. The `schema` and
+/// `timestamps` options work as for [`include_toml`] and [`include_yaml`],
+/// and apply to the merged config, as does `coerce`. With
+/// `expand_keys = true`, dotted keys of every file are turned into nested
+/// objects before merging.
+///
+/// # Example
+/// ```ignore
+/// use const_config::{Value, include_layered};
+///
+/// const CONFIG: Value<'static> = include_layered!(
+///     "config/base.toml",
+///     "config/prod.toml",
+///     "config/site-local.json",
+///     arrays = "append",
+/// );
+/// ```
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "yaml",
+    feature = "ron",
+    feature = "json5",
+    feature = "s-expr",
+    feature = "hjson",
+    feature = "csv",
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "pickle",
+    feature = "bson",
+    feature = "flexbuffers",
+    feature = "ini",
+    feature = "properties",
+    feature = "dotenv",
+    feature = "xml",
+    feature = "kdl",
+    feature = "jsonl",
+))]
+pub use const_config_macros::include_layered;
+
+/// Build a typed config struct at compile time.
+///
+/// A struct deriving [`ConstConfig`] that names a config source gets an
+/// associated `CONFIG` constant, built as a plain struct literal while the
+/// macro expands. A source is given as `include_<format> = "path"` or
+/// `from_<format> = "text"`, mirroring the `include_*` and `from_*`
+/// macros. Keys missing from the config, or values that do not fit their
+/// field, are reported as compile errors on the offending field.
+///
+/// Supported field types are `bool`, the integer and float primitives,
+/// `char`, `&'static str`, `&'static [T]`, `[T; N]`, `Option<T>`,
+/// [`Value<'static>`](Value), [`Date`], [`Time`], [`DateTime`] and other
+/// structs deriving [`ConstConfig`]. `Option` fields are `None` when the key
+/// is missing or null.
+///
+/// Nested structs are built as part of the struct naming the source, so
+/// they must be defined in the same crate. Errors in their fields name the
+/// full key, e.g. `endpoints[1].port`.
+///
+/// Keys default to the field names. They can be changed with
+/// `#[const_config(rename = "...")]` on a field, or for all fields with
+/// `#[const_config(rename_all = "...")]`, which accepts the same rules as
+/// serde's `rename_all`.
+///
+/// # Example
+/// ```
+/// use const_config::ConstConfig;
+///
+/// #[derive(ConstConfig)]
+/// #[const_config(from_toml = r#"
+/// [general]
+/// production = true
+///
+/// [networking]
+/// default-port = 443
+/// hosts = ["a.example.com", "b.example.com"]
+/// "#)]
+/// struct Config {
+///     general: General,
+///     networking: Networking,
+/// }
+///
+/// #[derive(ConstConfig)]
+/// struct General {
+///     production: bool,
+///     trace_logging: Option<bool>,
+/// }
+///
+/// #[derive(ConstConfig)]
+/// #[const_config(rename_all = "kebab-case")]
+/// struct Networking {
+///     default_port: u16,
+///     hosts: &'static [&'static str],
+/// }
+///
+/// const PORT: u16 = Config::CONFIG.networking.default_port;
+/// assert_eq!(PORT, 443);
+/// assert_eq!(Config::CONFIG.networking.hosts[1], "b.example.com");
+/// assert!(Config::CONFIG.general.production);
+/// assert_eq!(Config::CONFIG.general.trace_logging, None);
+/// ```
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "yaml",
+    feature = "ron",
+    feature = "json5",
+    feature = "s-expr",
+    feature = "hjson",
+    feature = "csv",
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "pickle",
+    feature = "bson",
+    feature = "flexbuffers",
+    feature = "ini",
+    feature = "properties",
+    feature = "dotenv",
+    feature = "xml",
+    feature = "kdl",
+    feature = "jsonl",
+))]
+pub use const_config_macros::ConstConfig;
+
+#[doc(hidden)]
+#[cfg(any(
+    feature = "toml",
+    feature = "json",
+    feature = "yaml",
+    feature = "ron",
+    feature = "json5",
+    feature = "s-expr",
+    feature = "hjson",
+    feature = "csv",
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "pickle",
+    feature = "bson",
+    feature = "flexbuffers",
+    feature = "ini",
+    feature = "properties",
+    feature = "dotenv",
+    feature = "xml",
+    feature = "kdl",
+    feature = "jsonl",
+))]
+pub use const_config_macros::__const_config_nested;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Value<'a> {
     Null,
@@ -241,6 +581,12 @@ pub enum Value<'a> {
     Map(Map<'a>),
 }
 
+/// A number from a config.
+///
+/// Numbers compare by value regardless of their representation, so
+/// `UInt(1)`, `Int(1)` and `Float(1.0)` are all equal. Comparisons with
+/// floats follow IEEE 754: NaN is not equal to any number, itself included,
+/// and is unordered with respect to every number.
 #[derive(Debug, Clone, Copy)]
 pub enum Number {
     UInt(u128),
@@ -277,6 +623,11 @@ pub struct Object<'a> {
     phf_values: &'a [u32],
 }
 
+/// A map with keys of any type.
+///
+/// Keys are looked up by value like [`Number`]s compare, except that all
+/// NaNs are the same key, so a NaN key can be found again. A map holds no
+/// repeated keys; when a config repeats a key, the last value wins.
 #[derive(Clone, Copy)]
 pub struct Map<'a> {
     entries: &'a [(Value<'a>, Value<'a>)],
@@ -377,7 +728,7 @@ impl<'a> Object<'a> {
         self.phf_values[i] as usize
     }
 
-    pub const fn try_get(&self, key: &str) -> Option<&'a Value> {
+    pub const fn try_get(&self, key: &str) -> Option<&'a Value<'a>> {
         if self.entries.is_empty() {
             return None;
         }
@@ -390,7 +741,7 @@ impl<'a> Object<'a> {
         }
     }
 
-    pub const fn get(&self, key: &str) -> &'a Value {
+    pub const fn get(&self, key: &str) -> &'a Value<'a> {
         self.try_get(key).unwrap()
     }
 
@@ -402,7 +753,7 @@ impl<'a> Object<'a> {
         self.entries.len()
     }
 
-    pub const fn entries(&self) -> &'a [(&'a str, Value)] {
+    pub const fn entries(&self) -> &'a [(&'a str, Value<'a>)] {
         self.entries
     }
 
@@ -478,6 +829,8 @@ impl<'a> Map<'a> {
                 ])
             }
             Value::Number(Number::Float(val)) => {
+                // All NaNs are the same key, whatever their sign and payload.
+                let val = if val.is_nan() { &f64::NAN } else { val };
                 if *val < 0.0f64 {
                     if ((*val as i128) as f64) == *val {
                         let val = u128::from_ne_bytes((*val as i128).to_ne_bytes());
@@ -582,7 +935,9 @@ impl<'a> Map<'a> {
             (Value::Number(lhs), Value::Number(rhs)) => match (lhs, rhs) {
                 (Number::UInt(lhs), Number::UInt(rhs)) => *lhs == *rhs,
                 (Number::Int(lhs), Number::Int(rhs)) => *lhs == *rhs,
-                (Number::Float(lhs), Number::Float(rhs)) => *lhs == *rhs,
+                (Number::Float(lhs), Number::Float(rhs)) => {
+                    *lhs == *rhs || (lhs.is_nan() && rhs.is_nan())
+                }
                 (Number::UInt(lhs), Number::Int(rhs)) => *rhs >= 0 && *lhs == (*rhs as u128),
                 (Number::Int(lhs), Number::UInt(rhs)) => *lhs >= 0 && (*lhs as u128) == *rhs,
                 (Number::UInt(lhs), Number::Float(rhs)) => {
@@ -621,8 +976,8 @@ impl<'a> Map<'a> {
                         _ => false,
                     }
             }
-            (Value::Str(lhs), Value::Str(rhs)) => str_eq(*lhs, *rhs),
-            (Value::Bytes(lhs), Value::Bytes(rhs)) => bytes_eq(*lhs, *rhs),
+            (Value::Str(lhs), Value::Str(rhs)) => str_eq(lhs, rhs),
+            (Value::Bytes(lhs), Value::Bytes(rhs)) => bytes_eq(lhs, rhs),
             (Value::Array(lhs), Value::Array(rhs)) => {
                 lhs.len() == rhs.len() && {
                     let mut ret = true;
@@ -989,7 +1344,7 @@ impl Number {
     }
 
     pub const fn is_integral(&self) -> bool {
-        return !self.is_float();
+        !self.is_float()
     }
 
     pub const fn is_float(&self) -> bool {
@@ -1288,9 +1643,7 @@ impl Display for DateParseError {
 /* poor man's nom parsers below :D */
 
 fn parse_symb(s: &[u8], symb: u8) -> Option<((), &[u8])> {
-    let Some((b, rem)) = s.split_first() else {
-        return None;
-    };
+    let (b, rem) = s.split_first()?;
     if *b != symb {
         return None;
     }
@@ -1298,9 +1651,7 @@ fn parse_symb(s: &[u8], symb: u8) -> Option<((), &[u8])> {
 }
 
 fn parse_digit(s: &[u8]) -> Option<(u8, &[u8])> {
-    let Some((digit, rem)) = s.split_first() else {
-        return None;
-    };
+    let (digit, rem) = s.split_first()?;
     if *digit < b'0' || *digit > b'9' {
         return None;
     }
@@ -1338,16 +1689,12 @@ fn parse_time(s: &[u8]) -> Option<(Time, &[u8])> {
         let mut s = s;
         let mut nano = 0u32;
         let mut count = 0usize;
-        loop {
-            if let Some((digit, rem)) = parse_digit(s) {
-                count += 1;
-                if count <= 9 {
-                    nano = (nano * 10) + (digit as u32);
-                }
-                s = rem;
-            } else {
-                break;
+        while let Some((digit, rem)) = parse_digit(s) {
+            count += 1;
+            if count <= 9 {
+                nano = (nano * 10) + (digit as u32);
             }
+            s = rem;
         }
         while count < 9 {
             nano *= 10;
@@ -1369,9 +1716,7 @@ fn parse_time(s: &[u8]) -> Option<(Time, &[u8])> {
 }
 
 fn parse_offset(s: &[u8]) -> Option<(i16, &[u8])> {
-    let Some((symb, s)) = s.split_first() else {
-        return None;
-    };
+    let (symb, s) = s.split_first()?;
     if *symb == b'Z' {
         return Some((0, s));
     }
@@ -1452,9 +1797,9 @@ impl Display for Time {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:02}:{:02}:{:02}", self.hour, self.minute, self.second)?;
         if self.nanosecond != 0 {
-            if self.nanosecond % 1_000_000 == 0 {
+            if self.nanosecond.is_multiple_of(1_000_000) {
                 write!(f, ".{:03}", self.nanosecond / 1_000_000)?;
-            } else if self.nanosecond % 1_000 == 0 {
+            } else if self.nanosecond.is_multiple_of(1_000) {
                 write!(f, ".{:06}", self.nanosecond / 1_000)?;
             } else {
                 write!(f, ".{:09}", self.nanosecond)?;
@@ -1493,7 +1838,7 @@ impl Time {
             self.hour as u32,
             self.minute as u32,
             self.second as u32,
-            self.nanosecond as u32,
+            self.nanosecond,
         )
         .expect("Invalid time")
     }
@@ -1802,7 +2147,7 @@ impl<'a> Value<'a> {
 
     pub const fn try_as_unit(&self) -> Option<()> {
         match self {
-            Value::Array(value) if value.is_empty() => Some(()),
+            Value::Array([]) => Some(()),
             Value::Object(value) if value.is_empty() => Some(()),
             _ => None,
         }
@@ -2070,7 +2415,7 @@ impl<'a> Value<'a> {
         }
     }
 
-    pub const fn as_unit(&self) -> () {
+    pub const fn as_unit(&self) {
         self.try_as_unit().unwrap()
     }
 
@@ -2199,9 +2544,9 @@ impl<'a> Value<'a> {
             Self::Date(date) => Self::Date(date.copy()),
             Self::Time(value) => Self::Time(value.copy()),
             Self::DateTime(value) => Self::DateTime(value.copy()),
-            Self::Str(value) => Self::Str(*value),
-            Self::Bytes(value) => Self::Bytes(*value),
-            Self::Array(value) => Self::Array(*value),
+            Self::Str(value) => Self::Str(value),
+            Self::Bytes(value) => Self::Bytes(value),
+            Self::Array(value) => Self::Array(value),
             Self::Object(value) => Self::Object(value.copy()),
             Self::Map(value) => Self::Map(value.copy()),
         }
@@ -2212,7 +2557,7 @@ impl<'a> Value<'a> {
     where
         D: serde::Deserialize<'a>,
     {
-        D::deserialize(Deser(self.clone()))
+        D::deserialize(Deser(*self))
     }
 
     #[cfg(feature = "serde")]
@@ -2280,7 +2625,7 @@ impl<'a> serde::de::SeqAccess<'a> for DeserArray<'a> {
     {
         if let Some((head, tail)) = self.0.split_first() {
             *self = DeserArray(tail);
-            Ok(Some(seed.deserialize(Deser(head.clone()))?))
+            Ok(Some(seed.deserialize(Deser(*head))?))
         } else {
             Ok(None)
         }
@@ -2312,7 +2657,7 @@ impl<'a> serde::de::MapAccess<'a> for DeserObject<'a> {
     {
         if let Some((head, tail)) = self.0.split_first() {
             *self = DeserObject(tail);
-            Ok(seed.deserialize(Deser(head.1.clone()))?)
+            Ok(seed.deserialize(Deser(head.1))?)
         } else {
             Err(InterpretError(format!(
                 "attempt to deserialize object field with no remaining fields to deserialize: {:?}",
@@ -2335,7 +2680,7 @@ impl<'a> serde::de::MapAccess<'a> for DeserMap<'a> {
         K: serde::de::DeserializeSeed<'a>,
     {
         if let Some(entry) = self.0.first() {
-            Ok(Some(seed.deserialize(Deser(entry.0.clone()))?))
+            Ok(Some(seed.deserialize(Deser(entry.0))?))
         } else {
             Ok(None)
         }
@@ -2347,7 +2692,7 @@ impl<'a> serde::de::MapAccess<'a> for DeserMap<'a> {
     {
         if let Some((head, tail)) = self.0.split_first() {
             *self = DeserMap(tail);
-            Ok(seed.deserialize(Deser(head.1.clone()))?)
+            Ok(seed.deserialize(Deser(head.1))?)
         } else {
             Err(InterpretError(format!(
                 "attempt to deserialize map entry value with no remaining entries to deserialize: {:?}",
@@ -2371,13 +2716,12 @@ impl<'a> serde::de::EnumAccess<'a> for DeserEnum<'a> {
         V: serde::de::DeserializeSeed<'a>,
     {
         match self.0 {
-            Value::Str(_) => Ok((seed.deserialize(Deser(self.0.clone()))?, DeserVariant(None))),
-            Value::Array(value) if value.len() == 1 => Ok((
-                seed.deserialize(Deser(value[0].clone()))?,
-                DeserVariant(None),
-            )),
+            Value::Str(_) => Ok((seed.deserialize(Deser(self.0))?, DeserVariant(None))),
+            Value::Array(value) if value.len() == 1 => {
+                Ok((seed.deserialize(Deser(value[0]))?, DeserVariant(None)))
+            }
             Value::Array(value) if value.len() == 2 => Ok((
-                seed.deserialize(Deser(value[0].clone()))?,
+                seed.deserialize(Deser(value[0]))?,
                 DeserVariant(Some(&value[1])),
             )),
             Value::Object(value) if value.len() == 1 => {
@@ -2415,11 +2759,11 @@ impl<'a> serde::de::VariantAccess<'a> for DeserVariant<'a> {
         T: serde::de::DeserializeSeed<'a>,
     {
         if let Some(value) = self.0 {
-            seed.deserialize(Deser(value.clone()))
+            seed.deserialize(Deser(*value))
         } else {
-            Err(InterpretError(format!(
-                "expected data for newtype variant, but have none"
-            )))
+            Err(InterpretError(
+                "expected data for newtype variant, but have none".to_string(),
+            ))
         }
     }
 
@@ -2430,11 +2774,11 @@ impl<'a> serde::de::VariantAccess<'a> for DeserVariant<'a> {
         use serde::Deserializer;
 
         if let Some(value) = self.0 {
-            Deser(value.clone()).deserialize_tuple(len, visitor)
+            Deser(*value).deserialize_tuple(len, visitor)
         } else {
-            Err(InterpretError(format!(
-                "expected data for tuple variant, but have none"
-            )))
+            Err(InterpretError(
+                "expected data for tuple variant, but have none".to_string(),
+            ))
         }
     }
 
@@ -2449,11 +2793,11 @@ impl<'a> serde::de::VariantAccess<'a> for DeserVariant<'a> {
         use serde::Deserializer;
 
         if let Some(value) = self.0 {
-            Deser(value.clone()).deserialize_map(visitor)
+            Deser(*value).deserialize_map(visitor)
         } else {
-            Err(InterpretError(format!(
-                "expected data for struct variant, but have none"
-            )))
+            Err(InterpretError(
+                "expected data for struct variant, but have none".to_string(),
+            ))
         }
     }
 }
@@ -2614,9 +2958,9 @@ impl<'a> serde::Deserializer<'a> for DeserKey<'a> {
                 )))
             }
         } else {
-            Err(InterpretError(format!(
-                "attempt to deserialize empty string as single character"
-            )))
+            Err(InterpretError(
+                "attempt to deserialize empty string as single character".to_string(),
+            ))
         }
     }
 
@@ -3070,7 +3414,7 @@ impl<'a> serde::Deserializer<'a> for Deser<'a> {
         V: serde::de::Visitor<'a>,
     {
         match self.0 {
-            Value::Array(value) if value.is_empty() => visitor.visit_unit(),
+            Value::Array([]) => visitor.visit_unit(),
             Value::Object(value) if value.is_empty() => visitor.visit_unit(),
             _ => Err(InterpretError(format!(
                 "requested deserialization of unit, have {:?}",
@@ -0,0 +1,765 @@
+//! Runtime overrides of a compiled-in config.
+
+use crate::{Date, DateTime, Number, Time, Value};
+use core::fmt::{self, Display};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+/// A compiled-in config with some of its values overridden at runtime by
+/// environment variables, an override file, or command line arguments.
+///
+/// Every string, number, boolean, date and time in the config, other than
+/// those in [`Map`](crate::Map)s, can be overridden by a variable named after its path:
+/// the prefix, followed by the keys and array indices along the path,
+/// upper-cased, with characters other than letters and digits replaced by
+/// `_`, and joined by `__`. With the prefix `APP_`, `networking.default-port`
+/// is overridden by `APP_NETWORKING__DEFAULT_PORT`, and `servers[1].host` by
+/// `APP_SERVERS__1__HOST`.
+///
+/// Keys that only differ in case or punctuation, such as `default-port` and
+/// `default_port`, would be overridden by the same variable, so a config
+/// with such keys cannot be overlaid and [`OverlayBuilder::build`] fails.
+///
+/// Overrides are parsed as the type of the value they override, so
+/// overriding a port with `http` is an error, reported by
+/// [`OverlayBuilder::build`] rather than on lookup. Strings are taken
+/// verbatim, dates and times are parsed like in TOML, and booleans must be
+/// `true` or `false`. The config does not record which integer or float
+/// type a number is read as, so numbers are only checked to be of the same
+/// kind, unsigned, signed or floating point, as the value they override. An
+/// override out of the range of the type it is read as, such as `70000` for
+/// a `u16` port, builds fine, and `try_as_u16` returns `None` for it.
+///
+/// ```
+/// use const_config::{Overlay, Value};
+///
+/// # const CONFIG: Value<'static> = Value::Object(const_config::Object::new(
+/// #     &[("port", Value::Number(const_config::Number::UInt(80)))],
+/// #     &[0],
+/// #     &[0],
+/// # ));
+/// // const CONFIG: Value<'static> = from_toml!("port = 80");
+/// let overlay = Overlay::builder(CONFIG)
+///     .env_prefix("APP_")
+///     .file(".env.local")
+///     .args(["APP_PORT=8080"])
+///     .build()
+///     .unwrap();
+/// assert_eq!(overlay.get("port").as_u16(), 8080);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Overlay {
+    root: Value<'static>,
+    overrides: HashMap<Vec<String>, Override>,
+}
+
+/// Builds an [`Overlay`], see [`Overlay::builder`].
+#[derive(Debug, Clone)]
+pub struct OverlayBuilder {
+    root: Value<'static>,
+    prefix: Option<String>,
+    file: Option<PathBuf>,
+    args: Vec<String>,
+}
+
+/// A value of an [`Overlay`], with the overrides of it and everything in it
+/// applied.
+#[derive(Debug, Clone)]
+pub struct OverlayValue<'a> {
+    overlay: &'a Overlay,
+    path: Vec<String>,
+    value: Value<'static>,
+}
+
+#[derive(Debug, Clone)]
+enum Override {
+    /// An override of anything but a string or bytes, which borrows nothing.
+    Value(Value<'static>),
+    Str(String),
+    Bytes(String),
+}
+
+/// Error produced by [`OverlayBuilder::build`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum OverlayError {
+    /// The override file could not be read.
+    IO {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// A line of the override file is not of the form `NAME=value`.
+    Syntax { path: PathBuf, line: usize },
+    /// An argument is not of the form `NAME=value`.
+    InvalidArg { arg: String },
+    /// An override in the file or the arguments does not name any value
+    /// of the config.
+    UnknownName { name: String },
+    /// An override is not of the type of the value it overrides.
+    Mismatch {
+        name: String,
+        expected: &'static str,
+        value: String,
+    },
+    /// Two values of the config are overridden by the same name.
+    Collision {
+        name: String,
+        first: String,
+        second: String,
+    },
+}
+
+impl Display for OverlayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::IO { path, source } => write!(f, "{}: {source}", path.display()),
+            Self::Syntax { path, line } => {
+                write!(f, "{}:{line}: expected `NAME=value`", path.display())
+            }
+            Self::InvalidArg { arg } => write!(f, "`{arg}`: expected `NAME=value`"),
+            Self::UnknownName { name } => write!(f, "`{name}` does not override any value"),
+            Self::Mismatch {
+                name,
+                expected,
+                value,
+            } => write!(f, "`{name}`: expected {expected}, found `{value}`"),
+            Self::Collision {
+                name,
+                first,
+                second,
+            } => write!(f, "`{name}` names both `{first}` and `{second}`"),
+        }
+    }
+}
+
+impl std::error::Error for OverlayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::IO { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl OverlayBuilder {
+    /// Looks up overrides in environment variables starting with `prefix`.
+    pub fn env_prefix<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Reads overrides from a file of `NAME=value` lines, with names as for
+    /// environment variables, like a `.env` file. Blank lines and lines
+    /// starting with `#` are skipped, and values may be quoted with `"` or
+    /// `'`. A missing file is skipped too.
+    ///
+    /// Environment variables take precedence over the file.
+    pub fn file<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.file = Some(path.as_ref().into());
+        self
+    }
+
+    /// Adds overrides of the form `NAME=value`, such as given on the
+    /// command line, which take precedence over everything else.
+    pub fn args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Reads and checks all overrides.
+    pub fn build(self) -> Result<Overlay, OverlayError> {
+        let mut named = HashMap::new();
+        if let Some(path) = &self.file {
+            match std::fs::read_to_string(path) {
+                Ok(text) => {
+                    for (idx, line) in text.lines().enumerate() {
+                        let line = line.trim();
+                        if line.is_empty() || line.starts_with('#') {
+                            continue;
+                        }
+                        let (name, value) =
+                            split_arg(line).ok_or_else(|| OverlayError::Syntax {
+                                path: path.clone(),
+                                line: idx + 1,
+                            })?;
+                        named.insert(name.to_string(), unquote(value).to_string());
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(source) => {
+                    return Err(OverlayError::IO {
+                        path: path.clone(),
+                        source,
+                    });
+                }
+            }
+        }
+        let mut args = HashMap::new();
+        for arg in &self.args {
+            let Some((name, value)) = split_arg(arg) else {
+                return Err(OverlayError::InvalidArg { arg: arg.clone() });
+            };
+            args.insert(name.to_string(), value.to_string());
+        }
+
+        let mut overlay = Overlay {
+            root: self.root,
+            overrides: HashMap::new(),
+        };
+        let mut used = HashSet::new();
+        let mut leaves = Vec::new();
+        leaves_of(&self.root, &mut Vec::new(), &mut leaves);
+        let mut names = HashMap::new();
+        for (path, _) in &leaves {
+            let name = env_name(self.prefix.as_deref().unwrap_or(""), path);
+            if let Some(first) = names.insert(name.clone(), path) {
+                return Err(OverlayError::Collision {
+                    name,
+                    first: first.join("."),
+                    second: path.join("."),
+                });
+            }
+        }
+        for (path, value) in leaves {
+            let name = env_name(self.prefix.as_deref().unwrap_or(""), &path);
+            let raw = if let Some(raw) = args.get(&name) {
+                raw.clone()
+            } else if let Some(raw) = self.prefix.as_ref().and_then(|_| std::env::var(&name).ok()) {
+                raw
+            } else if let Some(raw) = named.get(&name) {
+                raw.clone()
+            } else {
+                continue;
+            };
+            used.insert(name.clone());
+            overlay.overrides.insert(path, parse(&name, value, raw)?);
+        }
+
+        if let Some(name) = args
+            .keys()
+            .chain(named.keys())
+            .find(|name| !used.contains(*name))
+        {
+            return Err(OverlayError::UnknownName { name: name.clone() });
+        }
+        Ok(overlay)
+    }
+}
+
+fn split_arg(arg: &str) -> Option<(&str, &str)> {
+    let (name, value) = arg.split_once('=')?;
+    let name = name.trim();
+    (!name.is_empty()).then_some((name, value.trim()))
+}
+
+fn unquote(value: &str) -> &str {
+    for quote in ['"', '\''] {
+        if let Some(value) = value
+            .strip_prefix(quote)
+            .and_then(|value| value.strip_suffix(quote))
+        {
+            return value;
+        }
+    }
+    value
+}
+
+/// Collects the path of every value that can be overridden.
+fn leaves_of(
+    value: &Value<'static>,
+    path: &mut Vec<String>,
+    leaves: &mut Vec<(Vec<String>, Value<'static>)>,
+) {
+    match value {
+        Value::Null | Value::Map(_) => {}
+        Value::Array(array) => {
+            for (idx, elem) in array.iter().enumerate() {
+                path.push(idx.to_string());
+                leaves_of(elem, path, leaves);
+                path.pop();
+            }
+        }
+        Value::Object(obj) => {
+            for (key, value) in obj.entries() {
+                path.push(key.to_string());
+                leaves_of(value, path, leaves);
+                path.pop();
+            }
+        }
+        value => leaves.push((path.clone(), *value)),
+    }
+}
+
+fn env_name(prefix: &str, path: &[String]) -> String {
+    let mut name = String::from(prefix);
+    for (idx, segment) in path.iter().enumerate() {
+        if idx != 0 {
+            name.push_str("__");
+        }
+        name.extend(segment.chars().map(|ch| {
+            if ch.is_ascii_alphanumeric() {
+                ch.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        }));
+    }
+    name
+}
+
+/// Parses an override as the type of the value it overrides.
+fn parse(name: &str, value: Value<'static>, raw: String) -> Result<Override, OverlayError> {
+    let (parsed, expected) = match value {
+        Value::Bool(_) => (
+            match raw.as_str() {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            "a boolean",
+        ),
+        Value::Number(Number::UInt(_)) => (
+            raw.parse().ok().map(|val| Value::Number(Number::UInt(val))),
+            "an unsigned integer",
+        ),
+        Value::Number(Number::Int(_)) => (
+            raw.parse().ok().map(|val| Value::Number(Number::Int(val))),
+            "an integer",
+        ),
+        Value::Number(Number::Float(_)) => (
+            raw.parse()
+                .ok()
+                .map(|val| Value::Number(Number::Float(val))),
+            "a number",
+        ),
+        Value::Date(_) => (raw.parse::<Date>().ok().map(Value::Date), "a date"),
+        Value::Time(_) => (raw.parse::<Time>().ok().map(Value::Time), "a time"),
+        Value::DateTime(_) => (
+            raw.parse::<DateTime>().ok().map(Value::DateTime),
+            "a date-time",
+        ),
+        Value::Str(_) => return Ok(Override::Str(raw)),
+        Value::Bytes(_) => return Ok(Override::Bytes(raw)),
+        Value::Null | Value::Array(_) | Value::Object(_) | Value::Map(_) => {
+            unreachable!("not a leaf")
+        }
+    };
+    match parsed {
+        Some(value) => Ok(Override::Value(value)),
+        None => Err(OverlayError::Mismatch {
+            name: name.to_string(),
+            expected,
+            value: raw,
+        }),
+    }
+}
+
+impl Overlay {
+    /// Starts building an overlay of `root`, which has no overrides until
+    /// a prefix, a file or arguments are given.
+    pub fn builder(root: Value<'static>) -> OverlayBuilder {
+        OverlayBuilder {
+            root,
+            prefix: None,
+            file: None,
+            args: Vec::new(),
+        }
+    }
+
+    /// The root of the config.
+    pub fn root(&self) -> OverlayValue<'_> {
+        OverlayValue {
+            overlay: self,
+            path: Vec::new(),
+            value: self.root,
+        }
+    }
+
+    /// Whether any value is overridden.
+    pub fn is_overridden(&self) -> bool {
+        !self.overrides.is_empty()
+    }
+
+    pub fn try_get(&self, key: &str) -> Option<OverlayValue<'_>> {
+        self.root().try_get(key)
+    }
+
+    pub fn get(&self, key: &str) -> OverlayValue<'_> {
+        self.root().get(key)
+    }
+
+    /// Looks up a value by dotted path, such as `servers[1].host`, like
+    /// [`Value::path`].
+    pub fn path(&self, path: &str) -> Option<OverlayValue<'_>> {
+        self.root().path(path)
+    }
+}
+
+impl<'a> OverlayValue<'a> {
+    /// The value with its override applied. Arrays, objects and maps are
+    /// returned as compiled in, without the overrides of their contents,
+    /// which are looked up with [`try_get`](Self::try_get) and
+    /// [`try_at`](Self::try_at) instead.
+    pub fn value(&self) -> Value<'a> {
+        match self.overlay.overrides.get(&self.path) {
+            Some(Override::Value(value)) => *value,
+            Some(Override::Str(s)) => Value::Str(s),
+            Some(Override::Bytes(b)) => Value::Bytes(b.as_bytes()),
+            None => self.value,
+        }
+    }
+
+    /// Whether the value itself is overridden.
+    pub fn is_overridden(&self) -> bool {
+        self.overlay.overrides.contains_key(&self.path)
+    }
+
+    fn child(&self, segment: String, value: Value<'static>) -> Self {
+        let mut path = self.path.clone();
+        path.push(segment);
+        Self {
+            overlay: self.overlay,
+            path,
+            value,
+        }
+    }
+
+    /// Looks up a key of an object.
+    pub fn try_get(&self, key: &str) -> Option<Self> {
+        let value = self.value.try_as_object()?.try_get(key)?;
+        Some(self.child(key.to_string(), *value))
+    }
+
+    pub fn get(&self, key: &str) -> Self {
+        self.try_get(key).unwrap()
+    }
+
+    /// Looks up an element of an array.
+    pub fn try_at(&self, idx: usize) -> Option<Self> {
+        let value = self.value.try_as_array()?.get(idx)?;
+        Some(self.child(idx.to_string(), *value))
+    }
+
+    pub fn at(&self, idx: usize) -> Self {
+        self.try_at(idx).unwrap()
+    }
+
+    /// Looks up a value by dotted path, like [`Value::path`].
+    pub fn path(&self, path: &str) -> Option<Self> {
+        let mut value = self.clone();
+        for segment in path.split('.').filter(|segment| !segment.is_empty()) {
+            let (key, mut indices) = match segment.find('[') {
+                Some(idx) => segment.split_at(idx),
+                None => (segment, ""),
+            };
+            if !key.is_empty() {
+                value = match key.parse() {
+                    Ok(idx) if value.value.is_array() => value.try_at(idx)?,
+                    _ => value.try_get(key)?,
+                };
+            }
+            while !indices.is_empty() {
+                let (idx, rest) = indices.strip_prefix('[')?.split_once(']')?;
+                value = value.try_at(idx.parse().ok()?)?;
+                indices = rest;
+            }
+        }
+        Some(value)
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.value().is_null()
+    }
+
+    pub fn is_bool(&self) -> bool {
+        self.value().is_bool()
+    }
+
+    pub fn is_number(&self) -> bool {
+        self.value().is_number()
+    }
+
+    pub fn is_uint(&self) -> bool {
+        self.value().is_uint()
+    }
+
+    pub fn is_int(&self) -> bool {
+        self.value().is_int()
+    }
+
+    pub fn is_integral(&self) -> bool {
+        self.value().is_integral()
+    }
+
+    pub fn is_float(&self) -> bool {
+        self.value().is_float()
+    }
+
+    pub fn is_date(&self) -> bool {
+        self.value().is_date()
+    }
+
+    pub fn is_time(&self) -> bool {
+        self.value().is_time()
+    }
+
+    pub fn is_datetime(&self) -> bool {
+        self.value().is_datetime()
+    }
+
+    pub fn is_str(&self) -> bool {
+        self.value().is_str()
+    }
+
+    pub fn is_bytes(&self) -> bool {
+        self.value().is_bytes()
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.value().is_array()
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.value().is_object()
+    }
+
+    pub fn try_as_unit(&self) -> Option<()> {
+        self.value().try_as_unit()
+    }
+
+    pub fn try_as_bool(&self) -> Option<bool> {
+        self.value().try_as_bool()
+    }
+
+    pub fn try_as_number(&self) -> Option<Number> {
+        self.value().try_as_number()
+    }
+
+    pub fn try_as_u8(&self) -> Option<u8> {
+        self.value().try_as_u8()
+    }
+
+    pub fn try_as_u16(&self) -> Option<u16> {
+        self.value().try_as_u16()
+    }
+
+    pub fn try_as_u32(&self) -> Option<u32> {
+        self.value().try_as_u32()
+    }
+
+    pub fn try_as_u64(&self) -> Option<u64> {
+        self.value().try_as_u64()
+    }
+
+    pub fn try_as_u128(&self) -> Option<u128> {
+        self.value().try_as_u128()
+    }
+
+    pub fn try_as_i8(&self) -> Option<i8> {
+        self.value().try_as_i8()
+    }
+
+    pub fn try_as_i16(&self) -> Option<i16> {
+        self.value().try_as_i16()
+    }
+
+    pub fn try_as_i32(&self) -> Option<i32> {
+        self.value().try_as_i32()
+    }
+
+    pub fn try_as_i64(&self) -> Option<i64> {
+        self.value().try_as_i64()
+    }
+
+    pub fn try_as_i128(&self) -> Option<i128> {
+        self.value().try_as_i128()
+    }
+
+    pub fn try_as_f32(&self) -> Option<f32> {
+        self.value().try_as_f32()
+    }
+
+    pub fn try_as_f64(&self) -> Option<f64> {
+        self.value().try_as_f64()
+    }
+
+    pub fn try_as_char(&self) -> Option<char> {
+        self.value().try_as_char()
+    }
+
+    pub fn try_as_date(&self) -> Option<Date> {
+        self.value().try_as_date()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn try_as_naive_date(&self) -> Option<chrono::NaiveDate> {
+        self.value().try_as_naive_date()
+    }
+
+    pub fn try_as_time(&self) -> Option<Time> {
+        self.value().try_as_time()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn try_as_naive_time(&self) -> Option<chrono::NaiveTime> {
+        self.value().try_as_naive_time()
+    }
+
+    pub fn try_as_datetime(&self) -> Option<DateTime> {
+        self.value().try_as_datetime()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn try_as_naive_datetime_and_offset(
+        &self,
+    ) -> Option<(chrono::NaiveDateTime, Option<chrono::FixedOffset>)> {
+        self.value().try_as_naive_datetime_and_offset()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn try_as_datetime_tz<Tz>(&self) -> Option<chrono::DateTime<Tz>>
+    where
+        Tz: chrono::TimeZone<Offset = chrono::FixedOffset>,
+    {
+        self.value().try_as_datetime_tz()
+    }
+
+    pub fn try_as_str(&self) -> Option<&'a str> {
+        self.value().try_as_str()
+    }
+
+    pub fn try_as_bytes(&self) -> Option<&'a [u8]> {
+        self.value().try_as_bytes()
+    }
+
+    /// The elements of an array, with their overrides applied.
+    pub fn try_as_array(&self) -> Option<Vec<Self>> {
+        let array = self.value.try_as_array()?;
+        Some(
+            array
+                .iter()
+                .enumerate()
+                .map(|(idx, value)| self.child(idx.to_string(), *value))
+                .collect(),
+        )
+    }
+
+    pub fn as_unit(&self) {
+        self.try_as_unit().unwrap()
+    }
+
+    pub fn as_bool(&self) -> bool {
+        self.try_as_bool().unwrap()
+    }
+
+    pub fn as_number(&self) -> Number {
+        self.try_as_number().unwrap()
+    }
+
+    pub fn as_u8(&self) -> u8 {
+        self.try_as_u8().unwrap()
+    }
+
+    pub fn as_u16(&self) -> u16 {
+        self.try_as_u16().unwrap()
+    }
+
+    pub fn as_u32(&self) -> u32 {
+        self.try_as_u32().unwrap()
+    }
+
+    pub fn as_u64(&self) -> u64 {
+        self.try_as_u64().unwrap()
+    }
+
+    pub fn as_u128(&self) -> u128 {
+        self.try_as_u128().unwrap()
+    }
+
+    pub fn as_i8(&self) -> i8 {
+        self.try_as_i8().unwrap()
+    }
+
+    pub fn as_i16(&self) -> i16 {
+        self.try_as_i16().unwrap()
+    }
+
+    pub fn as_i32(&self) -> i32 {
+        self.try_as_i32().unwrap()
+    }
+
+    pub fn as_i64(&self) -> i64 {
+        self.try_as_i64().unwrap()
+    }
+
+    pub fn as_i128(&self) -> i128 {
+        self.try_as_i128().unwrap()
+    }
+
+    pub fn as_f32(&self) -> f32 {
+        self.try_as_f32().unwrap()
+    }
+
+    pub fn as_f64(&self) -> f64 {
+        self.try_as_f64().unwrap()
+    }
+
+    pub fn as_char(&self) -> char {
+        self.try_as_char().unwrap()
+    }
+
+    pub fn as_date(&self) -> Date {
+        self.try_as_date().unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn as_naive_date(&self) -> chrono::NaiveDate {
+        self.try_as_naive_date().unwrap()
+    }
+
+    pub fn as_time(&self) -> Time {
+        self.try_as_time().unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn as_naive_time(&self) -> chrono::NaiveTime {
+        self.try_as_naive_time().unwrap()
+    }
+
+    pub fn as_datetime(&self) -> DateTime {
+        self.try_as_datetime().unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn as_naive_datetime_and_offset(
+        &self,
+    ) -> (chrono::NaiveDateTime, Option<chrono::FixedOffset>) {
+        self.try_as_naive_datetime_and_offset().unwrap()
+    }
+
+    #[cfg(feature = "chrono")]
+    pub fn as_datetime_tz<Tz>(&self) -> chrono::DateTime<Tz>
+    where
+        Tz: chrono::TimeZone<Offset = chrono::FixedOffset>,
+    {
+        self.try_as_datetime_tz().unwrap()
+    }
+
+    pub fn as_str(&self) -> &'a str {
+        self.try_as_str().unwrap()
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.try_as_bytes().unwrap()
+    }
+
+    pub fn as_array(&self) -> Vec<Self> {
+        self.try_as_array().unwrap()
+    }
+}
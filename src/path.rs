@@ -0,0 +1,683 @@
+use crate::{Map, Object, Value};
+
+/// Look up a typed value by path at compile time.
+///
+/// `get!(CONFIG, "path": Type)` walks `CONFIG` along `path` and converts the
+/// value found there to `Type`, all during constant evaluation. `CONFIG`
+/// must be a constant [`Value`], [`Object`] or [`Map`].
+///
+/// The path is either dotted, like `networking.endpoints[3].port`, or a
+/// JSON Pointer, like `/networking/endpoints/3/port`, and is resolved as by
+/// [`Value::path`] or [`Value::pointer`]. A path starting with `/` is always
+/// treated as a JSON Pointer.
+///
+/// If the path does not exist, or the value cannot be converted, the build
+/// fails with a message naming the path, the type that was expected and
+/// the value that was found instead, e.g.
+/// ``const-config: `networking.default-port`: expected `u8`, found integer `443` ``.
+///
+/// Supported types are `bool`, the integer and float primitives except
+/// `usize` and `isize`, `char`, `&str`, `&[u8]`, `&[Value]`, `()`, [`Number`](crate::Number),
+/// [`Date`](crate::Date), [`Time`](crate::Time), [`DateTime`](crate::DateTime),
+/// [`Object`], [`Map`] and [`Value`].
+///
+/// # Example
+/// ```
+/// use const_config::{Value, get};
+///
+/// # const CONFIG: Value<'static> = Value::Object(const_config::Object::new(
+/// #     &[("answer", Value::Number(const_config::Number::UInt(42)))],
+/// #     &[0],
+/// #     &[0],
+/// # ));
+/// // const CONFIG: Value<'static> = from_toml!("answer = 42");
+/// const ANSWER: u8 = get!(CONFIG, "answer": u8);
+/// assert_eq!(ANSWER, 42);
+/// assert_eq!(get!(CONFIG, "/answer": u64), 42);
+/// ```
+#[macro_export]
+macro_rules! get {
+    ($config:expr, $path:literal : $($ty:tt)+) => {
+        const {
+            let root = ($config).__as_value();
+            let value = $crate::__private::lookup(&root, $path);
+            $crate::get!(@as value, $path, $($ty)+)
+        }
+    };
+    (@as $value:ident, $path:literal, ()) => {
+        $crate::get!(@try $value, $path, try_as_unit, "()")
+    };
+    (@as $value:ident, $path:literal, bool) => {
+        $crate::get!(@try $value, $path, try_as_bool, "bool")
+    };
+    (@as $value:ident, $path:literal, u8) => {
+        $crate::get!(@try $value, $path, try_as_u8, "u8")
+    };
+    (@as $value:ident, $path:literal, u16) => {
+        $crate::get!(@try $value, $path, try_as_u16, "u16")
+    };
+    (@as $value:ident, $path:literal, u32) => {
+        $crate::get!(@try $value, $path, try_as_u32, "u32")
+    };
+    (@as $value:ident, $path:literal, u64) => {
+        $crate::get!(@try $value, $path, try_as_u64, "u64")
+    };
+    (@as $value:ident, $path:literal, u128) => {
+        $crate::get!(@try $value, $path, try_as_u128, "u128")
+    };
+    (@as $value:ident, $path:literal, i8) => {
+        $crate::get!(@try $value, $path, try_as_i8, "i8")
+    };
+    (@as $value:ident, $path:literal, i16) => {
+        $crate::get!(@try $value, $path, try_as_i16, "i16")
+    };
+    (@as $value:ident, $path:literal, i32) => {
+        $crate::get!(@try $value, $path, try_as_i32, "i32")
+    };
+    (@as $value:ident, $path:literal, i64) => {
+        $crate::get!(@try $value, $path, try_as_i64, "i64")
+    };
+    (@as $value:ident, $path:literal, i128) => {
+        $crate::get!(@try $value, $path, try_as_i128, "i128")
+    };
+    (@as $value:ident, $path:literal, f32) => {
+        $crate::get!(@try $value, $path, try_as_f32, "f32")
+    };
+    (@as $value:ident, $path:literal, f64) => {
+        $crate::get!(@try $value, $path, try_as_f64, "f64")
+    };
+    (@as $value:ident, $path:literal, char) => {
+        $crate::get!(@try $value, $path, try_as_char, "char")
+    };
+    (@as $value:ident, $path:literal, & $($lt:lifetime)? str) => {
+        $crate::get!(@try $value, $path, try_as_str, "&str")
+    };
+    (@as $value:ident, $path:literal, & $($lt:lifetime)? [u8]) => {
+        $crate::get!(@try $value, $path, try_as_bytes, "&[u8]")
+    };
+    (@as $value:ident, $path:literal, & $($lt:lifetime)? [$($elem:tt)+]) => {
+        $crate::get!(@try $value, $path, try_as_array, "&[Value]")
+    };
+    (@as $value:ident, $path:literal, Number) => {
+        $crate::get!(@try $value, $path, try_as_number, "Number")
+    };
+    (@as $value:ident, $path:literal, Date) => {
+        $crate::get!(@try $value, $path, try_as_date, "Date")
+    };
+    (@as $value:ident, $path:literal, Time) => {
+        $crate::get!(@try $value, $path, try_as_time, "Time")
+    };
+    (@as $value:ident, $path:literal, DateTime) => {
+        $crate::get!(@try $value, $path, try_as_datetime, "DateTime")
+    };
+    (@as $value:ident, $path:literal, Object $($rest:tt)*) => {
+        $crate::get!(@try $value, $path, try_as_object, "Object")
+    };
+    (@as $value:ident, $path:literal, Map $($rest:tt)*) => {
+        $crate::get!(@try $value, $path, try_as_map, "Map")
+    };
+    (@as $value:ident, $path:literal, Value $($rest:tt)*) => {
+        $value.copy()
+    };
+    (@as $value:ident, $path:literal, $($ty:tt)+) => {
+        ::core::compile_error!(::core::concat!(
+            "const-config: `get!` does not support type `",
+            ::core::stringify!($($ty)+),
+            "`",
+        ))
+    };
+    (@try $value:ident, $path:literal, $method:ident, $name:literal) => {
+        match $value.$method() {
+            ::core::option::Option::Some(value) => value,
+            ::core::option::Option::None => $crate::__private::mismatch($path, $name, &$value),
+        }
+    };
+}
+
+/// Why a path lookup failed.
+pub(crate) enum Miss<'a> {
+    /// The path itself is malformed.
+    Syntax,
+    /// An object or map has no entry for the segment.
+    Key,
+    /// An array index is out of bounds for an array of the given length.
+    Index(usize),
+    /// The segment was applied to a value that cannot contain it.
+    Kind(Value<'a>),
+}
+
+/// A failed path lookup. `start..end` is the byte range of the segment
+/// that could not be resolved.
+pub(crate) struct Failure<'a> {
+    start: usize,
+    end: usize,
+    index: bool,
+    miss: Miss<'a>,
+}
+
+const fn subslice(bytes: &[u8], start: usize, end: usize) -> &[u8] {
+    bytes.split_at(end).0.split_at(start).1
+}
+
+const fn substr(s: &str, start: usize, end: usize) -> &str {
+    // Paths are only ever split at ASCII delimiters.
+    unsafe { core::str::from_utf8_unchecked(subslice(s.as_bytes(), start, end)) }
+}
+
+/// Parses an array index. As in JSON Pointer, leading zeros are rejected.
+const fn parse_index(seg: &[u8]) -> Option<usize> {
+    if seg.is_empty() || (seg.len() > 1 && seg[0] == b'0') {
+        return None;
+    }
+    let mut index = 0usize;
+    let mut idx = 0usize;
+    while idx < seg.len() {
+        if !seg[idx].is_ascii_digit() {
+            return None;
+        }
+        index = match index.checked_mul(10) {
+            Some(index) => match index.checked_add((seg[idx] - b'0') as usize) {
+                Some(index) => index,
+                None => return None,
+            },
+            None => return None,
+        };
+        idx += 1;
+    }
+    Some(index)
+}
+
+/// Compares `key` to a JSON Pointer segment, decoding `~0` and `~1`.
+const fn escaped_eq(key: &[u8], seg: &[u8]) -> bool {
+    let mut k = 0usize;
+    let mut s = 0usize;
+    while s < seg.len() {
+        if k == key.len() {
+            return false;
+        }
+        let b = if seg[s] == b'~' {
+            s += 1;
+            if s == seg.len() {
+                return false;
+            }
+            match seg[s] {
+                b'0' => b'~',
+                b'1' => b'/',
+                _ => return false,
+            }
+        } else {
+            seg[s]
+        };
+        if key[k] != b {
+            return false;
+        }
+        k += 1;
+        s += 1;
+    }
+    k == key.len()
+}
+
+const fn has_escapes(seg: &[u8]) -> bool {
+    let mut idx = 0usize;
+    while idx < seg.len() {
+        if seg[idx] == b'~' {
+            return true;
+        }
+        idx += 1;
+    }
+    false
+}
+
+const fn object_child<'a>(obj: &Object<'a>, seg: &str, escaped: bool) -> Option<&'a Value<'a>> {
+    if !escaped {
+        return obj.try_get(seg);
+    }
+    let entries = obj.entries();
+    let mut idx = 0usize;
+    while idx < entries.len() {
+        if escaped_eq(entries[idx].0.as_bytes(), seg.as_bytes()) {
+            return Some(&entries[idx].1);
+        }
+        idx += 1;
+    }
+    None
+}
+
+const fn map_child<'a>(map: &Map<'a>, seg: &str, escaped: bool) -> Option<&'a Value<'a>> {
+    let found = if escaped {
+        let entries = map.entries();
+        let mut found = None;
+        let mut idx = 0usize;
+        while idx < entries.len() {
+            if let Value::Str(key) = &entries[idx].0
+                && escaped_eq(key.as_bytes(), seg.as_bytes())
+            {
+                found = Some(&entries[idx].1);
+                break;
+            }
+            idx += 1;
+        }
+        found
+    } else {
+        map.try_get_str(seg)
+    };
+    if found.is_some() {
+        return found;
+    }
+    match parse_index(seg.as_bytes()) {
+        Some(index) => map.try_get_u128(index as u128),
+        None => None,
+    }
+}
+
+/// Resolves a single path segment against `value`. `index` is set for a
+/// bracketed `[index]` segment, and `escaped` for a JSON Pointer segment.
+const fn child<'a>(
+    value: &Value<'a>,
+    seg: &str,
+    index: bool,
+    escaped: bool,
+) -> Result<&'a Value<'a>, Miss<'a>> {
+    let found = match value {
+        Value::Object(obj) if !index => object_child(obj, seg, escaped),
+        Value::Map(map) => map_child(map, seg, escaped),
+        Value::Array(array) => match parse_index(seg.as_bytes()) {
+            Some(idx) if idx < array.len() => Some(&array[idx]),
+            Some(_) => return Err(Miss::Index(array.len())),
+            None if index => return Err(Miss::Syntax),
+            None => return Err(Miss::Kind(value.copy())),
+        },
+        _ => return Err(Miss::Kind(value.copy())),
+    };
+    match found {
+        Some(found) => Ok(found),
+        None => Err(Miss::Key),
+    }
+}
+
+/// Returns `true` if `path` should be read as a JSON Pointer rather than a
+/// dotted path.
+pub(crate) const fn is_pointer(path: &str) -> bool {
+    !path.is_empty() && path.as_bytes()[0] == b'/'
+}
+
+/// Walks `root` along a JSON Pointer, or a dotted path if `pointer` is not
+/// set. Returns `None` for an empty path, which refers to `root` itself.
+pub(crate) const fn walk<'a>(
+    root: &Value<'a>,
+    path: &str,
+    pointer: bool,
+) -> Result<Option<&'a Value<'a>>, Failure<'a>> {
+    let bytes = path.as_bytes();
+    if pointer && !bytes.is_empty() && bytes[0] != b'/' {
+        return Err(Failure {
+            start: 0,
+            end: 0,
+            index: false,
+            miss: Miss::Syntax,
+        });
+    }
+    let mut value: Option<&'a Value<'a>> = None;
+    let mut pos = 0usize;
+    while pos < bytes.len() {
+        let (start, end, next, index) = if pointer {
+            let start = pos + 1;
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b'/' {
+                end += 1;
+            }
+            (start, end, end, false)
+        } else if bytes[pos] == b'[' {
+            let start = pos + 1;
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b']' {
+                end += 1;
+            }
+            if end == bytes.len() {
+                return Err(Failure {
+                    start,
+                    end,
+                    index: true,
+                    miss: Miss::Syntax,
+                });
+            }
+            (start, end, end + 1, true)
+        } else {
+            let start = pos;
+            let mut end = start;
+            while end < bytes.len() && bytes[end] != b'.' && bytes[end] != b'[' {
+                end += 1;
+            }
+            (start, end, end, false)
+        };
+
+        let mut next = next;
+        let mut malformed = !pointer && !index && start == end;
+        if !pointer && next < bytes.len() {
+            if bytes[next] == b'.' {
+                next += 1;
+                malformed |= next == bytes.len() || bytes[next] == b'[';
+            } else if bytes[next] != b'[' {
+                malformed = true;
+            }
+        }
+        if malformed {
+            return Err(Failure {
+                start,
+                end,
+                index,
+                miss: Miss::Syntax,
+            });
+        }
+
+        let seg = substr(path, start, end);
+        let escaped = pointer && has_escapes(seg.as_bytes());
+        let current = match value {
+            Some(value) => value,
+            None => root,
+        };
+        value = match child(current, seg, index, escaped) {
+            Ok(value) => Some(value),
+            Err(miss) => {
+                return Err(Failure {
+                    start,
+                    end,
+                    index,
+                    miss,
+                });
+            }
+        };
+        pos = next;
+    }
+    Ok(value)
+}
+
+impl<'a> Value<'a> {
+    /// Looks up a value by [JSON Pointer](https://www.rfc-editor.org/rfc/rfc6901).
+    ///
+    /// Each `/` separated segment names a key of an [`Object`] or [`Map`], or
+    /// an index into an array. `~1` and `~0` in a segment stand for `/` and
+    /// `~`. An index also matches an integer key of a [`Map`]. The empty
+    /// pointer refers to `self`.
+    ///
+    /// Returns `None` if the pointer is malformed or does not exist.
+    ///
+    /// # Example
+    /// ```
+    /// use const_config::Value;
+    ///
+    /// const CONFIG: Value<'static> = Value::Array(&[Value::Null, Value::Bool(true)]);
+    /// const FLAG: Option<&Value<'static>> = CONFIG.pointer("/1");
+    /// assert_eq!(FLAG, Some(&Value::Bool(true)));
+    /// assert_eq!(CONFIG.pointer("/2"), None);
+    /// ```
+    pub const fn pointer(&self, pointer: &str) -> Option<&Value<'a>> {
+        match walk(self, pointer, true) {
+            Ok(Some(value)) => Some(value),
+            Ok(None) => Some(self),
+            Err(_) => None,
+        }
+    }
+
+    /// Looks up a value by dotted path, such as
+    /// `networking.endpoints[3].port`.
+    ///
+    /// Each `.` separated segment names a key of an [`Object`] or [`Map`],
+    /// and each `[index]` segment an index into an array, or an integer key
+    /// of a [`Map`]. A plain numeric segment also indexes into an array. Keys
+    /// containing `.` or `[` can only be reached with [`Value::pointer`]. The
+    /// empty path refers to `self`.
+    ///
+    /// Returns `None` if the path is malformed or does not exist.
+    pub const fn path(&self, path: &str) -> Option<&Value<'a>> {
+        match walk(self, path, false) {
+            Ok(Some(value)) => Some(value),
+            Ok(None) => Some(self),
+            Err(_) => None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn __as_value(&self) -> Value<'a> {
+        self.copy()
+    }
+}
+
+impl<'a> Object<'a> {
+    /// Looks up a value by JSON Pointer, as [`Value::pointer`]. The empty
+    /// pointer, which would refer to the object itself, returns `None`.
+    pub const fn pointer(&self, pointer: &str) -> Option<&'a Value<'a>> {
+        match walk(&Value::Object(self.copy()), pointer, true) {
+            Ok(value) => value,
+            Err(_) => None,
+        }
+    }
+
+    /// Looks up a value by dotted path, as [`Value::path`]. The empty path,
+    /// which would refer to the object itself, returns `None`.
+    pub const fn path(&self, path: &str) -> Option<&'a Value<'a>> {
+        match walk(&Value::Object(self.copy()), path, false) {
+            Ok(value) => value,
+            Err(_) => None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn __as_value(&self) -> Value<'a> {
+        Value::Object(self.copy())
+    }
+}
+
+impl<'a> Map<'a> {
+    /// Looks up a value by JSON Pointer, as [`Value::pointer`]. The empty
+    /// pointer, which would refer to the map itself, returns `None`.
+    pub const fn pointer(&self, pointer: &str) -> Option<&'a Value<'a>> {
+        match walk(&Value::Map(self.copy()), pointer, true) {
+            Ok(value) => value,
+            Err(_) => None,
+        }
+    }
+
+    /// Looks up a value by dotted path, as [`Value::path`]. The empty path,
+    /// which would refer to the map itself, returns `None`.
+    pub const fn path(&self, path: &str) -> Option<&'a Value<'a>> {
+        match walk(&Value::Map(self.copy()), path, false) {
+            Ok(value) => value,
+            Err(_) => None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn __as_value(&self) -> Value<'a> {
+        Value::Map(self.copy())
+    }
+}
+
+/// A fixed capacity buffer for building panic messages during constant
+/// evaluation. Messages that do not fit are truncated.
+struct Message {
+    buf: [u8; 512],
+    len: usize,
+}
+
+impl Message {
+    const fn new() -> Self {
+        Self {
+            buf: [0; 512],
+            len: 0,
+        }
+    }
+
+    const fn push(&mut self, s: &str) {
+        let s = s.as_bytes();
+        let mut idx = 0usize;
+        while idx < s.len() && self.len < self.buf.len() {
+            self.buf[self.len] = s[idx];
+            self.len += 1;
+            idx += 1;
+        }
+    }
+
+    const fn push_u128(&mut self, mut value: u128) {
+        let mut digits = [0u8; 39];
+        let mut idx = digits.len();
+        loop {
+            idx -= 1;
+            digits[idx] = b'0' + (value % 10) as u8;
+            value /= 10;
+            if value == 0 {
+                break;
+            }
+        }
+        self.push(unsafe { core::str::from_utf8_unchecked(digits.split_at(idx).1) });
+    }
+
+    const fn push_i128(&mut self, value: i128) {
+        if value < 0 {
+            self.push("-");
+        }
+        self.push_u128(value.unsigned_abs());
+    }
+
+    const fn as_str(&self) -> &str {
+        let mut len = self.len;
+        // Drop a multi-byte character cut in half by truncation.
+        if len == self.buf.len() {
+            while len > 0 && (self.buf[len - 1] & 0b1100_0000) == 0b1000_0000 {
+                len -= 1;
+            }
+            if len > 0 && self.buf[len - 1] >= 0b1100_0000 {
+                len -= 1;
+            }
+        }
+        unsafe { core::str::from_utf8_unchecked(self.buf.split_at(len).0) }
+    }
+}
+
+const fn kind_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "bool",
+        Value::Number(crate::Number::Float(_)) => "float",
+        Value::Number(_) => "integer",
+        Value::Date(_) => "date",
+        Value::Time(_) => "time",
+        Value::DateTime(_) => "datetime",
+        Value::Str(_) => "string",
+        Value::Bytes(_) => "bytes",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+        Value::Map(_) => "map",
+    }
+}
+
+const fn push_found(msg: &mut Message, value: &Value) {
+    msg.push(kind_name(value));
+    match value {
+        Value::Bool(true) => msg.push(" `true`"),
+        Value::Bool(false) => msg.push(" `false`"),
+        Value::Number(crate::Number::UInt(value)) => {
+            msg.push(" `");
+            msg.push_u128(*value);
+            msg.push("`");
+        }
+        Value::Number(crate::Number::Int(value)) => {
+            msg.push(" `");
+            msg.push_i128(*value);
+            msg.push("`");
+        }
+        Value::Str(value) => {
+            msg.push(" \"");
+            msg.push(value);
+            msg.push("\"");
+        }
+        _ => {}
+    }
+}
+
+/// Parent path of the segment starting at `start`, for error messages.
+const fn parent(path: &str, start: usize, index: bool) -> &str {
+    let bytes = path.as_bytes();
+    let mut end = start;
+    if end > 0 && (index || bytes[end - 1] == b'.' || bytes[end - 1] == b'/') {
+        end -= 1;
+    }
+    substr(path, 0, end)
+}
+
+#[doc(hidden)]
+pub mod __private {
+    use super::{Failure, Message, Miss, is_pointer, parent, push_found, substr, walk};
+    use crate::Value;
+
+    pub const fn lookup<'a>(root: &Value<'a>, path: &str) -> Value<'a> {
+        let Failure {
+            start,
+            end,
+            index,
+            miss,
+        } = match walk(root, path, is_pointer(path)) {
+            Ok(Some(value)) => return value.copy(),
+            Ok(None) => return root.copy(),
+            Err(failure) => failure,
+        };
+
+        let bracket_end = if index && end < path.len() {
+            end + 1
+        } else {
+            end
+        };
+        let mut msg = Message::new();
+        msg.push("const-config: `");
+        msg.push(path);
+        msg.push("`: ");
+        match miss {
+            Miss::Syntax => {
+                msg.push("malformed path at `");
+                msg.push(substr(path, 0, bracket_end));
+                msg.push("`");
+            }
+            Miss::Key => {
+                msg.push("missing key `");
+                msg.push(substr(path, start, end));
+                msg.push("`");
+                let parent = parent(path, start, index);
+                if !parent.is_empty() {
+                    msg.push(" in `");
+                    msg.push(parent);
+                    msg.push("`");
+                }
+            }
+            Miss::Index(len) => {
+                msg.push("index `");
+                msg.push(substr(path, start, end));
+                msg.push("` is out of bounds for an array of length ");
+                msg.push_u128(len as u128);
+            }
+            Miss::Kind(value) => {
+                msg.push("cannot look up `");
+                msg.push(substr(path, start, end));
+                msg.push("` in ");
+                push_found(&mut msg, &value);
+                let parent = parent(path, start, index);
+                if !parent.is_empty() {
+                    msg.push(" at `");
+                    msg.push(parent);
+                    msg.push("`");
+                }
+            }
+        }
+        panic!("{}", msg.as_str())
+    }
+
+    pub const fn mismatch(path: &str, expected: &str, found: &Value) -> ! {
+        let mut msg = Message::new();
+        msg.push("const-config: `");
+        msg.push(path);
+        msg.push("`: expected `");
+        msg.push(expected);
+        msg.push("`, found ");
+        push_found(&mut msg, found);
+        panic!("{}", msg.as_str())
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{Number, Value};
+use core::fmt::{self, Write};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Style {
+    Compact,
+    Pretty,
+}
+
+impl<'a> Value<'a> {
+    /// Writes the value as JSON without any whitespace.
+    ///
+    /// This needs neither `std` nor an allocator, so it also works on
+    /// embedded targets, e.g. to report a config over a serial port. The
+    /// output only depends on the value:
+    /// - Dates, times and date-times are written as RFC 3339 strings.
+    /// - Byte strings are written as arrays of numbers.
+    /// - NaN and infinite floats, which JSON has no notation for, are
+    ///   written as `null`.
+    /// - Map keys other than strings are written as strings holding their
+    ///   compact JSON, e.g. `"1"` or `"[1,2]"`.
+    ///
+    /// # Example
+    /// ```
+    /// use const_config::{Number, Value};
+    ///
+    /// let value = Value::Array(&[Value::Number(Number::UInt(1)), Value::Str("a\"b")]);
+    /// let mut json = String::new();
+    /// value.write_json(&mut json).unwrap();
+    /// assert_eq!(json, r#"[1,"a\"b"]"#);
+    /// ```
+    pub fn write_json(&self, w: &mut impl Write) -> fmt::Result {
+        json(self, w, Style::Compact, 0)
+    }
+
+    /// Writes the value as JSON indented by two spaces, see
+    /// [`write_json`](Self::write_json).
+    pub fn write_json_pretty(&self, w: &mut impl Write) -> fmt::Result {
+        json(self, w, Style::Pretty, 0)
+    }
+
+    /// Writes an object as a TOML document, with a `[table]` for every
+    /// nested object or map and an `[[array]]` for every array of them.
+    ///
+    /// Like [`write_json`](Self::write_json), this needs no allocator. TOML
+    /// has no null, so keys whose value is null are left out, and null
+    /// elements of arrays are written as empty inline tables. Byte strings
+    /// are written as arrays of numbers, and map keys other than strings as
+    /// their TOML text, quoted when needed. Values other than objects are
+    /// written as a single inline value.
+    ///
+    /// # Example
+    /// ```
+    /// use const_config::{Number, Object, Value};
+    ///
+    /// # const CONFIG: Value<'static> = Value::Object(Object::new(
+    /// #     &[
+    /// #         ("name", Value::Str("app")),
+    /// #         ("networking", Value::Object(Object::new(&[("port", Value::Number(Number::UInt(443)))], &[0], &[0]))),
+    /// #     ],
+    /// #     &[1, 1],
+    /// #     &[1, 0],
+    /// # ));
+    /// // const CONFIG: Value<'static> = from_json!(r#"{"name": "app", "networking": {"port": 443}}"#);
+    /// let mut toml = String::new();
+    /// CONFIG.write_toml(&mut toml).unwrap();
+    /// assert_eq!(toml, "name = \"app\"\n\n[networking]\nport = 443\n");
+    /// ```
+    pub fn write_toml(&self, w: &mut impl Write) -> fmt::Result {
+        if !is_table(self) {
+            return toml_inline(self, w, Style::Pretty);
+        }
+        toml_table(self, w, None, &mut false)
+    }
+
+    /// Writes an object as a TOML document with a line for every key, and
+    /// nested objects and maps as inline tables, without optional
+    /// whitespace. See [`write_toml`](Self::write_toml).
+    pub fn write_toml_compact(&self, w: &mut impl Write) -> fmt::Result {
+        if !is_table(self) {
+            return toml_inline(self, w, Style::Compact);
+        }
+        for idx in 0..entry_count(self) {
+            let (key, value) = entry(self, idx);
+            if value.is_null() {
+                continue;
+            }
+            toml_key(w, key)?;
+            w.write_char('=')?;
+            toml_inline(value, w, Style::Compact)?;
+            w.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// Writes everything as the contents of a JSON or TOML string.
+struct Escaped<'w>(&'w mut dyn Write);
+
+impl Write for Escaped<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for ch in s.chars() {
+            match ch {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                '\n' => self.0.write_str("\\n")?,
+                '\r' => self.0.write_str("\\r")?,
+                '\t' => self.0.write_str("\\t")?,
+                '\u{8}' => self.0.write_str("\\b")?,
+                '\u{c}' => self.0.write_str("\\f")?,
+                ch if ch < ' ' || ch == '\u{7f}' => write!(self.0, "\\u{:04x}", ch as u32)?,
+                ch => self.0.write_char(ch)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+fn quoted(w: &mut dyn Write, s: &str) -> fmt::Result {
+    w.write_char('"')?;
+    Escaped(w).write_str(s)?;
+    w.write_char('"')
+}
+
+fn indent(w: &mut dyn Write, depth: usize) -> fmt::Result {
+    for _ in 0..depth {
+        w.write_str("  ")?;
+    }
+    Ok(())
+}
+
+/// Writes `len` items between `open` and `close`, one per line in the
+/// pretty style.
+fn json_seq(
+    w: &mut dyn Write,
+    style: Style,
+    depth: usize,
+    [open, close]: [char; 2],
+    len: usize,
+    mut item: impl FnMut(&mut dyn Write, usize) -> fmt::Result,
+) -> fmt::Result {
+    w.write_char(open)?;
+    for idx in 0..len {
+        if idx != 0 {
+            w.write_char(',')?;
+        }
+        if style == Style::Pretty {
+            w.write_char('\n')?;
+            indent(w, depth + 1)?;
+        }
+        item(w, idx)?;
+    }
+    if style == Style::Pretty && len != 0 {
+        w.write_char('\n')?;
+        indent(w, depth)?;
+    }
+    w.write_char(close)
+}
+
+fn json(value: &Value, w: &mut dyn Write, style: Style, depth: usize) -> fmt::Result {
+    let colon = if style == Style::Pretty { ": " } else { ":" };
+    match value {
+        Value::Null => w.write_str("null"),
+        Value::Bool(value) => write!(w, "{value}"),
+        Value::Number(Number::Float(value)) if !value.is_finite() => w.write_str("null"),
+        Value::Number(Number::Float(value)) => write!(w, "{value:?}"),
+        Value::Number(value) => write!(w, "{value}"),
+        Value::Date(value) => write!(w, "\"{value}\""),
+        Value::Time(value) => write!(w, "\"{value}\""),
+        Value::DateTime(value) => write!(w, "\"{value}\""),
+        Value::Str(value) => quoted(w, value),
+        Value::Bytes(bytes) => json_seq(w, style, depth, ['[', ']'], bytes.len(), |w, idx| {
+            write!(w, "{}", bytes[idx])
+        }),
+        Value::Array(array) => json_seq(w, style, depth, ['[', ']'], array.len(), |w, idx| {
+            json(&array[idx], w, style, depth + 1)
+        }),
+        Value::Object(obj) => json_seq(w, style, depth, ['{', '}'], obj.len(), |w, idx| {
+            let (key, value) = &obj.entries()[idx];
+            quoted(w, key)?;
+            w.write_str(colon)?;
+            json(value, w, style, depth + 1)
+        }),
+        Value::Map(map) => json_seq(w, style, depth, ['{', '}'], map.len(), |w, idx| {
+            let (key, value) = &map.entries()[idx];
+            if let Value::Str(key) = key {
+                quoted(w, key)?;
+            } else {
+                w.write_char('"')?;
+                json(key, &mut Escaped(w), Style::Compact, 0)?;
+                w.write_char('"')?;
+            }
+            w.write_str(colon)?;
+            json(value, w, style, depth + 1)
+        }),
+    }
+}
+
+/// A key of an object or a map.
+#[derive(Clone, Copy)]
+enum Key<'a> {
+    Str(&'a str),
+    Value(&'a Value<'a>),
+}
+
+/// The path of a TOML table, as a list of keys from the end.
+struct Path<'p> {
+    parent: Option<&'p Path<'p>>,
+    key: Key<'p>,
+}
+
+fn is_table(value: &Value) -> bool {
+    matches!(value, Value::Object(_) | Value::Map(_))
+}
+
+fn is_table_array(value: &Value) -> bool {
+    matches!(value, Value::Array(array) if !array.is_empty() && array.iter().all(is_table))
+}
+
+fn entry_count(table: &Value) -> usize {
+    match table {
+        Value::Object(obj) => obj.len(),
+        Value::Map(map) => map.len(),
+        _ => 0,
+    }
+}
+
+fn entry<'v>(table: &'v Value<'v>, idx: usize) -> (Key<'v>, &'v Value<'v>) {
+    match table {
+        Value::Object(obj) => {
+            let (key, value) = &obj.entries()[idx];
+            (Key::Str(key), value)
+        }
+        Value::Map(map) => {
+            let (key, value) = &map.entries()[idx];
+            match key {
+                Value::Str(key) => (Key::Str(key), value),
+                key => (Key::Value(key), value),
+            }
+        }
+        _ => unreachable!(),
+    }
+}
+
+/// Checks whether everything written to it could be a bare TOML key.
+struct Bare(bool);
+
+impl Write for Bare {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0 &= s
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-');
+        Ok(())
+    }
+}
+
+fn toml_key(w: &mut dyn Write, key: Key) -> fmt::Result {
+    match key {
+        Key::Str(key)
+            if !key.is_empty() && {
+                let mut bare = Bare(true);
+                bare.write_str(key)?;
+                bare.0
+            } =>
+        {
+            w.write_str(key)
+        }
+        Key::Str(key) => quoted(w, key),
+        Key::Value(key) => {
+            let mut bare = Bare(true);
+            toml_inline(key, &mut bare, Style::Compact)?;
+            if bare.0 {
+                toml_inline(key, w, Style::Compact)
+            } else {
+                w.write_char('"')?;
+                toml_inline(key, &mut Escaped(w), Style::Compact)?;
+                w.write_char('"')
+            }
+        }
+    }
+}
+
+fn toml_path(w: &mut dyn Write, path: &Path) -> fmt::Result {
+    if let Some(parent) = path.parent {
+        toml_path(w, parent)?;
+        w.write_char('.')?;
+    }
+    toml_key(w, path.key)
+}
+
+fn toml_inline(value: &Value, w: &mut dyn Write, style: Style) -> fmt::Result {
+    let sep = if style == Style::Pretty { ", " } else { "," };
+    match value {
+        Value::Null => w.write_str("{}"),
+        Value::Bool(value) => write!(w, "{value}"),
+        Value::Number(Number::Float(value)) if value.is_nan() => w.write_str("nan"),
+        Value::Number(Number::Float(value)) if value.is_infinite() => {
+            w.write_str(if *value > 0.0 { "inf" } else { "-inf" })
+        }
+        Value::Number(Number::Float(value)) => write!(w, "{value:?}"),
+        Value::Number(value) => write!(w, "{value}"),
+        Value::Date(value) => write!(w, "{value}"),
+        Value::Time(value) => write!(w, "{value}"),
+        Value::DateTime(value) => write!(w, "{value}"),
+        Value::Str(value) => quoted(w, value),
+        Value::Bytes(bytes) => {
+            w.write_char('[')?;
+            for (idx, byte) in bytes.iter().enumerate() {
+                if idx != 0 {
+                    w.write_str(sep)?;
+                }
+                write!(w, "{byte}")?;
+            }
+            w.write_char(']')
+        }
+        Value::Array(array) => {
+            w.write_char('[')?;
+            for (idx, elem) in array.iter().enumerate() {
+                if idx != 0 {
+                    w.write_str(sep)?;
+                }
+                toml_inline(elem, w, style)?;
+            }
+            w.write_char(']')
+        }
+        Value::Object(_) | Value::Map(_) => {
+            let (open, eq, close) = match style {
+                Style::Compact => ("{", "=", "}"),
+                Style::Pretty => ("{ ", " = ", " }"),
+            };
+            let mut first = true;
+            for idx in 0..entry_count(value) {
+                let (key, value) = entry(value, idx);
+                if value.is_null() {
+                    continue;
+                }
+                w.write_str(if first { open } else { sep })?;
+                first = false;
+                toml_key(w, key)?;
+                w.write_str(eq)?;
+                toml_inline(value, w, style)?;
+            }
+            w.write_str(if first { "{}" } else { close })
+        }
+    }
+}
+
+/// Writes the keys of `table` that are not tables themselves, then its
+/// nested tables under their headers.
+fn toml_table(
+    table: &Value,
+    w: &mut dyn Write,
+    path: Option<&Path>,
+    started: &mut bool,
+) -> fmt::Result {
+    for idx in 0..entry_count(table) {
+        let (key, value) = entry(table, idx);
+        if value.is_null() || is_table(value) || is_table_array(value) {
+            continue;
+        }
+        toml_key(w, key)?;
+        w.write_str(" = ")?;
+        toml_inline(value, w, Style::Pretty)?;
+        w.write_char('\n')?;
+        *started = true;
+    }
+
+    for idx in 0..entry_count(table) {
+        let (key, value) = entry(table, idx);
+        let path = Path { parent: path, key };
+        if is_table(value) {
+            if *started {
+                w.write_char('\n')?;
+            }
+            w.write_char('[')?;
+            toml_path(w, &path)?;
+            w.write_str("]\n")?;
+            *started = true;
+            toml_table(value, w, Some(&path), started)?;
+        } else if is_table_array(value) {
+            for elem in value.as_array() {
+                if *started {
+                    w.write_char('\n')?;
+                }
+                w.write_str("[[")?;
+                toml_path(w, &path)?;
+                w.write_str("]]\n")?;
+                *started = true;
+                toml_table(elem, w, Some(&path), started)?;
+            }
+        }
+    }
+    Ok(())
+}
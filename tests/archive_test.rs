@@ -0,0 +1,129 @@
+#![cfg(feature = "yaml")]
+
+use const_config::{ArchiveError, ArchivedValue, Date, Number, Value};
+
+// `config.bin` is generated from `config.yaml` with
+// `const-config-gen -i tests/archive/config.yaml -t -e archive -o tests/archive/config.bin`.
+const ARCHIVE: &[u8] = include_bytes!("archive/config.bin");
+const CFG: Value<'static> = const_config::include_yaml!("archive/config.yaml", timestamps = true);
+
+fn assert_same(archived: ArchivedValue, value: &Value) {
+    match value {
+        Value::Array(array) => {
+            let archived = archived.as_array();
+            assert_eq!(archived.len(), array.len());
+            for (archived, value) in archived.iter().zip(*array) {
+                assert_same(archived, value);
+            }
+        }
+        Value::Object(obj) => {
+            let archived = archived.as_object();
+            assert_eq!(archived.len(), obj.len());
+            for (key, value) in obj.entries() {
+                assert_same(archived.get(key), value);
+            }
+        }
+        Value::Map(map) => {
+            let archived = archived.as_map();
+            assert_eq!(archived.len(), map.len());
+            for (key, value) in map.entries() {
+                assert_same(archived.get(key), value);
+            }
+        }
+        value => assert_eq!(format!("{archived:?}"), format!("{value:?}")),
+    }
+}
+
+#[test]
+fn archive_test() {
+    let archived = ArchivedValue::from_bytes(ARCHIVE).unwrap();
+    assert_same(archived, &CFG);
+
+    let obj = archived.as_object();
+    assert_eq!(obj.get("name").as_str(), "app");
+    assert_eq!(obj.get("huge").as_u128(), u128::MAX);
+    assert_eq!(obj.get("tiny").as_i128(), i128::MIN);
+    assert_eq!(obj.get("offset").as_number(), Number::Int(-12));
+    assert_eq!(
+        obj.get("since").as_date(),
+        Date {
+            year: 2024,
+            month: 1,
+            day: 2
+        }
+    );
+    assert_eq!(obj.get("at").as_datetime().offset, Some(60));
+    assert_eq!(obj.get("alarm").as_time().hour, 7);
+    assert!(obj.get("missing").is_null());
+    assert!(!obj.contains("absent"));
+    assert_eq!(
+        obj.get("servers")
+            .as_array()
+            .get(1)
+            .unwrap()
+            .as_object()
+            .get("port")
+            .as_u16(),
+        443
+    );
+    assert!(obj.get("servers").as_array().get(2).is_none());
+
+    let ports = obj.get("ports").as_map();
+    assert_eq!(
+        ports.get(&Value::Number(Number::UInt(443))).as_str(),
+        "https"
+    );
+    assert_eq!(
+        ports.get(&Value::Number(Number::Float(80.0))).as_str(),
+        "http"
+    );
+    assert_eq!(
+        ports.get(&Value::Number(Number::Float(f64::NAN))).as_str(),
+        "not a number"
+    );
+    let pair = [
+        Value::Number(Number::UInt(1)),
+        Value::Number(Number::UInt(2)),
+    ];
+    assert_eq!(ports.get(&Value::Array(&pair)).as_str(), "pair");
+    assert!(!ports.contains(&Value::Array(&pair[..1])));
+}
+
+#[test]
+fn invalid_archives() {
+    assert_eq!(
+        ArchivedValue::from_bytes(b"not an archive").unwrap_err(),
+        ArchiveError::BadMagic
+    );
+
+    let mut data = ARCHIVE.to_vec();
+    data[8] = 2;
+    assert_eq!(
+        ArchivedValue::from_bytes(&data).unwrap_err(),
+        ArchiveError::UnsupportedVersion(2)
+    );
+
+    let truncated = &ARCHIVE[..ARCHIVE.len() - 1];
+    assert!(matches!(
+        ArchivedValue::from_bytes(truncated).unwrap_err(),
+        ArchiveError::OutOfBounds { .. }
+    ));
+
+    let mut data = ARCHIVE.to_vec();
+    data[16] = 0xff;
+    assert_eq!(
+        ArchivedValue::from_bytes(&data).unwrap_err(),
+        ArchiveError::InvalidSlot { offset: 16 }
+    );
+}
+
+#[test]
+fn self_referencing_archive() {
+    // An array whose only element is the array itself.
+    let mut data = b"CCFGARCH\x01\0\0\0\0\0\0\0".to_vec();
+    data.extend_from_slice(&[12, 0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(
+        ArchivedValue::from_bytes(&data).unwrap_err(),
+        ArchiveError::TooManyValues
+    );
+}
@@ -0,0 +1,20 @@
+#![cfg(all(feature = "toml", feature = "json", feature = "cbor"))]
+
+use const_config::{Value, get, include_config};
+
+const TOML: Value<'static> = include_config!("test.toml");
+const JSON: Value<'static> = include_config!("test.json");
+const CBOR: Value<'static> = include_config!("detect/app.bin");
+
+#[test]
+fn by_extension() {
+    assert_eq!(get!(TOML, "i": u8), 42);
+    assert_eq!(get!(TOML, "s": &str), "hello world");
+    assert!(JSON.is_object());
+}
+
+#[test]
+fn by_contents() {
+    assert_eq!(get!(CBOR, "port": u16), 443);
+    assert_eq!(get!(CBOR, "name": &str), "app");
+}
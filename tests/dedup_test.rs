@@ -0,0 +1,29 @@
+#![cfg(feature = "json")]
+
+use const_config::{Number, Value};
+
+const CFG: Value<'static> = const_config::from_json!(
+    r#"
+{
+    "primary": {"host": "localhost", "ports": [80, 443]},
+    "replica": {"host": "localhost", "ports": [80, 443]},
+    "hosts": ["localhost", "localhost"],
+    "ports": [80, 443]
+}
+"#
+);
+
+#[test]
+fn dedup_test() {
+    let cfg = CFG.as_object();
+    for server in ["primary", "replica"] {
+        let server = cfg.get(server).as_object();
+        assert_eq!(server.get("host").as_str(), "localhost");
+        assert_eq!(
+            server.get("ports").as_array()[1],
+            Value::Number(Number::UInt(443))
+        );
+    }
+    assert_eq!(cfg.get("hosts").as_array()[1].as_str(), "localhost");
+    assert_eq!(cfg.get("ports").as_array().len(), 2);
+}
@@ -0,0 +1,11 @@
+use const_config::ConstConfig;
+
+#[derive(ConstConfig)]
+#[const_config(from_toml = r#"
+ratio = 1e300
+"#)]
+struct Config {
+    ratio: f32,
+}
+
+fn main() {}
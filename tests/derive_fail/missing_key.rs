@@ -0,0 +1,12 @@
+use const_config::ConstConfig;
+
+#[derive(ConstConfig)]
+#[const_config(from_toml = r#"
+name = "server"
+"#)]
+struct Config {
+    name: &'static str,
+    port: u16,
+}
+
+fn main() {}
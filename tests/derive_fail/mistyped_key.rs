@@ -0,0 +1,14 @@
+use const_config::ConstConfig;
+
+#[derive(ConstConfig)]
+#[const_config(from_toml = r#"
+name = "server"
+default-port = "http"
+"#)]
+#[const_config(rename_all = "kebab-case")]
+struct Config {
+    name: &'static str,
+    default_port: u16,
+}
+
+fn main() {}
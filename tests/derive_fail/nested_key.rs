@@ -0,0 +1,25 @@
+use const_config::ConstConfig;
+
+#[derive(ConstConfig)]
+#[const_config(from_toml = r#"
+[[endpoints]]
+host = "a.example.com"
+port = 443
+
+[[endpoints]]
+host = "b.example.com"
+port = 70000
+aliases = ["b", 2]
+"#)]
+struct Config {
+    endpoints: &'static [Endpoint],
+}
+
+#[derive(ConstConfig)]
+struct Endpoint {
+    host: &'static str,
+    port: u16,
+    aliases: &'static [&'static str],
+}
+
+fn main() {}
@@ -0,0 +1,7 @@
+#![cfg(feature = "toml")]
+
+#[test]
+fn derive_fail_test() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/derive_fail/*.rs");
+}
@@ -0,0 +1,190 @@
+#![cfg(feature = "toml")]
+
+use const_config::{ConstConfig, Date, DateTime, Time, Value};
+
+#[derive(ConstConfig)]
+#[const_config(from_toml = r#"
+name = "server"
+port = 8080
+ratio = 0.5
+small = -3
+letter = "x"
+tags = ["a", "b"]
+matrix = [[1, 2], [3, 4]]
+fixed = [1, 2, 3]
+blob = "raw"
+missing-ok = 7
+released = 2024-01-02
+alarm = 06:30:00
+started = 2024-01-02T03:04:05Z
+
+[general]
+production = true
+trace-logging = false
+
+[[endpoints]]
+host = "a.example.com"
+port = 443
+
+[[endpoints]]
+host = "b.example.com"
+port = 80
+weights = [1, 2]
+aliases = ["b", "bee"]
+ports = [80, 8080]
+checksum = [1, 2]
+tls = { cert = "b.pem", ciphers = ["aes", "chacha"], curves = [{ name = "x25519", bits = 255 }] }
+"#)]
+struct Config {
+    name: &'static str,
+    port: u16,
+    ratio: f32,
+    small: i8,
+    letter: char,
+    tags: &'static [&'static str],
+    matrix: &'static [&'static [u64]],
+    fixed: [u8; 3],
+    blob: &'static [u8],
+    #[const_config(rename = "missing-ok")]
+    renamed: Option<u32>,
+    absent: Option<&'static str>,
+    released: Date,
+    alarm: Time,
+    started: DateTime,
+    general: General,
+    endpoints: &'static [Endpoint],
+}
+
+#[derive(ConstConfig)]
+#[const_config(rename_all = "kebab-case")]
+struct General {
+    production: bool,
+    trace_logging: bool,
+    log_level: Option<u8>,
+}
+
+#[derive(ConstConfig)]
+struct Endpoint {
+    host: &'static str,
+    port: u16,
+    weights: Option<&'static [Value<'static>]>,
+    aliases: Option<&'static [&'static str]>,
+    ports: Option<&'static [u16]>,
+    checksum: Option<[u8; 2]>,
+    tls: Option<tls::Tls>,
+}
+
+mod tls {
+    use const_config::ConstConfig;
+
+    #[derive(ConstConfig)]
+    pub struct Tls {
+        pub cert: &'static str,
+        pub ciphers: &'static [&'static str],
+        pub curves: &'static [Curve],
+    }
+
+    #[derive(ConstConfig)]
+    pub struct Curve {
+        pub name: &'static str,
+        pub bits: u16,
+    }
+}
+
+#[derive(ConstConfig)]
+#[const_config(include_toml = "test.toml")]
+struct FileConfig {
+    i: u64,
+    b: bool,
+    s: &'static str,
+    a: &'static [Value<'static>],
+    o: FileInner,
+}
+
+#[derive(ConstConfig)]
+struct FileInner {
+    e: &'static [Value<'static>],
+    d: Date,
+    t: Time,
+    u: Value<'static>,
+}
+
+#[test]
+fn derive_test() {
+    const CFG: Config = Config::CONFIG;
+
+    assert_eq!(CFG.name, "server");
+    assert_eq!(CFG.port, 8080);
+    assert_eq!(CFG.ratio, 0.5);
+    assert_eq!(CFG.small, -3);
+    assert_eq!(CFG.letter, 'x');
+    assert_eq!(CFG.tags, &["a", "b"]);
+    assert_eq!(CFG.matrix, &[&[1, 2][..], &[3, 4][..]]);
+    assert_eq!(CFG.fixed, [1, 2, 3]);
+    assert_eq!(CFG.blob, b"raw");
+    assert_eq!(CFG.renamed, Some(7));
+    assert_eq!(CFG.absent, None);
+    assert_eq!(
+        CFG.released,
+        Date {
+            year: 2024,
+            month: 1,
+            day: 2
+        }
+    );
+    assert_eq!(CFG.alarm.hour, 6);
+    assert_eq!(CFG.alarm.minute, 30);
+    assert_eq!(CFG.started.date.day, 2);
+    assert_eq!(CFG.started.time.second, 5);
+    assert_eq!(CFG.started.offset, Some(0));
+}
+
+#[test]
+fn nested_test() {
+    const CFG: Config = Config::CONFIG;
+
+    const { assert!(CFG.general.production) };
+    const { assert!(!CFG.general.trace_logging) };
+    assert_eq!(CFG.general.log_level, None);
+    assert_eq!(CFG.endpoints.len(), 2);
+    assert_eq!(CFG.endpoints[0].host, "a.example.com");
+    assert_eq!(CFG.endpoints[0].port, 443);
+    assert!(CFG.endpoints[0].weights.is_none());
+    assert_eq!(CFG.endpoints[1].host, "b.example.com");
+    assert_eq!(CFG.endpoints[1].port, 80);
+    assert_eq!(CFG.endpoints[1].weights.unwrap().len(), 2);
+    assert_eq!(CFG.endpoints[1].weights.unwrap()[1].as_u8(), 2);
+}
+
+#[test]
+fn nested_slice_test() {
+    const CFG: Config = Config::CONFIG;
+
+    assert_eq!(CFG.endpoints[0].aliases, None);
+    assert_eq!(CFG.endpoints[1].aliases, Some(&["b", "bee"][..]));
+    assert_eq!(CFG.endpoints[1].ports, Some(&[80, 8080][..]));
+    assert_eq!(CFG.endpoints[1].checksum, Some([1, 2]));
+    assert!(CFG.endpoints[0].tls.is_none());
+
+    let tls = CFG.endpoints[1].tls.as_ref().unwrap();
+    assert_eq!(tls.cert, "b.pem");
+    assert_eq!(tls.ciphers, &["aes", "chacha"]);
+    assert_eq!(tls.curves.len(), 1);
+    assert_eq!(tls.curves[0].name, "x25519");
+    assert_eq!(tls.curves[0].bits, 255);
+}
+
+#[test]
+fn include_test() {
+    const CFG: FileConfig = FileConfig::CONFIG;
+
+    assert_eq!(CFG.i, 42);
+    const { assert!(CFG.b) };
+    assert_eq!(CFG.s, "hello world");
+    assert_eq!(CFG.a.len(), 4);
+    assert_eq!(CFG.a[1].as_i8(), -2);
+    assert!(CFG.o.e.is_empty());
+    assert_eq!(CFG.o.d.year, 1970);
+    assert_eq!(CFG.o.t.nanosecond, 123_000_000);
+    assert!(CFG.o.u.as_object().is_empty());
+}
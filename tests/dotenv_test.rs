@@ -0,0 +1,44 @@
+#![cfg(feature = "dotenv")]
+
+use const_config::{Number, Value, from_dotenv};
+
+#[test]
+fn dotenv_test() {
+    const CFG: Value<'static> = from_dotenv!(
+        r#"
+# deploy defaults
+HOST=example.com
+export PORT=8080
+URL="https://${HOST}:$PORT/"
+GREETING='hello $HOST'   # single quotes are literal
+MOTD="line one
+line two\t!"
+DEBUG=false
+RATIO=0.5 # trailing comment
+OFFSET=-3
+"#
+    );
+    let cfg = CFG.as_object();
+    assert_eq!(cfg.len(), 8);
+    assert_eq!(cfg.get("PORT").as_str(), "8080");
+    assert_eq!(cfg.get("URL").as_str(), "https://example.com:8080/");
+    assert_eq!(cfg.get("GREETING").as_str(), "hello $HOST");
+    assert_eq!(cfg.get("MOTD").as_str(), "line one\nline two\t!");
+    assert_eq!(cfg.get("DEBUG").as_str(), "false");
+    assert_eq!(cfg.get("RATIO").as_str(), "0.5");
+}
+
+#[test]
+fn coerce_test() {
+    const CFG: Value<'static> = from_dotenv!(
+        "PORT=8080\nOFFSET=-3\nRATIO=0.5\nDEBUG=false\nHOST=example.com\nHEX=0x10",
+        coerce = true,
+    );
+    let cfg = CFG.as_object();
+    assert!(matches!(cfg.get("PORT"), Value::Number(Number::UInt(8080))));
+    assert!(matches!(cfg.get("OFFSET"), Value::Number(Number::Int(-3))));
+    assert_eq!(cfg.get("RATIO").as_f64(), 0.5);
+    assert!(matches!(cfg.get("DEBUG"), Value::Bool(false)));
+    assert_eq!(cfg.get("HOST").as_str(), "example.com");
+    assert_eq!(cfg.get("HEX").as_str(), "0x10");
+}
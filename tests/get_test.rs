@@ -0,0 +1,83 @@
+#![cfg(feature = "toml")]
+
+use const_config::{Date, Object, Value, from_toml, get};
+
+const CFG: Value<'static> = from_toml!(
+    r#"
+[general]
+production = true
+name = "server"
+released = 2024-01-02
+
+[networking]
+default-port = 443
+ratio = 0.25
+
+[[networking.endpoints]]
+host = "a.example.com"
+
+[[networking.endpoints]]
+host = "b.example.com"
+ports = [80, 8080]
+
+["a/b"]
+"c~d" = -7
+"#
+);
+
+const GENERAL: Object<'static> = get!(CFG, "general": Object);
+
+#[test]
+fn dotted_test() {
+    assert!(const { get!(CFG, "general.production": bool) });
+    assert_eq!(get!(CFG, "general.name": &str), "server");
+    assert_eq!(get!(CFG, "networking.default-port": u16), 443);
+    assert_eq!(get!(CFG, "networking.ratio": f64), 0.25);
+    assert_eq!(
+        get!(CFG, "general.released": Date),
+        Date {
+            year: 2024,
+            month: 1,
+            day: 2
+        }
+    );
+    assert_eq!(
+        get!(CFG, "networking.endpoints[1].host": &'static str),
+        "b.example.com"
+    );
+    assert_eq!(get!(CFG, "networking.endpoints[1].ports[1]": u32), 8080);
+    assert_eq!(
+        get!(CFG, "networking.endpoints.0.host": &str),
+        "a.example.com"
+    );
+    assert_eq!(get!(CFG, "networking.endpoints": &[Value]).len(), 2);
+}
+
+#[test]
+fn pointer_test() {
+    assert_eq!(get!(CFG, "/networking/default-port": u64), 443);
+    assert_eq!(get!(CFG, "/networking/endpoints/1/ports/0": u8), 80);
+    assert_eq!(get!(CFG, "/a~1b/c~0d": i8), -7);
+    assert!(get!(CFG, "": Value).is_object());
+}
+
+#[test]
+fn object_test() {
+    assert!(const { get!(GENERAL, "production": bool) });
+    assert_eq!(get!(GENERAL, "/name": &str), "server");
+}
+
+mod shadowed {
+    use const_config::get;
+
+    // The prelude's `Some` and `None` must not be used by `get!`.
+    #[allow(dead_code)]
+    struct Some<T>(T);
+    #[allow(dead_code, non_upper_case_globals)]
+    const None: () = ();
+
+    #[test]
+    fn shadowed_option_test() {
+        assert_eq!(get!(super::CFG, "networking.default-port": u16), 443);
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(all(feature = "ini", feature = "properties"))]
+
+use const_config::{Value, from_ini, from_properties};
+
+#[test]
+fn ini_test() {
+    const CFG: Value<'static> = from_ini!(
+        r#"
+; global settings
+name = app
+
+[server]
+host = example.com
+port: 8080
+banner = "  hello  "
+
+# sections can be reopened
+[server]
+port = 443
+"#
+    );
+    let cfg = CFG.as_object();
+    assert_eq!(cfg.get("name").as_str(), "app");
+    let server = cfg.get("server").as_object();
+    assert_eq!(server.len(), 3);
+    assert_eq!(server.get("host").as_str(), "example.com");
+    assert_eq!(server.get("port").as_str(), "443");
+    assert_eq!(server.get("banner").as_str(), "  hello  ");
+}
+
+#[test]
+fn properties_test() {
+    const CFG: Value<'static> = from_properties!(
+        r#"
+# comment
+! also a comment
+server.host = example.com
+server.port:8080
+greeting hello \
+         world
+path = C:\\temp\u00e9
+key\ with\ spaces = yes
+"#
+    );
+    let cfg = CFG.as_object();
+    assert_eq!(cfg.get("server.host").as_str(), "example.com");
+    assert_eq!(cfg.get("server.port").as_str(), "8080");
+    assert_eq!(cfg.get("greeting").as_str(), "hello world");
+    assert_eq!(cfg.get("path").as_str(), "C:\\temp\u{e9}");
+    assert_eq!(cfg.get("key with spaces").as_str(), "yes");
+}
+
+#[test]
+fn expand_keys_test() {
+    const CFG: Value<'static> = from_properties!(
+        "server.host = example.com\nserver.tls.port = 443\nname = app",
+        expand_keys = true,
+    );
+    let cfg = CFG.as_object();
+    assert_eq!(cfg.get("name").as_str(), "app");
+    let server = cfg.get("server").as_object();
+    assert_eq!(server.get("host").as_str(), "example.com");
+    assert_eq!(server.get("tls").as_object().get("port").as_str(), "443");
+
+    const INI: Value<'static> = from_ini!("[a.b]\nc.d = 1", expand_keys = true);
+    let d = INI
+        .as_object()
+        .get("a")
+        .as_object()
+        .get("b")
+        .as_object()
+        .get("c");
+    assert_eq!(d.as_object().get("d").as_str(), "1");
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "toml")]
+
+use const_config::{Value, from_toml};
+
+const CFG: Value<'static> = from_toml!(
+    r#"
+name = "${CARGO_PKG_NAME}"
+version = "v${CARGO_PKG_VERSION:?the package version is required}"
+data-dir = "${CONST_CONFIG_TEST_UNSET:-/var/lib/app}"
+empty = "[${CONST_CONFIG_TEST_UNSET}]"
+escaped = "$${CARGO_PKG_NAME}"
+paths = ["${CARGO_PKG_NAME}/a", "${CONST_CONFIG_TEST_UNSET-b}"]
+"#,
+    interpolate = true,
+);
+
+const RAW: Value<'static> = from_toml!(r#"name = "${CARGO_PKG_NAME}""#);
+
+#[test]
+fn interpolate_test() {
+    assert_eq!(CFG.path("name").map(Value::as_str), Some("const-config"));
+    assert_eq!(
+        CFG.path("version").map(Value::as_str),
+        Some(concat!("v", env!("CARGO_PKG_VERSION")))
+    );
+    assert_eq!(
+        CFG.path("data-dir").map(Value::as_str),
+        Some("/var/lib/app")
+    );
+    assert_eq!(CFG.path("empty").map(Value::as_str), Some("[]"));
+    assert_eq!(
+        CFG.path("escaped").map(Value::as_str),
+        Some("${CARGO_PKG_NAME}")
+    );
+    assert_eq!(
+        CFG.path("paths[0]").map(Value::as_str),
+        Some("const-config/a")
+    );
+    assert_eq!(CFG.path("paths[1]").map(Value::as_str), Some("b"));
+}
+
+#[test]
+fn opt_in_test() {
+    assert_eq!(
+        RAW.path("name").map(Value::as_str),
+        Some("${CARGO_PKG_NAME}")
+    );
+}
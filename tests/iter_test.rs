@@ -0,0 +1,88 @@
+#![cfg(all(feature = "json", feature = "yaml"))]
+
+use const_config::{Number, Value};
+
+const CFG: Value<'static> = const_config::from_json!(
+    r#"
+{
+    "ports": [80, 443, 8080],
+    "servers": {"web": 80, "api": 8080}
+}
+"#
+);
+
+const MAP: Value<'static> = const_config::from_yaml!("{1: one, 2: two}");
+
+const PORT_SUM: u32 = {
+    let mut ports = CFG.as_object().get("ports").iter();
+    let mut sum = 0;
+    while let Some(port) = ports.next() {
+        sum += port.as_u32();
+    }
+    sum
+};
+
+const SERVER_PORT_SUM: u32 = {
+    let mut servers = CFG.as_object().get("servers").as_object().iter();
+    let mut sum = 0;
+    while let Some((_, port)) = servers.next() {
+        sum += port.as_u32();
+    }
+    sum
+};
+
+#[test]
+fn const_cursors() {
+    assert_eq!(PORT_SUM, 8603);
+    assert_eq!(SERVER_PORT_SUM, 8160);
+
+    let mut ports = CFG.as_object().get("ports").iter();
+    assert_eq!(ports.remaining(), 3);
+    ports.next();
+    assert_eq!(ports.index(), 1);
+    assert_eq!(ports.remaining(), 2);
+    assert!(!ports.is_done());
+    ports.next();
+    ports.next();
+    assert!(ports.is_done());
+    assert_eq!(ports.next(), None);
+}
+
+#[test]
+fn iterators() {
+    let ports: Vec<u16> = CFG
+        .as_object()
+        .get("ports")
+        .iter()
+        .map(Value::as_u16)
+        .collect();
+    assert_eq!(ports, [80, 443, 8080]);
+
+    let servers = CFG.as_object().get("servers").as_object();
+    let keys: Vec<&str> = servers.keys().collect();
+    assert_eq!(keys, ["web", "api"]);
+    let values: Vec<u16> = servers.values().map(Value::as_u16).collect();
+    assert_eq!(values, [80, 8080]);
+    let mut count = 0;
+    for (key, value) in servers {
+        assert_eq!(servers.get(key), value);
+        count += 1;
+    }
+    assert_eq!(count, servers.len());
+    assert_eq!(CFG.as_object().iter().len(), 2);
+
+    let map = MAP.as_map();
+    let keys: Vec<&Value> = map.keys().collect();
+    assert_eq!(
+        keys,
+        [
+            &Value::Number(Number::UInt(1)),
+            &Value::Number(Number::UInt(2))
+        ]
+    );
+    let values: Vec<&str> = map.values().map(Value::as_str).collect();
+    assert_eq!(values, ["one", "two"]);
+    for (key, value) in &map {
+        assert_eq!(map.get(key), value);
+    }
+}
@@ -0,0 +1,17 @@
+#![cfg(feature = "jsonl")]
+
+use const_config::{Value, from_jsonl};
+
+#[test]
+fn jsonl_test() {
+    const EVENTS: Value<'static> = from_jsonl!(
+        r#"{"id": 1, "kind": "start"}
+
+{"id": 2, "kind": "stop", "tags": ["a"]}
+"#
+    );
+    let events = EVENTS.as_array();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0].as_object().get("id").as_u32(), 1);
+    assert_eq!(events[1].as_object().get("kind").as_str(), "stop");
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "kdl")]
+
+use const_config::{Date, Number, Value, from_kdl};
+
+#[test]
+fn kdl_test() {
+    const DOC: Value<'static> = from_kdl!(
+        r#"
+// deployment settings
+server "web" port=8080 tls=#true {
+    route "/" handler=index
+    route "/api" handler=api /- disabled=#true
+}
+/- server "old"
+release (date)"2024-01-02" (u8)3 ratio=0.5 note=#null
+"#
+    );
+    let nodes = DOC.as_array();
+    assert_eq!(nodes.len(), 2);
+
+    let server = nodes[0].as_object();
+    assert_eq!(server.get("name").as_str(), "server");
+    assert_eq!(server.get("args").as_array()[0].as_str(), "web");
+    assert!(matches!(
+        server.get("port"),
+        Value::Number(Number::UInt(8080))
+    ));
+    assert!(server.get("tls").as_bool());
+
+    let routes = server.get("children").as_array();
+    assert_eq!(routes.len(), 2);
+    let api = routes[1].as_object();
+    assert_eq!(api.get("args").as_array()[0].as_str(), "/api");
+    assert_eq!(api.get("handler").as_str(), "api");
+    assert!(!api.contains("disabled"));
+
+    let release = nodes[1].as_object();
+    let args = release.get("args").as_array();
+    assert_eq!(
+        args[0].try_as_date(),
+        Some(Date {
+            year: 2024,
+            month: 1,
+            day: 2
+        })
+    );
+    assert!(matches!(args[1], Value::Number(Number::UInt(3))));
+    assert_eq!(release.get("ratio").as_f64(), 0.5);
+    assert!(release.get("note").is_null());
+    assert!(!release.contains("children"));
+}
@@ -0,0 +1,47 @@
+#![cfg(all(feature = "toml", feature = "json"))]
+
+use const_config::{Value, include_layered};
+
+const REPLACE: Value<'static> = include_layered!(
+    "layered/base.toml",
+    "layered/prod.json",
+    "layered/local.toml"
+);
+
+const APPEND: Value<'static> =
+    include_layered!("layered/base.toml", "layered/prod.json", arrays = "append",);
+
+#[test]
+fn replace_test() {
+    const CFG: Value<'static> = REPLACE;
+
+    assert_eq!(CFG.path("name").map(Value::as_str), Some("app"));
+    assert_eq!(
+        CFG.path("server.host").map(Value::as_str),
+        Some("example.com")
+    );
+    assert_eq!(CFG.path("server.port").map(Value::as_u16), Some(8080));
+    assert_eq!(CFG.path("server.debug"), Some(&Value::Bool(false)));
+    assert_eq!(
+        CFG.path("server.tls.cert").map(Value::as_str),
+        Some("/etc/tls/cert.pem")
+    );
+    assert_eq!(CFG.path("limits.connections").map(Value::as_u32), Some(100));
+    assert_eq!(CFG.path("limits.burst").map(Value::as_u32), Some(5));
+
+    let tags = CFG.as_object().get("tags").as_array();
+    assert_eq!(tags.len(), 1);
+    assert_eq!(tags[0].as_str(), "prod");
+}
+
+#[test]
+fn append_test() {
+    const CFG: Value<'static> = APPEND;
+
+    let tags = CFG.as_object().get("tags").as_array();
+    assert_eq!(tags.len(), 2);
+    assert_eq!(tags[0].as_str(), "base");
+    assert_eq!(tags[1].as_str(), "prod");
+    assert_eq!(CFG.path("limits.connections").map(Value::as_u32), Some(10));
+    assert_eq!(CFG.path("limits.burst"), None);
+}
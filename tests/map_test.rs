@@ -0,0 +1,33 @@
+#![cfg(feature = "yaml")]
+
+use const_config::{Number, Value, from_yaml};
+
+const MAP: Value<'static> = from_yaml!(
+    r#"
+{
+    1: one, 2: two, 3: three, 4: four, 5: five, 6: six, 7: seven, 8: eight,
+    -1: minus one, 0.5: half, true: yes, false: no, null: nothing,
+    a: 10, b: 11, c: 12, [1, 2]: pair, {x: 1}: object,
+}
+"#
+);
+
+#[test]
+fn lookup_every_key() {
+    let map = MAP.as_map();
+    assert_eq!(map.len(), 18);
+    for (key, value) in map.entries() {
+        assert_eq!(map.try_get(key), Some(value), "lookup of {key:?}");
+    }
+}
+
+#[test]
+fn lookup_by_type() {
+    let map = MAP.as_map();
+    assert_eq!(map.get_u8(1).as_str(), "one");
+    assert_eq!(map.get_i32(-1).as_str(), "minus one");
+    assert_eq!(map.get_number(Number::Float(0.5)).as_str(), "half");
+    assert_eq!(map.get_bool(true).as_str(), "yes");
+    assert_eq!(map.get_str("c").as_u8(), 12);
+    assert_eq!(map.try_get_u8(9), None);
+}
@@ -0,0 +1,77 @@
+#![cfg(all(
+    feature = "toml",
+    feature = "yaml",
+    feature = "ron",
+    feature = "json5",
+    feature = "cbor",
+    feature = "msgpack",
+    feature = "pickle",
+    feature = "bson",
+))]
+
+use const_config::{Number, Value};
+
+fn assert_non_finite(values: &[Value]) {
+    assert_eq!(values.len(), 3);
+    assert!(values[0].as_f64().is_nan());
+    assert_eq!(values[1].as_f64(), f64::INFINITY);
+    assert_eq!(values[2].as_f64(), f64::NEG_INFINITY);
+}
+
+#[test]
+fn text_formats() {
+    const TOML: Value<'static> = const_config::from_toml!("a = [nan, inf, -inf]");
+    const YAML: Value<'static> = const_config::from_yaml!("[.nan, .inf, -.inf]");
+    const RON: Value<'static> = const_config::from_ron!("[NaN, inf, -inf]");
+    const JSON5: Value<'static> = const_config::from_json5!("[NaN, Infinity, -Infinity]");
+
+    assert_non_finite(TOML.as_object().get("a").as_array());
+    assert_non_finite(YAML.as_array());
+    assert_non_finite(RON.as_array());
+    assert_non_finite(JSON5.as_array());
+}
+
+#[test]
+fn binary_formats() {
+    const CBOR: Value<'static> =
+        const_config::from_cbor!(b"\x83\xf9\x7e\x00\xf9\x7c\x00\xf9\xfc\x00");
+    const MSGPACK: Value<'static> = const_config::from_msgpack!(
+        b"\x93\xcb\x7f\xf8\x00\x00\x00\x00\x00\x00\xcb\x7f\xf0\x00\x00\x00\x00\x00\x00\xcb\xff\xf0\x00\x00\x00\x00\x00\x00"
+    );
+    const PICKLE: Value<'static> = const_config::from_pickle!(
+        b"\x80\x02\x5d\x28\x47\x7f\xf8\x00\x00\x00\x00\x00\x00\x47\x7f\xf0\x00\x00\x00\x00\x00\x00\x47\xff\xf0\x00\x00\x00\x00\x00\x00\x65\x2e"
+    );
+    const BSON: Value<'static> = const_config::from_bson!(
+        b"\x2e\x00\x00\x00\x04\x61\x00\x26\x00\x00\x00\x01\x30\x00\x00\x00\x00\x00\x00\x00\xf8\x7f\x01\x31\x00\x00\x00\x00\x00\x00\x00\xf0\x7f\x01\x32\x00\x00\x00\x00\x00\x00\x00\xf0\xff\x00\x00"
+    );
+
+    assert_non_finite(CBOR.as_array());
+    assert_non_finite(MSGPACK.as_array());
+    assert_non_finite(PICKLE.as_array());
+    assert_non_finite(BSON.as_object().get("a").as_array());
+}
+
+#[test]
+fn number_semantics() {
+    let nan = Number::Float(f64::NAN);
+    assert_ne!(nan, nan);
+    assert_ne!(nan, Number::UInt(0));
+    assert_eq!(nan.partial_cmp(&nan), None);
+    assert_eq!(nan.partial_cmp(&Number::Int(-1)), None);
+    assert!(Number::Float(f64::INFINITY) > Number::UInt(u128::MAX));
+    assert!(Number::Float(f64::NEG_INFINITY) < Number::Int(i128::MIN));
+    assert_eq!(Number::Float(-0.0), Number::UInt(0));
+}
+
+#[test]
+fn nan_keys() {
+    const MAP: Value<'static> =
+        const_config::from_yaml!("{.nan: first, 1: one, .NaN: last, -.inf: neg}");
+
+    let map = MAP.as_map();
+    assert_eq!(map.len(), 3);
+    assert_eq!(map.get_number(Number::Float(f64::NAN)).as_str(), "last");
+    assert_eq!(map.get_number(Number::Float(-f64::NAN)).as_str(), "last");
+    assert_eq!(map.get_f64(f64::NEG_INFINITY).as_str(), "neg");
+    assert_eq!(map.get_u8(1).as_str(), "one");
+}
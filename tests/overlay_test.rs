@@ -0,0 +1,168 @@
+#![cfg(all(feature = "std", feature = "toml"))]
+
+use const_config::{Date, Overlay, OverlayError, Value};
+
+const CFG: Value<'static> = const_config::from_toml!(
+    r#"
+name = "app"
+since = 2024-01-02
+ratio = 0.5
+
+[networking]
+default-port = 443
+allow-insecure = false
+
+[[servers]]
+host = "localhost"
+
+[[servers]]
+host = "example.com"
+"#
+);
+
+#[test]
+fn no_overrides() {
+    let overlay = Overlay::builder(CFG).build().unwrap();
+    assert!(!overlay.is_overridden());
+    assert_eq!(overlay.get("name").as_str(), "app");
+    assert_eq!(
+        overlay.path("networking.default-port").unwrap().as_u16(),
+        443
+    );
+}
+
+/// Checks everything read from environment variables. Setting them is only
+/// sound while no other thread reads the environment, so this is the only
+/// test that sets or reads them, and it sets all of them before reading any.
+#[test]
+fn env_overrides() {
+    // SAFETY: no other test reads the environment, see above.
+    unsafe {
+        std::env::set_var("ENV_TEST_NETWORKING__ALLOW_INSECURE", "true");
+        std::env::set_var("ENV_TEST_SERVERS__0__HOST", "env.example.com");
+        std::env::set_var("ENV_TEST_SINCE", "2025-03-04");
+        std::env::set_var("OVERLAY_NAME", "env app");
+        std::env::set_var("OVERLAY_RATIO", "0.75");
+    }
+
+    let overlay = Overlay::builder(CFG)
+        .env_prefix("ENV_TEST_")
+        .build()
+        .unwrap();
+    let networking = overlay.get("networking");
+    assert!(networking.get("allow-insecure").as_bool());
+    assert!(networking.get("allow-insecure").is_overridden());
+    assert!(!networking.get("default-port").is_overridden());
+    assert_eq!(
+        overlay.get("servers").at(0).get("host").as_str(),
+        "env.example.com"
+    );
+    assert_eq!(
+        overlay.path("servers[1].host").unwrap().as_str(),
+        "example.com"
+    );
+    assert_eq!(
+        overlay.get("since").as_date(),
+        Date {
+            year: 2025,
+            month: 3,
+            day: 4
+        }
+    );
+
+    // Arguments take precedence over the environment, and the environment
+    // over the file.
+    let overlay = Overlay::builder(CFG)
+        .env_prefix("OVERLAY_")
+        .file("tests/overlay/override.env")
+        .args(["OVERLAY_RATIO=0.25"])
+        .build()
+        .unwrap();
+    assert_eq!(overlay.get("networking").get("default-port").as_u16(), 8080);
+    assert_eq!(overlay.get("name").as_str(), "env app");
+    assert_eq!(overlay.get("ratio").as_f64(), 0.25);
+    assert_eq!(
+        overlay.path("servers.1.host").unwrap().as_str(),
+        "file.example.com"
+    );
+}
+
+#[test]
+fn errors() {
+    let err = Overlay::builder(CFG)
+        .args(["NETWORKING__DEFAULT_PORT=http"])
+        .build()
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        OverlayError::Mismatch {
+            expected: "an integer",
+            ..
+        }
+    ));
+    assert_eq!(
+        err.to_string(),
+        "`NETWORKING__DEFAULT_PORT`: expected an integer, found `http`"
+    );
+
+    let err = Overlay::builder(CFG)
+        .args(["NETWORKING__PORT=80"])
+        .build()
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`NETWORKING__PORT` does not override any value"
+    );
+
+    let err = Overlay::builder(CFG).args(["NAME"]).build().unwrap_err();
+    assert!(matches!(err, OverlayError::InvalidArg { .. }));
+
+    // A missing file is not an error.
+    Overlay::builder(CFG)
+        .file("tests/overlay/missing.env")
+        .build()
+        .unwrap();
+}
+
+#[test]
+fn out_of_range() {
+    let overlay = Overlay::builder(CFG)
+        .args(["NETWORKING__DEFAULT_PORT=70000"])
+        .build()
+        .unwrap();
+    let port = overlay.get("networking").get("default-port");
+    assert_eq!(port.try_as_u16(), None);
+    assert_eq!(port.as_u32(), 70000);
+}
+
+#[test]
+fn collisions() {
+    const CFG: Value<'static> = const_config::from_toml!(
+        r#"
+default-port = 443
+default_port = 80
+"#
+    );
+    let err = Overlay::builder(CFG).build().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`DEFAULT_PORT` names both `default-port` and `default_port`"
+    );
+}
+
+#[test]
+fn nested_lookups() {
+    let overlay = Overlay::builder(CFG)
+        .args(["SERVERS__1__HOST=args.example.com"])
+        .build()
+        .unwrap();
+    let servers = overlay.get("servers");
+    assert!(servers.is_array());
+    let servers = servers.as_array();
+    assert_eq!(servers.len(), 2);
+    assert_eq!(servers[0].get("host").as_str(), "localhost");
+    assert_eq!(servers[1].get("host").as_str(), "args.example.com");
+    assert!(servers[1].get("host").is_str());
+    assert!(overlay.get("networking").get("default-port").is_integral());
+    assert_eq!(overlay.get("networking").try_as_unit(), None);
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "toml")]
+
+use const_config::{Value, from_toml};
+
+const CFG: Value<'static> = from_toml!(
+    r#"
+[networking]
+default-port = 443
+
+[[networking.endpoints]]
+host = "a.example.com"
+port = 80
+
+[[networking.endpoints]]
+host = "b.example.com"
+port = 8080
+
+["a/b"]
+"c~d" = true
+"e.f" = 1
+"#
+);
+
+#[test]
+fn pointer_test() {
+    const PORT: Option<&Value<'static>> = CFG.pointer("/networking/endpoints/1/port");
+
+    assert_eq!(PORT.map(Value::as_u16), Some(8080));
+    assert_eq!(CFG.pointer(""), Some(&CFG));
+    assert!(CFG.pointer("/networking").unwrap().is_object());
+    assert_eq!(CFG.pointer("/a~1b/c~0d"), Some(&Value::Bool(true)));
+    assert_eq!(CFG.pointer("/a~1b/e.f").map(Value::as_u8), Some(1));
+    assert_eq!(CFG.pointer("/networking/endpoints/2"), None);
+    assert_eq!(CFG.pointer("/networking/endpoints/01"), None);
+    assert_eq!(CFG.pointer("/networking/endpoints/-"), None);
+    assert_eq!(CFG.pointer("/networking/missing"), None);
+    assert_eq!(CFG.pointer("/networking/default-port/x"), None);
+    assert_eq!(CFG.pointer("networking"), None);
+    assert_eq!(CFG.pointer("/a~2b"), None);
+}
+
+#[test]
+fn path_test() {
+    const HOST: Option<&Value<'static>> = CFG.path("networking.endpoints[0].host");
+
+    assert_eq!(HOST.map(Value::as_str), Some("a.example.com"));
+    assert_eq!(CFG.path(""), Some(&CFG));
+    assert_eq!(
+        CFG.path("networking.endpoints.1.host").map(Value::as_str),
+        Some("b.example.com")
+    );
+    assert_eq!(CFG.path("a/b.c~d"), Some(&Value::Bool(true)));
+    assert_eq!(CFG.path("networking.endpoints[2]"), None);
+    assert_eq!(CFG.path("networking.endpoints[x]"), None);
+    assert_eq!(CFG.path("networking.endpoints[0"), None);
+    assert_eq!(CFG.path("networking..endpoints"), None);
+    assert_eq!(CFG.path("networking."), None);
+    assert_eq!(CFG.path("networking[0]"), None);
+    assert_eq!(CFG.path("/networking"), None);
+}
+
+#[test]
+fn object_test() {
+    const NETWORKING: Value<'static> = *CFG.as_object().get("networking");
+
+    let obj = NETWORKING.as_object();
+    assert_eq!(obj.pointer("/default-port").map(Value::as_u16), Some(443));
+    assert_eq!(obj.path("endpoints[1].port").map(Value::as_u16), Some(8080));
+    assert_eq!(obj.pointer(""), None);
+    assert_eq!(obj.path(""), None);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn map_test() {
+    const MAP: Value<'static> = const_config::from_yaml!(
+        r#"
+1: one
+name: two
+nested:
+  2: three
+"#
+    );
+
+    assert!(MAP.try_as_map().is_some());
+    assert_eq!(MAP.pointer("/1").map(Value::as_str), Some("one"));
+    assert_eq!(MAP.path("name").map(Value::as_str), Some("two"));
+    assert_eq!(MAP.path("nested[2]").map(Value::as_str), Some("three"));
+    assert_eq!(
+        MAP.as_map().pointer("/nested/2").map(Value::as_str),
+        Some("three")
+    );
+    assert_eq!(MAP.as_map().path("3"), None);
+}
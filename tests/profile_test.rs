@@ -0,0 +1,55 @@
+#![cfg(feature = "toml")]
+
+use const_config::{Value, from_toml, include_toml};
+
+const PROD: Value<'static> = include_toml!("profile.toml", profile = "prod");
+const DEV: Value<'static> = include_toml!("profile.toml", profile = "dev");
+const NONE: Value<'static> = include_toml!(
+    "profile.toml",
+    profile = option_env!("CONST_CONFIG_TEST_PROFILE_UNSET"),
+);
+const BASE: Value<'static> = include_toml!("profile.toml", profile = None);
+const PKG: Value<'static> = from_toml!(
+    r#"
+    name = "app"
+
+    [profile.const-config]
+    name = "lib"
+    "#,
+    profile = env!("CARGO_PKG_NAME"),
+);
+
+#[test]
+fn profile_test() {
+    assert_eq!(PROD.path("name").map(Value::as_str), Some("app"));
+    assert_eq!(PROD.path("log-level").map(Value::as_str), Some("info"));
+    assert_eq!(
+        PROD.path("server.host").map(Value::as_str),
+        Some("example.com")
+    );
+    assert_eq!(PROD.path("server.port").map(Value::as_u16), Some(8080));
+    assert_eq!(PROD.path("profile"), None);
+
+    assert_eq!(DEV.path("log-level").map(Value::as_str), Some("debug"));
+    assert_eq!(
+        DEV.path("server.host").map(Value::as_str),
+        Some("localhost")
+    );
+    assert_eq!(DEV.path("profile"), None);
+}
+
+#[test]
+fn env_profile_test() {
+    assert_eq!(NONE.path("log-level").map(Value::as_str), Some("info"));
+    assert_eq!(NONE.path("profile"), None);
+    assert_eq!(NONE.as_object().len(), 3);
+
+    assert_eq!(PKG.path("name").map(Value::as_str), Some("lib"));
+}
+
+#[test]
+fn no_profile_test() {
+    assert_eq!(BASE.path("log-level").map(Value::as_str), Some("info"));
+    assert_eq!(BASE.path("profile"), None);
+    assert_eq!(BASE.as_object().len(), 3);
+}
@@ -0,0 +1,24 @@
+#![cfg(all(feature = "json", feature = "yaml"))]
+
+use const_config::{Value, from_json, from_yaml};
+
+#[test]
+fn object_keys() {
+    const CFG: Value<'static> = from_json!(r#"{"a": 1, "b": 2, "a": 3}"#);
+
+    let obj = CFG.as_object();
+    assert_eq!(obj.len(), 2);
+    assert_eq!(obj.entries()[0].0, "a");
+    assert_eq!(obj.get("a").as_u8(), 3);
+    assert_eq!(obj.get("b").as_u8(), 2);
+}
+
+#[test]
+fn map_keys() {
+    const CFG: Value<'static> = from_yaml!("{1: first, 2: two, 1.0: last}");
+
+    let map = CFG.as_map();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get_u8(1).as_str(), "last");
+    assert_eq!(map.get_u8(2).as_str(), "two");
+}
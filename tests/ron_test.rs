@@ -0,0 +1,25 @@
+#![cfg(feature = "ron")]
+
+use const_config::{Value, from_ron};
+
+const CFG: Value<'static> = from_ron!(
+    r#"
+{
+    "name": "server",
+    "port": 443,
+    "ratio": 0.25,
+    "hosts": ["a.example.com", "b.example.com"],
+    "limits": {1: "one", 2: "two"},
+}
+"#
+);
+
+#[test]
+fn parse_test() {
+    let obj = CFG.as_object();
+    assert_eq!(obj.get("name").as_str(), "server");
+    assert_eq!(obj.get("port").as_u16(), 443);
+    assert_eq!(obj.get("ratio").as_f64(), 0.25);
+    assert_eq!(obj.get("hosts").as_array().len(), 2);
+    assert_eq!(obj.get("limits").as_map().get_u8(2).as_str(), "two");
+}
@@ -0,0 +1,34 @@
+#![cfg(all(feature = "toml", feature = "json"))]
+
+use const_config::{Value, from_toml, include_layered, include_toml};
+
+const PROD: Value<'static> = include_toml!(
+    "profile.toml",
+    profile = "prod",
+    schema = "schema/app.schema.json",
+);
+const INLINE: Value<'static> = from_toml!(
+    r#"
+    name = "inline"
+
+    [server]
+    host = "localhost"
+    port = 443
+    "#,
+    schema = "schema/app.schema.json",
+);
+const LAYERED: Value<'static> = include_layered!(
+    "layered/base.toml",
+    "layered/prod.json",
+    schema = "schema/layered.schema.json",
+);
+
+#[test]
+fn schema_test() {
+    assert_eq!(
+        PROD.path("server.host").map(Value::as_str),
+        Some("example.com")
+    );
+    assert_eq!(INLINE.path("server.port").map(Value::as_u16), Some(443));
+    assert!(LAYERED.try_as_object().is_some());
+}
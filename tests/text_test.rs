@@ -0,0 +1,97 @@
+#![cfg(all(feature = "toml", feature = "json", feature = "yaml"))]
+
+use const_config::{Number, Value};
+
+const CFG: Value<'static> = const_config::from_toml!(
+    r#"
+name = "app"
+ratio = 0.5
+released = 2024-01-02
+start = 08:30:00.250
+updated = 2024-01-02T08:30:00Z
+tags = ["a", "b\n"]
+
+[networking]
+port = 443
+
+[[servers]]
+host = "web"
+
+[[servers]]
+host = "api"
+"#
+);
+
+fn json(value: &Value) -> String {
+    let mut out = String::new();
+    value.write_json(&mut out).unwrap();
+    out
+}
+
+fn toml(value: &Value) -> String {
+    let mut out = String::new();
+    value.write_toml(&mut out).unwrap();
+    out
+}
+
+#[test]
+fn compact_json() {
+    assert_eq!(
+        json(&CFG),
+        concat!(
+            r#"{"name":"app","ratio":0.5,"released":"2024-01-02","start":"08:30:00.250","#,
+            r#""updated":"2024-01-02T08:30:00Z","tags":["a","b\n"],"networking":{"port":443},"#,
+            r#""servers":[{"host":"web"},{"host":"api"}]}"#,
+        )
+    );
+    assert_eq!(json(&Value::Bytes(&[1, 2, 255])), "[1,2,255]");
+    assert_eq!(json(&Value::Number(Number::Float(f64::NAN))), "null");
+    assert_eq!(json(&Value::Str("\u{1}")), r#""\u0001""#);
+}
+
+#[test]
+fn pretty_json() {
+    const VALUE: Value<'static> = const_config::from_json!(r#"{"a": [1, {}], "b": []}"#);
+    let mut out = String::new();
+    VALUE.write_json_pretty(&mut out).unwrap();
+    assert_eq!(out, "{\n  \"a\": [\n    1,\n    {}\n  ],\n  \"b\": []\n}");
+}
+
+#[test]
+fn json_map_keys() {
+    const MAP: Value<'static> = const_config::from_yaml!("{1: one, [2, 3]: two}");
+    assert_eq!(json(&MAP), r#"{"1":"one","[2,3]":"two"}"#);
+}
+
+#[test]
+fn toml_document() {
+    assert_eq!(
+        toml(&CFG),
+        concat!(
+            "name = \"app\"\n",
+            "ratio = 0.5\n",
+            "released = 2024-01-02\n",
+            "start = 08:30:00.250\n",
+            "updated = 2024-01-02T08:30:00Z\n",
+            "tags = [\"a\", \"b\\n\"]\n",
+            "\n",
+            "[networking]\n",
+            "port = 443\n",
+            "\n",
+            "[[servers]]\n",
+            "host = \"web\"\n",
+            "\n",
+            "[[servers]]\n",
+            "host = \"api\"\n",
+        )
+    );
+}
+
+#[test]
+fn compact_toml() {
+    const VALUE: Value<'static> =
+        const_config::from_yaml!("{name: app, skip: null, net: {port: 443, tags: [a]}, 1: x}");
+    let mut out = String::new();
+    VALUE.write_toml_compact(&mut out).unwrap();
+    assert_eq!(out, "name=\"app\"\nnet={port=443,tags=[\"a\"]}\n1=\"x\"\n");
+}
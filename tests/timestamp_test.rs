@@ -0,0 +1,71 @@
+#![cfg(all(
+    feature = "yaml",
+    feature = "json",
+    feature = "json5",
+    feature = "hjson"
+))]
+
+use const_config::{Date, DateTime, Time, Value, from_hjson, from_json, from_json5, from_yaml};
+
+const DATE: Date = Date {
+    year: 2024,
+    month: 1,
+    day: 2,
+};
+
+#[test]
+fn yaml_test() {
+    const CFG: Value<'static> = from_yaml!(
+        "
+released: 2024-01-02
+quoted: '2024-01-02'
+starts: 08:30:00
+deployed: 2001-12-14 21:59:43.10 -5
+name: app
+",
+        timestamps = true,
+    );
+    let cfg = CFG.as_object();
+    assert_eq!(cfg.get("released").try_as_date(), Some(DATE));
+    assert_eq!(cfg.get("quoted").try_as_date(), Some(DATE));
+    assert_eq!(
+        cfg.get("starts").try_as_time(),
+        Some(Time {
+            hour: 8,
+            minute: 30,
+            second: 0,
+            nanosecond: 0,
+        })
+    );
+    assert_eq!(
+        cfg.get("deployed").try_as_datetime(),
+        Some(DateTime {
+            date: Date {
+                year: 2001,
+                month: 12,
+                day: 14,
+            },
+            time: Time {
+                hour: 21,
+                minute: 59,
+                second: 43,
+                nanosecond: 100_000_000,
+            },
+            offset: Some(-300),
+        })
+    );
+    assert_eq!(cfg.get("name").as_str(), "app");
+}
+
+#[test]
+fn json_test() {
+    const JSON: Value<'static> = from_json!(r#"["2024-01-02"]"#, timestamps = true);
+    const JSON5: Value<'static> = from_json5!("['2024-01-02']", timestamps = true);
+    const HJSON: Value<'static> = from_hjson!("[\"2024-01-02\"]", timestamps = true);
+    const OFF: Value<'static> = from_json!(r#"["2024-01-02"]"#);
+
+    assert_eq!(JSON.as_array()[0].try_as_date(), Some(DATE));
+    assert_eq!(JSON5.as_array()[0].try_as_date(), Some(DATE));
+    assert_eq!(HJSON.as_array()[0].try_as_date(), Some(DATE));
+    assert_eq!(OFF.as_array()[0].try_as_date(), None);
+}
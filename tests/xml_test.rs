@@ -0,0 +1,47 @@
+#![cfg(feature = "xml")]
+
+use const_config::{Number, Value, from_xml};
+
+#[test]
+fn xml_test() {
+    const DEVICE: Value<'static> = from_xml!(
+        r#"<?xml version="1.0"?>
+<!-- vendor description -->
+<device id="sensor-1" version="2">
+    <name>Thermometer</name>
+    <port>8080</port>
+    <channel index="0">temperature</channel>
+    <channel index="1">humidity</channel>
+    <reserved/>
+    <note><![CDATA[<raw> text]]></note>
+</device>
+"#
+    );
+    let device = DEVICE.as_object().get("device").as_object();
+    assert_eq!(device.get("@id").as_str(), "sensor-1");
+    assert_eq!(device.get("name").as_str(), "Thermometer");
+    assert_eq!(device.get("port").as_str(), "8080");
+    assert!(device.get("reserved").is_null());
+    assert_eq!(device.get("note").as_str(), "<raw> text");
+
+    let channels = device.get("channel").as_array();
+    assert_eq!(channels.len(), 2);
+    assert_eq!(channels[1].as_object().get("@index").as_str(), "1");
+    assert_eq!(channels[1].as_object().get("#text").as_str(), "humidity");
+}
+
+#[test]
+fn options_test() {
+    const DEVICE: Value<'static> = from_xml!(
+        r#"<dev:device xmlns:dev="urn:example:device" dev:id="7"><dev:port>8080</dev:port><enabled>true</enabled></dev:device>"#,
+        namespaces = "strip",
+        coerce = true,
+    );
+    let device = DEVICE.as_object().get("device").as_object();
+    assert!(matches!(device.get("@id"), Value::Number(Number::UInt(7))));
+    assert!(matches!(
+        device.get("port"),
+        Value::Number(Number::UInt(8080))
+    ));
+    assert!(matches!(device.get("enabled"), Value::Bool(true)));
+}